@@ -0,0 +1,68 @@
+use std::{collections::HashMap, str::FromStr};
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::Result;
+
+/// The positional arguments and `--name value` options of a command line.
+#[derive(Debug, Default)]
+pub struct Args {
+    pub positional: Vec<String>,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    pub fn parse(args: &[String]) -> Result<Self> {
+        let mut parsed = Args::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.strip_prefix("--") {
+                Some(name) => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("missing value for --{name}"))?;
+                    if parsed
+                        .options
+                        .insert(name.to_string(), value.clone())
+                        .is_some()
+                    {
+                        return Err(format!("--{name} given more than once").into());
+                    }
+                }
+                None => parsed.positional.push(arg.clone()),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    pub fn required(&self, name: &str) -> Result<&str> {
+        self.get(name)
+            .ok_or_else(|| format!("missing required option --{name}").into())
+    }
+
+    /// Parses the value of an option, `None` when it is not given.
+    pub fn parse_optional<T: FromStr>(&self, name: &str) -> Result<Option<T>> {
+        self.get(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("invalid value for --{name}: {value}").into())
+            })
+            .transpose()
+    }
+
+    pub fn parse_required<T: FromStr>(&self, name: &str) -> Result<T> {
+        self.parse_optional(name)?
+            .ok_or_else(|| format!("missing required option --{name}").into())
+    }
+
+    pub fn pubkey(&self, name: &str) -> Result<Pubkey> {
+        self.parse_required(name)
+    }
+}
@@ -0,0 +1,225 @@
+use std::fs;
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use pinocchio_timebase_vault::client::{self, VaultAccount};
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    transaction::Transaction,
+};
+
+use crate::{args::Args, Result};
+
+pub const USAGE: &str = "\
+Usage: timebase-vault <COMMAND> [OPTIONS]
+
+Commands:
+  address        Derive a vault address
+                 --owner <PUBKEY> --amount <U64> --unlock-timestamp <I64> [--mint <PUBKEY>]
+  init-sol       Build a transaction locking SOL
+                 --amount <U64> --unlock-timestamp <I64> --treasury <PUBKEY> --blockhash <HASH>
+                 [--early-exit-penalty-bps <U16>]
+  init-spl       Build a transaction locking SPL tokens
+                 --mint <PUBKEY> --amount <U64> --unlock-timestamp <I64> --treasury <PUBKEY>
+                 --blockhash <HASH> [--early-exit-penalty-bps <U16>]
+  withdraw-sol   Build a transaction withdrawing a SOL vault
+                 --vault <PUBKEY> --blockhash <HASH> [--creator <PUBKEY>]
+  withdraw-spl   Build a transaction withdrawing an SPL vault
+                 --vault <PUBKEY> --mint <PUBKEY> --blockhash <HASH> [--creator <PUBKEY>]
+  sign           Sign a transaction built by another command
+                 --transaction <BASE64> --keypair <PATH>
+  show-vault     Print a vault account
+                 --file <PATH> | --base64 <BASE64>
+
+The transaction commands take the vault owner from --owner <PUBKEY> or
+--keypair <PATH>. With --keypair the transaction is signed, otherwise it is
+printed unsigned. Transactions are printed as base64.
+
+A vault with a receipt token is withdrawn by the holder of the receipt: pass
+the holder as --owner or --keypair and the owner the vault was created by as
+--creator.";
+
+pub fn run(args: &[String]) -> Result<String> {
+    let args = Args::parse(args)?;
+
+    match args.positional.as_slice() {
+        [command] => match command.as_str() {
+            "address" => address(&args),
+            "init-sol" => init_sol(&args),
+            "init-spl" => init_spl(&args),
+            "withdraw-sol" => withdraw_sol(&args),
+            "withdraw-spl" => withdraw_spl(&args),
+            "sign" => sign(&args),
+            "show-vault" => show_vault(&args),
+            "help" => Ok(USAGE.to_string()),
+            _ => Err(format!("unknown command {command}\n\n{USAGE}").into()),
+        },
+        _ => Err(USAGE.into()),
+    }
+}
+
+fn address(args: &Args) -> Result<String> {
+    let owner = args.pubkey("owner")?;
+    let mint = args.parse_optional::<Pubkey>("mint")?;
+    let amount = args.parse_required("amount")?;
+    let unlock_timestamp = args.parse_required("unlock-timestamp")?;
+
+    let (vault, bump) = client::find_vault_address(&owner, mint.as_ref(), amount, unlock_timestamp);
+
+    Ok(format!("vault: {vault}\nbump: {bump}"))
+}
+
+fn init_sol(args: &Args) -> Result<String> {
+    let (owner, keypair) = owner(args)?;
+    let amount = args.parse_required("amount")?;
+    let unlock_timestamp = args.parse_required("unlock-timestamp")?;
+    let early_exit_penalty_bps = args
+        .parse_optional("early-exit-penalty-bps")?
+        .unwrap_or_default();
+    let treasury = args.pubkey("treasury")?;
+
+    let (vault, _) = client::find_vault_address(&owner, None, amount, unlock_timestamp);
+    let instruction = client::init_sol_vault(
+        &owner,
+        amount,
+        unlock_timestamp,
+        early_exit_penalty_bps,
+        &treasury,
+    );
+
+    let transaction = transaction(args, instruction, &owner, keypair)?;
+    Ok(format!("vault: {vault}\ntransaction: {transaction}"))
+}
+
+fn init_spl(args: &Args) -> Result<String> {
+    let (owner, keypair) = owner(args)?;
+    let mint = args.pubkey("mint")?;
+    let amount = args.parse_required("amount")?;
+    let unlock_timestamp = args.parse_required("unlock-timestamp")?;
+    let early_exit_penalty_bps = args
+        .parse_optional("early-exit-penalty-bps")?
+        .unwrap_or_default();
+    let treasury = args.pubkey("treasury")?;
+
+    let (vault, _) = client::find_vault_address(&owner, Some(&mint), amount, unlock_timestamp);
+    let instruction = client::init_spl_vault(
+        &owner,
+        &mint,
+        amount,
+        unlock_timestamp,
+        early_exit_penalty_bps,
+        &treasury,
+    );
+
+    let transaction = transaction(args, instruction, &owner, keypair)?;
+    Ok(format!("vault: {vault}\ntransaction: {transaction}"))
+}
+
+fn withdraw_sol(args: &Args) -> Result<String> {
+    let (owner, keypair) = owner(args)?;
+    let vault = args.pubkey("vault")?;
+
+    let instruction = match args.parse_optional::<Pubkey>("creator")? {
+        Some(creator) => client::withdraw_sol_vault_with_receipt(&owner, &creator, &vault),
+        None => client::withdraw_sol_vault(&owner, &vault),
+    };
+
+    let transaction = transaction(args, instruction, &owner, keypair)?;
+    Ok(format!("transaction: {transaction}"))
+}
+
+fn withdraw_spl(args: &Args) -> Result<String> {
+    let (owner, keypair) = owner(args)?;
+    let vault = args.pubkey("vault")?;
+    let mint = args.pubkey("mint")?;
+
+    let instruction = match args.parse_optional::<Pubkey>("creator")? {
+        Some(creator) => client::withdraw_spl_vault_with_receipt(&owner, &creator, &vault, &mint),
+        None => client::withdraw_spl_vault(&owner, &vault, &mint),
+    };
+
+    let transaction = transaction(args, instruction, &owner, keypair)?;
+    Ok(format!("transaction: {transaction}"))
+}
+
+fn sign(args: &Args) -> Result<String> {
+    let keypair = read_keypair_file(args.required("keypair")?)?;
+    let mut transaction: Transaction =
+        bincode::deserialize(&BASE64_STANDARD.decode(args.required("transaction")?)?)?;
+
+    let blockhash = transaction.message.recent_blockhash;
+    transaction.try_partial_sign(&[&keypair], blockhash)?;
+
+    Ok(format!(
+        "transaction: {}",
+        BASE64_STANDARD.encode(bincode::serialize(&transaction)?)
+    ))
+}
+
+fn show_vault(args: &Args) -> Result<String> {
+    let data = match (args.get("file"), args.get("base64")) {
+        (Some(path), None) => fs::read(path)?,
+        (None, Some(data)) => BASE64_STANDARD.decode(data.trim())?,
+        _ => return Err("expected one of --file or --base64".into()),
+    };
+    let vault = VaultAccount::decode(&data).map_err(|_| "not a vault account")?;
+
+    let mint = match vault.mint {
+        Some(mint) => mint.to_string(),
+        None => "SOL".to_string(),
+    };
+    let receipt_mint = match vault.receipt_mint {
+        Some(receipt_mint) => receipt_mint.to_string(),
+        None => "none".to_string(),
+    };
+    Ok(format!(
+        "owner: {}\nmint: {}\namount: {}\nseed_amount: {}\nunlock_timestamp: {}\nid: {}\nearly_exit_penalty_bps: {}\nbump: {}\nreceipt_mint: {}\ntracked: {}\nwithdrawn: {}",
+        vault.owner,
+        mint,
+        vault.amount,
+        vault.seed_amount,
+        vault.unlock_timestamp,
+        vault.id,
+        vault.early_exit_penalty_bps,
+        vault.bump,
+        receipt_mint,
+        vault.tracked,
+        vault.withdrawn,
+    ))
+}
+
+/// Returns the vault owner and, when `--keypair` is given, its keypair.
+fn owner(args: &Args) -> Result<(Pubkey, Option<Keypair>)> {
+    let keypair = args.get("keypair").map(read_keypair_file).transpose()?;
+    let owner = args.parse_optional::<Pubkey>("owner")?;
+
+    match (owner, &keypair) {
+        (Some(owner), Some(keypair)) if owner != keypair.pubkey() => {
+            Err("--owner does not match --keypair".into())
+        }
+        (Some(owner), _) => Ok((owner, keypair)),
+        (None, Some(signer)) => Ok((signer.pubkey(), keypair)),
+        (None, None) => Err("missing required option --owner or --keypair".into()),
+    }
+}
+
+/// Builds a transaction paid by `owner`, signed if `keypair` is given.
+fn transaction(
+    args: &Args,
+    instruction: Instruction,
+    owner: &Pubkey,
+    keypair: Option<Keypair>,
+) -> Result<String> {
+    let blockhash = args.parse_required::<Hash>("blockhash")?;
+
+    let message = Message::new_with_blockhash(&[instruction], Some(owner), &blockhash);
+    let mut transaction = Transaction::new_unsigned(message);
+    if let Some(keypair) = keypair {
+        transaction.try_sign(&[&keypair], blockhash)?;
+    }
+
+    Ok(BASE64_STANDARD.encode(bincode::serialize(&transaction)?))
+}
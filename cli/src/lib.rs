@@ -0,0 +1,12 @@
+//! Offline command-line tool for the timebase vault program.
+//!
+//! Every command works without network access: transactions are built
+//! against a blockhash given on the command line, so they can be signed on
+//! an air-gapped machine and broadcast from elsewhere.
+
+pub mod args;
+pub mod commands;
+
+pub use commands::run;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
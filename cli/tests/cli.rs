@@ -0,0 +1,294 @@
+use base64::{prelude::BASE64_STANDARD, Engine};
+use pinocchio_timebase_vault::{client, states::Vault, utils::to_bytes};
+use solana_sdk::{
+    hash::Hash,
+    message::Message,
+    pubkey::Pubkey,
+    signature::{write_keypair_file, Keypair, Signer},
+    transaction::Transaction,
+};
+
+fn run(args: &[&str]) -> Result<String, String> {
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    timebase_vault_cli::run(&args).map_err(|error| error.to_string())
+}
+
+/// Returns the value of the `name: value` line of a command output.
+fn field<'a>(output: &'a str, name: &str) -> &'a str {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix(name)?.strip_prefix(": "))
+        .unwrap()
+}
+
+fn decode_transaction(output: &str) -> Transaction {
+    let bytes = BASE64_STANDARD
+        .decode(field(output, "transaction"))
+        .unwrap();
+    bincode::deserialize(&bytes).unwrap()
+}
+
+#[test]
+fn address_matches_client() {
+    let owner = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+
+    let output = run(&[
+        "address",
+        "--owner",
+        &owner.to_string(),
+        "--amount",
+        "1000",
+        "--unlock-timestamp",
+        "1700000000",
+    ])
+    .unwrap();
+    let (vault, bump) = client::find_vault_address(&owner, None, 1000, 1_700_000_000);
+    assert_eq!(field(&output, "vault"), vault.to_string());
+    assert_eq!(field(&output, "bump"), bump.to_string());
+
+    let output = run(&[
+        "address",
+        "--owner",
+        &owner.to_string(),
+        "--mint",
+        &mint.to_string(),
+        "--amount",
+        "1000",
+        "--unlock-timestamp",
+        "1700000000",
+    ])
+    .unwrap();
+    let (vault, _) = client::find_vault_address(&owner, Some(&mint), 1000, 1_700_000_000);
+    assert_eq!(field(&output, "vault"), vault.to_string());
+}
+
+#[test]
+fn init_sol_builds_unsigned_transaction() {
+    let owner = Pubkey::new_unique();
+    let treasury = Pubkey::new_unique();
+    let blockhash = Hash::new_unique();
+
+    let output = run(&[
+        "init-sol",
+        "--owner",
+        &owner.to_string(),
+        "--amount",
+        "1000",
+        "--unlock-timestamp",
+        "1700000000",
+        "--treasury",
+        &treasury.to_string(),
+        "--blockhash",
+        &blockhash.to_string(),
+    ])
+    .unwrap();
+
+    let transaction = decode_transaction(&output);
+    let instruction = client::init_sol_vault(&owner, 1000, 1_700_000_000, 0, &treasury);
+    assert_eq!(transaction.message.recent_blockhash, blockhash);
+    assert_eq!(transaction.message.account_keys[0], owner);
+    assert_eq!(transaction.message.instructions[0].data, instruction.data);
+    assert!(!transaction.is_signed());
+}
+
+#[test]
+fn withdraw_spl_signs_with_keypair() {
+    let keypair = Keypair::new();
+    let path = std::env::temp_dir().join(format!("{}.json", keypair.pubkey()));
+    write_keypair_file(&keypair, &path).unwrap();
+    let vault = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let blockhash = Hash::new_unique();
+
+    let output = run(&[
+        "withdraw-spl",
+        "--keypair",
+        path.to_str().unwrap(),
+        "--vault",
+        &vault.to_string(),
+        "--mint",
+        &mint.to_string(),
+        "--blockhash",
+        &blockhash.to_string(),
+    ])
+    .unwrap();
+
+    let transaction = decode_transaction(&output);
+    assert_eq!(transaction.message.account_keys[0], keypair.pubkey());
+    assert!(transaction.is_signed());
+    transaction.verify().unwrap();
+
+    let error = run(&[
+        "withdraw-spl",
+        "--keypair",
+        path.to_str().unwrap(),
+        "--owner",
+        &Pubkey::new_unique().to_string(),
+        "--vault",
+        &vault.to_string(),
+        "--mint",
+        &mint.to_string(),
+        "--blockhash",
+        &blockhash.to_string(),
+    ])
+    .unwrap_err();
+    assert_eq!(error, "--owner does not match --keypair");
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn withdraw_with_creator_uses_receipt() {
+    let holder = Pubkey::new_unique();
+    let creator = Pubkey::new_unique();
+    let vault = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let blockhash = Hash::new_unique();
+
+    let output = run(&[
+        "withdraw-sol",
+        "--owner",
+        &holder.to_string(),
+        "--creator",
+        &creator.to_string(),
+        "--vault",
+        &vault.to_string(),
+        "--blockhash",
+        &blockhash.to_string(),
+    ])
+    .unwrap();
+    let instruction = client::withdraw_sol_vault_with_receipt(&holder, &creator, &vault);
+    assert_eq!(
+        decode_transaction(&output).message,
+        Message::new_with_blockhash(&[instruction], Some(&holder), &blockhash)
+    );
+
+    let output = run(&[
+        "withdraw-spl",
+        "--owner",
+        &holder.to_string(),
+        "--creator",
+        &creator.to_string(),
+        "--vault",
+        &vault.to_string(),
+        "--mint",
+        &mint.to_string(),
+        "--blockhash",
+        &blockhash.to_string(),
+    ])
+    .unwrap();
+    let instruction = client::withdraw_spl_vault_with_receipt(&holder, &creator, &vault, &mint);
+    assert_eq!(
+        decode_transaction(&output).message,
+        Message::new_with_blockhash(&[instruction], Some(&holder), &blockhash)
+    );
+}
+
+#[test]
+fn sign_signs_unsigned_transaction() {
+    let keypair = Keypair::new();
+    let path = std::env::temp_dir().join(format!("{}.json", keypair.pubkey()));
+    write_keypair_file(&keypair, &path).unwrap();
+
+    let output = run(&[
+        "withdraw-sol",
+        "--owner",
+        &keypair.pubkey().to_string(),
+        "--vault",
+        &Pubkey::new_unique().to_string(),
+        "--blockhash",
+        &Hash::new_unique().to_string(),
+    ])
+    .unwrap();
+    let output = run(&[
+        "sign",
+        "--keypair",
+        path.to_str().unwrap(),
+        "--transaction",
+        field(&output, "transaction"),
+    ])
+    .unwrap();
+
+    decode_transaction(&output).verify().unwrap();
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn show_vault_prints_vault() {
+    let owner = Pubkey::new_unique();
+    let vault = Vault {
+        owner: owner.to_bytes(),
+        amount: 1000u64.to_le_bytes(),
+        unlock_timestamp: 1_700_000_000i64.to_le_bytes(),
+        mint: None,
+        bump: [254],
+        id: 3u64.to_le_bytes(),
+        early_exit_penalty_bps: 250u16.to_le_bytes(),
+        seed_amount: 1000u64.to_le_bytes(),
+        receipt_mint: None,
+        tracked: [1],
+        withdrawn: [0; 8],
+    };
+    let data = unsafe { to_bytes(&vault) };
+
+    let output = run(&["show-vault", "--base64", &BASE64_STANDARD.encode(data)]).unwrap();
+    assert_eq!(field(&output, "owner"), owner.to_string());
+    assert_eq!(field(&output, "mint"), "SOL");
+    assert_eq!(field(&output, "amount"), "1000");
+    assert_eq!(field(&output, "unlock_timestamp"), "1700000000");
+    assert_eq!(field(&output, "id"), "3");
+    assert_eq!(field(&output, "early_exit_penalty_bps"), "250");
+    assert_eq!(field(&output, "seed_amount"), "1000");
+    assert_eq!(field(&output, "receipt_mint"), "none");
+    assert_eq!(field(&output, "tracked"), "true");
+    assert_eq!(field(&output, "withdrawn"), "0");
+
+    // a split vault with a receipt token
+    let receipt_mint = Pubkey::new_unique();
+    let split = Vault {
+        amount: 600u64.to_le_bytes(),
+        receipt_mint: Some(receipt_mint.to_bytes()),
+        withdrawn: 400u64.to_le_bytes(),
+        ..vault
+    };
+    let output = run(&[
+        "show-vault",
+        "--base64",
+        &BASE64_STANDARD.encode(unsafe { to_bytes(&split) }),
+    ])
+    .unwrap();
+    assert_eq!(field(&output, "amount"), "600");
+    assert_eq!(field(&output, "seed_amount"), "1000");
+    assert_eq!(field(&output, "receipt_mint"), receipt_mint.to_string());
+    assert_eq!(field(&output, "withdrawn"), "400");
+
+    let error = run(&[
+        "show-vault",
+        "--base64",
+        &BASE64_STANDARD.encode(&data[1..]),
+    ])
+    .unwrap_err();
+    assert_eq!(error, "not a vault account");
+}
+
+#[test]
+fn rejects_invalid_arguments() {
+    assert!(run(&[]).is_err());
+    assert!(run(&["unknown"])
+        .unwrap_err()
+        .starts_with("unknown command unknown"));
+    assert_eq!(
+        run(&["address", "--owner"]).unwrap_err(),
+        "missing value for --owner"
+    );
+    assert_eq!(
+        run(&["address", "--owner", "invalid"]).unwrap_err(),
+        "invalid value for --owner: invalid"
+    );
+    assert_eq!(
+        run(&["withdraw-sol", "--vault", &Pubkey::new_unique().to_string()]).unwrap_err(),
+        "missing required option --owner or --keypair"
+    );
+}
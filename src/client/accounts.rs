@@ -0,0 +1,80 @@
+use pinocchio::program_error::ProgramError;
+use solana_pubkey::Pubkey;
+
+use crate::{
+    states::Vault,
+    utils::{load_acc_unchecked, DataLen},
+};
+
+/// The data size of vault accounts, which no other account of the program
+/// shares.
+pub const VAULT_ACCOUNT_LEN: usize = Vault::LEN;
+
+/// A decoded [`Vault`] account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VaultAccount {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+    pub unlock_timestamp: i64,
+    pub mint: Option<Pubkey>,
+    pub id: u64,
+    pub early_exit_penalty_bps: u16,
+    pub seed_amount: u64,
+    pub receipt_mint: Option<Pubkey>,
+    pub tracked: bool,
+    pub withdrawn: u64,
+}
+
+impl VaultAccount {
+    /// Decodes the data of a vault account.
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        // reject invalid `Option` tags before reading the data as a `Vault`
+        for offset in [
+            core::mem::offset_of!(Vault, mint),
+            core::mem::offset_of!(Vault, receipt_mint),
+        ] {
+            if !matches!(data.get(offset), Some(0 | 1)) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+        let vault = unsafe { load_acc_unchecked::<Vault>(data) }?;
+
+        Ok(VaultAccount {
+            owner: Pubkey::new_from_array(vault.owner),
+            amount: u64::from_le_bytes(vault.amount),
+            bump: vault.bump[0],
+            unlock_timestamp: i64::from_le_bytes(vault.unlock_timestamp),
+            mint: vault.mint.map(Pubkey::new_from_array),
+            id: u64::from_le_bytes(vault.id),
+            early_exit_penalty_bps: u16::from_le_bytes(vault.early_exit_penalty_bps),
+            seed_amount: u64::from_le_bytes(vault.seed_amount),
+            receipt_mint: vault.receipt_mint.map(Pubkey::new_from_array),
+            tracked: vault.is_tracked(),
+            withdrawn: u64::from_le_bytes(vault.withdrawn),
+        })
+    }
+
+    /// Decodes the data of the vault account at `address`, rejecting accounts
+    /// that are not at the PDA of their own seeds.
+    pub fn decode_at(address: &Pubkey, data: &[u8]) -> Result<Self, ProgramError> {
+        let vault = Self::decode(data)?;
+        if vault.address()? != *address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(vault)
+    }
+
+    /// Returns the PDA of the vault, derived from its stored seeds and bump.
+    pub fn address(&self) -> Result<Pubkey, ProgramError> {
+        Vault::derive_address(
+            self.owner.as_array(),
+            self.mint.as_ref().map(Pubkey::as_array),
+            self.seed_amount,
+            self.unlock_timestamp,
+            self.bump,
+        )
+        .map(Pubkey::new_from_array)
+    }
+}
@@ -0,0 +1,80 @@
+//! `getProgramAccounts` filters selecting vault accounts.
+//!
+//! The offsets are taken from the [`Vault`] layout, each [`Memcmp`] maps to
+//! an RPC `memcmp` filter and should be combined with a `dataSize` filter of
+//! [`VAULT_ACCOUNT_LEN`](crate::client::VAULT_ACCOUNT_LEN).
+
+use core::mem::offset_of;
+use std::{vec, vec::Vec};
+
+use solana_pubkey::Pubkey;
+
+use crate::states::Vault;
+
+pub const OWNER_OFFSET: usize = offset_of!(Vault, owner);
+pub const UNLOCK_TIMESTAMP_OFFSET: usize = offset_of!(Vault, unlock_timestamp);
+/// The offset of the `Option` tag of the mint, the mint follows it.
+pub const MINT_OFFSET: usize = offset_of!(Vault, mint);
+
+/// The maximum number of queries [`unlock_timestamp_filters`] splits a range
+/// into.
+pub const MAX_UNLOCK_TIMESTAMP_QUERIES: usize = 16;
+
+/// Matches the account data starting at `offset` against `bytes`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Memcmp {
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+}
+
+/// Selects the vaults of `owner`.
+pub fn owner_filter(owner: &Pubkey) -> Memcmp {
+    Memcmp {
+        offset: OWNER_OFFSET,
+        bytes: owner.to_bytes().to_vec(),
+    }
+}
+
+/// Selects the vaults of `mint`, or the SOL vaults when `mint` is `None`.
+pub fn mint_filter(mint: Option<&Pubkey>) -> Memcmp {
+    let bytes = match mint {
+        Some(mint) => [&[1], mint.as_ref()].concat(),
+        None => vec![0],
+    };
+
+    Memcmp {
+        offset: MINT_OFFSET,
+        bytes,
+    }
+}
+
+/// Returns one filter per query, together selecting the vaults unlocking in
+/// `start..=end`.
+///
+/// `memcmp` can only match exact bytes, so each filter matches the high bytes
+/// of the little-endian timestamp and the queries can return vaults outside
+/// of the range, which the caller filters out after decoding. Ranges shorter
+/// than 2^56 seconds take at most [`MAX_UNLOCK_TIMESTAMP_QUERIES`] filters.
+pub fn unlock_timestamp_filters(start: i64, end: i64) -> Vec<Memcmp> {
+    if start > end {
+        return Vec::new();
+    }
+
+    // the smallest number of low bytes to leave out so the range spans few
+    // enough blocks of equal high bytes
+    let mut shift = 0;
+    while shift < 56
+        && ((end >> shift) as i128 - (start >> shift) as i128)
+            >= MAX_UNLOCK_TIMESTAMP_QUERIES as i128
+    {
+        shift += 8;
+    }
+    let skipped = shift as usize / 8;
+
+    ((start >> shift)..=(end >> shift))
+        .map(|block| Memcmp {
+            offset: UNLOCK_TIMESTAMP_OFFSET + skipped,
+            bytes: (block << shift).to_le_bytes()[skipped..].to_vec(),
+        })
+        .collect()
+}
@@ -0,0 +1,903 @@
+use std::{vec, vec::Vec};
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+use crate::{
+    client::{
+        event_authority_address, find_bucket_receipt_mint_address, find_claim_receipt_address,
+        find_config_address, find_locked_bucket_address, find_merkle_vault_address,
+        find_mint_config_address, find_program_data_address, find_receipt_mint_address,
+        find_registry_address, find_vault_address, get_associated_token_address,
+        ASSOCIATED_TOKEN_PROGRAM_ID, PROGRAM_ID, SYSTEM_PROGRAM_ID, TOKEN_PROGRAM_ID,
+    },
+    instructions::{
+        AddMintToList, BatchInitializeSplVault, BatchInitializeSplVaultInstructionData,
+        BatchVaultEntry, BatchWithdraw, ClaimMerkleVault, ClaimMerkleVaultInstructionData,
+        DepositLockedBucket, DepositLockedBucketInstructionData, EarlyWithdrawSolVault,
+        EarlyWithdrawSplVault, GetVaultStatus, GetVotingPower, GetVotingPowerInstructionData,
+        InitializeConfig, InitializeConfigInstructionData, InitializeMerkleVault,
+        InitializeMerkleVaultInstructionData, InitializeSolVault,
+        InitializeSolVaultInstructionData, InitializeSplVault, InitializeSplVaultInstructionData,
+        MergeVaults, ReclaimMerkleVault, RedeemLockedBucket, RedeemLockedBucketInstructionData,
+        RemoveMintFromList, SetMintConfig, SetMintConfigInstructionData, SetMintListMode,
+        SetPaused, SplitVault, SplitVaultInstructionData, TransferAdmin,
+        TransferAdminInstructionData, UpdateConfig, UpdateConfigInstructionData, WithdrawSolVault,
+        WithdrawSplVault,
+    },
+    states::{MerkleVault, MintListMode},
+    utils::{to_bytes, DataLen},
+};
+
+/// The settings passed to [`initialize_config`] and [`update_config`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigSettings {
+    pub min_lock_duration: i64,
+    pub max_lock_duration: i64,
+    pub fee_bps: u16,
+    pub flat_fee: u64,
+    pub treasury: Pubkey,
+    pub penalty_recipient: Pubkey,
+}
+
+fn instruction_data<T: DataLen>(discriminator: u8, data: &T) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(1 + T::LEN);
+    bytes.push(discriminator);
+    bytes.extend_from_slice(unsafe { to_bytes(data) });
+    bytes
+}
+
+fn event_accounts() -> [AccountMeta; 2] {
+    [
+        AccountMeta::new_readonly(event_authority_address().0, false),
+        AccountMeta::new_readonly(PROGRAM_ID, false),
+    ]
+}
+
+/// Locks `amount` lamports of `owner` until `unlock_timestamp`.
+pub fn init_sol_vault(
+    owner: &Pubkey,
+    amount: u64,
+    unlock_timestamp: i64,
+    early_exit_penalty_bps: u16,
+    treasury: &Pubkey,
+) -> Instruction {
+    let (vault, bump) = find_vault_address(owner, None, amount, unlock_timestamp);
+    let (registry, registry_bump) = find_registry_address(owner);
+
+    let data = InitializeSolVaultInstructionData {
+        amount,
+        unlock_timestamp,
+        bump,
+        registry_bump,
+        early_exit_penalty_bps,
+    };
+
+    let mut accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(vault, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        AccountMeta::new(registry, false),
+        AccountMeta::new_readonly(find_config_address().0, false),
+        AccountMeta::new(*treasury, false),
+    ];
+    accounts.extend(event_accounts());
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &instruction_data(*InitializeSolVault::DISCRIMINATOR, &data),
+        accounts,
+    )
+}
+
+/// Appends the accounts and the receipt mint bump that create the vault of
+/// an initialize `instruction` with a receipt token, minted to `owner`.
+fn with_receipt_mint(mut instruction: Instruction, owner: &Pubkey) -> Instruction {
+    let (receipt_mint, bump) = find_receipt_mint_address(&instruction.accounts[1].pubkey);
+
+    instruction.accounts.extend([
+        AccountMeta::new(receipt_mint, false),
+        AccountMeta::new(get_associated_token_address(owner, &receipt_mint), false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+    ]);
+    instruction.data.push(bump);
+    instruction
+}
+
+/// Appends the accounts proving that `holder` holds the receipt token of
+/// `vault` to a withdraw `instruction`.
+fn with_receipt(mut instruction: Instruction, holder: &Pubkey, vault: &Pubkey) -> Instruction {
+    let (receipt_mint, _) = find_receipt_mint_address(vault);
+
+    instruction.accounts.extend([
+        AccountMeta::new(receipt_mint, false),
+        AccountMeta::new(get_associated_token_address(holder, &receipt_mint), false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ]);
+    instruction
+}
+
+/// Same as [`init_sol_vault`], minting a receipt token to `owner` whose holder
+/// withdraws the vault.
+pub fn init_sol_vault_with_receipt(
+    owner: &Pubkey,
+    amount: u64,
+    unlock_timestamp: i64,
+    early_exit_penalty_bps: u16,
+    treasury: &Pubkey,
+) -> Instruction {
+    with_receipt_mint(
+        init_sol_vault(
+            owner,
+            amount,
+            unlock_timestamp,
+            early_exit_penalty_bps,
+            treasury,
+        ),
+        owner,
+    )
+}
+
+fn sol_withdraw_accounts(signer: &Pubkey, owner: &Pubkey, vault: &Pubkey) -> Vec<AccountMeta> {
+    let mut accounts = vec![
+        AccountMeta::new(*signer, true),
+        AccountMeta::new(*vault, false),
+        AccountMeta::new(find_registry_address(owner).0, false),
+    ];
+    accounts.extend(event_accounts());
+    accounts
+}
+
+pub fn withdraw_sol_vault(owner: &Pubkey, vault: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[*WithdrawSolVault::DISCRIMINATOR],
+        sol_withdraw_accounts(owner, owner, vault),
+    )
+}
+
+/// Withdraws the SOL `vault` created by `owner` to `holder`, the holder of its
+/// receipt token.
+pub fn withdraw_sol_vault_with_receipt(
+    holder: &Pubkey,
+    owner: &Pubkey,
+    vault: &Pubkey,
+) -> Instruction {
+    with_receipt(
+        Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[*WithdrawSolVault::DISCRIMINATOR],
+            sol_withdraw_accounts(holder, owner, vault),
+        ),
+        holder,
+        vault,
+    )
+}
+
+/// Locks `amount` tokens of `mint` held in the associated token account of
+/// `owner` until `unlock_timestamp`.
+pub fn init_spl_vault(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    unlock_timestamp: i64,
+    early_exit_penalty_bps: u16,
+    treasury: &Pubkey,
+) -> Instruction {
+    let (vault, bump) = find_vault_address(owner, Some(mint), amount, unlock_timestamp);
+    let (registry, registry_bump) = find_registry_address(owner);
+    let (mint_config, mint_config_bump) = find_mint_config_address(mint);
+
+    let data = InitializeSplVaultInstructionData {
+        amount,
+        unlock_timestamp,
+        bump,
+        registry_bump,
+        early_exit_penalty_bps,
+        mint_config_bump,
+    };
+
+    let mut accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(vault, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(get_associated_token_address(owner, mint), false),
+        AccountMeta::new(get_associated_token_address(&vault, mint), false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        AccountMeta::new(registry, false),
+        AccountMeta::new_readonly(find_config_address().0, false),
+        AccountMeta::new(*treasury, false),
+        AccountMeta::new(get_associated_token_address(treasury, mint), false),
+        AccountMeta::new_readonly(mint_config, false),
+    ];
+    accounts.extend(event_accounts());
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &instruction_data(*InitializeSplVault::DISCRIMINATOR, &data),
+        accounts,
+    )
+}
+
+/// Same as [`init_spl_vault`], minting a receipt token to `owner` whose holder
+/// withdraws the vault.
+pub fn init_spl_vault_with_receipt(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    unlock_timestamp: i64,
+    early_exit_penalty_bps: u16,
+    treasury: &Pubkey,
+) -> Instruction {
+    with_receipt_mint(
+        init_spl_vault(
+            owner,
+            mint,
+            amount,
+            unlock_timestamp,
+            early_exit_penalty_bps,
+            treasury,
+        ),
+        owner,
+    )
+}
+
+/// One vault of [`batch_init_spl_vault`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchVault {
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub unlock_timestamp: i64,
+}
+
+/// Locks tokens of `mint` held in the associated token account of `funder`
+/// in one vault per entry of `vaults`, owned by its beneficiary.
+pub fn batch_init_spl_vault(
+    funder: &Pubkey,
+    mint: &Pubkey,
+    vaults: &[BatchVault],
+    early_exit_penalty_bps: u16,
+    treasury: &Pubkey,
+) -> Instruction {
+    let (mint_config, mint_config_bump) = find_mint_config_address(mint);
+
+    let mut data = instruction_data(
+        *BatchInitializeSplVault::DISCRIMINATOR,
+        &BatchInitializeSplVaultInstructionData {
+            early_exit_penalty_bps,
+            mint_config_bump,
+        },
+    );
+
+    let mut accounts = vec![
+        AccountMeta::new(*funder, true),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(get_associated_token_address(funder, mint), false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        AccountMeta::new_readonly(find_config_address().0, false),
+        AccountMeta::new(*treasury, false),
+        AccountMeta::new(get_associated_token_address(treasury, mint), false),
+        AccountMeta::new_readonly(mint_config, false),
+    ];
+    accounts.extend(event_accounts());
+
+    for entry in vaults {
+        let (vault, bump) = find_vault_address(
+            &entry.beneficiary,
+            Some(mint),
+            entry.amount,
+            entry.unlock_timestamp,
+        );
+        let (registry, registry_bump) = find_registry_address(&entry.beneficiary);
+
+        data.extend_from_slice(unsafe {
+            to_bytes(&BatchVaultEntry {
+                beneficiary: entry.beneficiary.to_bytes(),
+                amount: entry.amount,
+                unlock_timestamp: entry.unlock_timestamp,
+                bump,
+                registry_bump,
+            })
+        });
+        accounts.extend([
+            AccountMeta::new(vault, false),
+            AccountMeta::new(get_associated_token_address(&vault, mint), false),
+            AccountMeta::new(registry, false),
+        ]);
+    }
+
+    Instruction::new_with_bytes(PROGRAM_ID, &data, accounts)
+}
+
+/// Locks `total` tokens of `mint` held in the associated token account of
+/// `funder` for the leaves of the tree of `root`, see
+/// [`MerkleTree`](crate::client::MerkleTree). The funder can take back the
+/// unclaimed tokens from `reclaim_timestamp` (0 = never).
+pub fn init_merkle_vault(
+    funder: &Pubkey,
+    mint: &Pubkey,
+    root: &[u8; 32],
+    total: u64,
+    reclaim_timestamp: i64,
+    treasury: &Pubkey,
+) -> Instruction {
+    let (merkle_vault, bump) = find_merkle_vault_address(funder, mint, root);
+    let (mint_config, mint_config_bump) = find_mint_config_address(mint);
+
+    let data = InitializeMerkleVaultInstructionData {
+        root: *root,
+        total,
+        reclaim_timestamp,
+        bump,
+        mint_config_bump,
+    };
+
+    let mut accounts = vec![
+        AccountMeta::new(*funder, true),
+        AccountMeta::new(merkle_vault, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(get_associated_token_address(funder, mint), false),
+        AccountMeta::new(get_associated_token_address(&merkle_vault, mint), false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        AccountMeta::new_readonly(find_config_address().0, false),
+        AccountMeta::new(*treasury, false),
+        AccountMeta::new(get_associated_token_address(treasury, mint), false),
+        AccountMeta::new_readonly(mint_config, false),
+    ];
+    accounts.extend(event_accounts());
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &instruction_data(*InitializeMerkleVault::DISCRIMINATOR, &data),
+        accounts,
+    )
+}
+
+/// Claims the `(recipient, amount, unlock_timestamp)` leaf of `merkle_vault`
+/// into the associated token account of `recipient`, `proof` being the
+/// sibling hashes returned by [`MerkleTree::proof`](crate::client::MerkleTree::proof).
+pub fn claim_merkle_vault(
+    recipient: &Pubkey,
+    merkle_vault: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    unlock_timestamp: i64,
+    proof: &[[u8; 32]],
+) -> Instruction {
+    let leaf = MerkleVault::leaf_hash(&recipient.to_bytes(), amount, unlock_timestamp);
+    let (claim_receipt, receipt_bump) = find_claim_receipt_address(merkle_vault, &leaf);
+
+    let mut data = instruction_data(
+        *ClaimMerkleVault::DISCRIMINATOR,
+        &ClaimMerkleVaultInstructionData {
+            amount,
+            unlock_timestamp,
+            receipt_bump,
+        },
+    );
+    data.extend(proof.iter().flatten());
+
+    let mut accounts = vec![
+        AccountMeta::new(*recipient, true),
+        AccountMeta::new(*merkle_vault, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(get_associated_token_address(recipient, mint), false),
+        AccountMeta::new(get_associated_token_address(merkle_vault, mint), false),
+        AccountMeta::new(claim_receipt, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+    accounts.extend(event_accounts());
+
+    Instruction::new_with_bytes(PROGRAM_ID, &data, accounts)
+}
+
+/// Returns the unclaimed tokens of `merkle_vault` to the associated token
+/// account of `funder`.
+pub fn reclaim_merkle_vault(funder: &Pubkey, merkle_vault: &Pubkey, mint: &Pubkey) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*funder, true),
+        AccountMeta::new(*merkle_vault, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(get_associated_token_address(funder, mint), false),
+        AccountMeta::new(get_associated_token_address(merkle_vault, mint), false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ];
+    accounts.extend(event_accounts());
+
+    Instruction::new_with_bytes(PROGRAM_ID, &[*ReclaimMerkleVault::DISCRIMINATOR], accounts)
+}
+
+/// Moves `amount` out of the `vault` of `owner` into a new vault of
+/// `new_owner` unlocking at `unlock_timestamp`, `mint` is `None` for SOL
+/// vaults.
+pub fn split_vault(
+    owner: &Pubkey,
+    vault: &Pubkey,
+    mint: Option<&Pubkey>,
+    new_owner: &Pubkey,
+    amount: u64,
+    unlock_timestamp: i64,
+) -> Instruction {
+    let (new_vault, bump) = find_vault_address(new_owner, mint, amount, unlock_timestamp);
+    let (new_registry, new_registry_bump) = find_registry_address(new_owner);
+    let mint_config = mint.map(find_mint_config_address);
+
+    let data = SplitVaultInstructionData {
+        amount,
+        unlock_timestamp,
+        bump,
+        new_registry_bump,
+        mint_config_bump: mint_config.map_or(0, |(_, bump)| bump),
+    };
+
+    let mut accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(*vault, false),
+        AccountMeta::new(new_vault, false),
+        AccountMeta::new_readonly(*new_owner, false),
+        AccountMeta::new(find_registry_address(owner).0, false),
+        AccountMeta::new(new_registry, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        AccountMeta::new_readonly(find_config_address().0, false),
+    ];
+    accounts.extend(event_accounts());
+    if let (Some(mint), Some((mint_config, _))) = (mint, mint_config) {
+        accounts.extend([
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(get_associated_token_address(vault, mint), false),
+            AccountMeta::new(get_associated_token_address(&new_vault, mint), false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(mint_config, false),
+        ]);
+    }
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &instruction_data(*SplitVault::DISCRIMINATOR, &data),
+        accounts,
+    )
+}
+
+/// Folds the `source` vault of `owner` into its `destination` vault and
+/// closes `source`, `mint` is `None` for SOL vaults.
+pub fn merge_vaults(
+    owner: &Pubkey,
+    source: &Pubkey,
+    destination: &Pubkey,
+    mint: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(*source, false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new(find_registry_address(owner).0, false),
+        AccountMeta::new_readonly(find_config_address().0, false),
+    ];
+    accounts.extend(event_accounts());
+    if let Some(mint) = mint {
+        accounts.extend([
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(get_associated_token_address(source, mint), false),
+            AccountMeta::new(get_associated_token_address(destination, mint), false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ]);
+    }
+
+    Instruction::new_with_bytes(PROGRAM_ID, &[*MergeVaults::DISCRIMINATOR], accounts)
+}
+
+/// Returns the [`VaultStatus`](crate::instructions::VaultStatus) of `vault`
+/// as return data. Simulate it to read the result.
+pub fn get_vault_status(vault: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[*GetVaultStatus::DISCRIMINATOR],
+        vec![AccountMeta::new_readonly(*vault, false)],
+    )
+}
+
+/// Returns the [`VotingPower`](crate::instructions::VotingPower) of `vault`
+/// as return data, weighted up to `max_multiplier_bps` for a remaining lock
+/// time of `max_lock_duration` or more. Simulate it to read the result.
+pub fn get_voting_power(
+    vault: &Pubkey,
+    max_multiplier_bps: u16,
+    max_lock_duration: i64,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &instruction_data(
+            *GetVotingPower::DISCRIMINATOR,
+            &GetVotingPowerInstructionData {
+                max_multiplier_bps,
+                max_lock_duration,
+            },
+        ),
+        vec![AccountMeta::new_readonly(*vault, false)],
+    )
+}
+
+/// Deposits `amount` tokens of `mint` held in the associated token account of
+/// `depositor` into the bucket unlocking at `unlock_timestamp` and mints as
+/// many receipts to `depositor`.
+pub fn deposit_locked_bucket(
+    depositor: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    unlock_timestamp: i64,
+    treasury: &Pubkey,
+) -> Instruction {
+    let (bucket, bump) = find_locked_bucket_address(mint, unlock_timestamp);
+    let (receipt_mint, receipt_mint_bump) = find_bucket_receipt_mint_address(&bucket);
+    let (mint_config, mint_config_bump) = find_mint_config_address(mint);
+
+    let data = DepositLockedBucketInstructionData {
+        amount,
+        unlock_timestamp,
+        bump,
+        receipt_mint_bump,
+        mint_config_bump,
+    };
+
+    let mut accounts = vec![
+        AccountMeta::new(*depositor, true),
+        AccountMeta::new(bucket, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(receipt_mint, false),
+        AccountMeta::new(get_associated_token_address(depositor, mint), false),
+        AccountMeta::new(get_associated_token_address(&bucket, mint), false),
+        AccountMeta::new(
+            get_associated_token_address(depositor, &receipt_mint),
+            false,
+        ),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        AccountMeta::new_readonly(find_config_address().0, false),
+        AccountMeta::new(*treasury, false),
+        AccountMeta::new(get_associated_token_address(treasury, mint), false),
+        AccountMeta::new_readonly(mint_config, false),
+    ];
+    accounts.extend(event_accounts());
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &instruction_data(*DepositLockedBucket::DISCRIMINATOR, &data),
+        accounts,
+    )
+}
+
+/// Burns `amount` receipts of the bucket of `mint` unlocking at
+/// `unlock_timestamp` held by `holder` and redeems as many tokens into its
+/// associated token account.
+pub fn redeem_locked_bucket(
+    holder: &Pubkey,
+    mint: &Pubkey,
+    unlock_timestamp: i64,
+    amount: u64,
+) -> Instruction {
+    let bucket = find_locked_bucket_address(mint, unlock_timestamp).0;
+    let receipt_mint = find_bucket_receipt_mint_address(&bucket).0;
+
+    let mut accounts = vec![
+        AccountMeta::new(*holder, true),
+        AccountMeta::new(bucket, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(receipt_mint, false),
+        AccountMeta::new(get_associated_token_address(holder, &receipt_mint), false),
+        AccountMeta::new(get_associated_token_address(holder, mint), false),
+        AccountMeta::new(get_associated_token_address(&bucket, mint), false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ];
+    accounts.extend(event_accounts());
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &instruction_data(
+            *RedeemLockedBucket::DISCRIMINATOR,
+            &RedeemLockedBucketInstructionData { amount },
+        ),
+        accounts,
+    )
+}
+
+/// The accounts of the SPL withdraw instructions, the tokens going to the
+/// associated token account of `signer`.
+fn spl_withdraw_accounts(
+    signer: &Pubkey,
+    owner: &Pubkey,
+    vault: &Pubkey,
+    mint: &Pubkey,
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![
+        AccountMeta::new(*signer, true),
+        AccountMeta::new(*vault, false),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(get_associated_token_address(signer, mint), false),
+        AccountMeta::new(get_associated_token_address(vault, mint), false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        AccountMeta::new(find_registry_address(owner).0, false),
+    ];
+    accounts.extend(event_accounts());
+    accounts
+}
+
+pub fn withdraw_spl_vault(owner: &Pubkey, vault: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[*WithdrawSplVault::DISCRIMINATOR],
+        spl_withdraw_accounts(owner, owner, vault, mint),
+    )
+}
+
+/// Withdraws the SPL `vault` created by `owner` to the associated token
+/// account of `holder`, the holder of its receipt token.
+pub fn withdraw_spl_vault_with_receipt(
+    holder: &Pubkey,
+    owner: &Pubkey,
+    vault: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    with_receipt(
+        Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[*WithdrawSplVault::DISCRIMINATOR],
+            spl_withdraw_accounts(holder, owner, vault, mint),
+        ),
+        holder,
+        vault,
+    )
+}
+
+/// Withdraws the `(vault, mint)` vaults of `owner`, `mint` is `None` for SOL
+/// vaults. Locked vaults are skipped with `skip_locked`, otherwise they fail
+/// the instruction.
+pub fn batch_withdraw(
+    owner: &Pubkey,
+    vaults: &[(Pubkey, Option<Pubkey>)],
+    skip_locked: bool,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(find_registry_address(owner).0, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ];
+    accounts.extend(event_accounts());
+
+    for (vault, mint) in vaults {
+        accounts.push(AccountMeta::new(*vault, false));
+        if let Some(mint) = mint {
+            accounts.extend([
+                AccountMeta::new(*mint, false),
+                AccountMeta::new(get_associated_token_address(owner, mint), false),
+                AccountMeta::new(get_associated_token_address(vault, mint), false),
+            ]);
+        }
+    }
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[*BatchWithdraw::DISCRIMINATOR, skip_locked as u8],
+        accounts,
+    )
+}
+
+fn early_withdraw_sol_accounts(
+    signer: &Pubkey,
+    owner: &Pubkey,
+    vault: &Pubkey,
+    penalty_recipient: &Pubkey,
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![
+        AccountMeta::new(*signer, true),
+        AccountMeta::new(*vault, false),
+        AccountMeta::new(find_registry_address(owner).0, false),
+        AccountMeta::new_readonly(find_config_address().0, false),
+        AccountMeta::new(*penalty_recipient, false),
+    ];
+    accounts.extend(event_accounts());
+    accounts
+}
+
+pub fn early_withdraw_sol_vault(
+    owner: &Pubkey,
+    vault: &Pubkey,
+    penalty_recipient: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[*EarlyWithdrawSolVault::DISCRIMINATOR],
+        early_withdraw_sol_accounts(owner, owner, vault, penalty_recipient),
+    )
+}
+
+/// Same as [`early_withdraw_sol_vault`] for `holder`, the holder of the
+/// receipt token of the `vault` created by `owner`.
+pub fn early_withdraw_sol_vault_with_receipt(
+    holder: &Pubkey,
+    owner: &Pubkey,
+    vault: &Pubkey,
+    penalty_recipient: &Pubkey,
+) -> Instruction {
+    with_receipt(
+        Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[*EarlyWithdrawSolVault::DISCRIMINATOR],
+            early_withdraw_sol_accounts(holder, owner, vault, penalty_recipient),
+        ),
+        holder,
+        vault,
+    )
+}
+
+pub fn early_withdraw_spl_vault(owner: &Pubkey, vault: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[*EarlyWithdrawSplVault::DISCRIMINATOR],
+        spl_withdraw_accounts(owner, owner, vault, mint),
+    )
+}
+
+/// Same as [`early_withdraw_spl_vault`] for `holder`, the holder of the
+/// receipt token of the `vault` created by `owner`.
+pub fn early_withdraw_spl_vault_with_receipt(
+    holder: &Pubkey,
+    owner: &Pubkey,
+    vault: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    with_receipt(
+        Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[*EarlyWithdrawSplVault::DISCRIMINATOR],
+            spl_withdraw_accounts(holder, owner, vault, mint),
+        ),
+        holder,
+        vault,
+    )
+}
+
+/// Creates the config, `admin` must be the upgrade authority of the program.
+pub fn initialize_config(admin: &Pubkey, settings: &ConfigSettings) -> Instruction {
+    let (config, bump) = find_config_address();
+
+    let data = InitializeConfigInstructionData {
+        min_lock_duration: settings.min_lock_duration,
+        max_lock_duration: settings.max_lock_duration,
+        fee_bps: settings.fee_bps,
+        flat_fee: settings.flat_fee,
+        treasury: settings.treasury.to_bytes(),
+        penalty_recipient: settings.penalty_recipient.to_bytes(),
+        bump,
+    };
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &instruction_data(*InitializeConfig::DISCRIMINATOR, &data),
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_program_data_address().0, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    )
+}
+
+fn admin_accounts(admin: &Pubkey) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new(find_config_address().0, false),
+    ]
+}
+
+pub fn update_config(admin: &Pubkey, settings: &ConfigSettings) -> Instruction {
+    let data = UpdateConfigInstructionData {
+        min_lock_duration: settings.min_lock_duration,
+        max_lock_duration: settings.max_lock_duration,
+        fee_bps: settings.fee_bps,
+        flat_fee: settings.flat_fee,
+        treasury: settings.treasury.to_bytes(),
+        penalty_recipient: settings.penalty_recipient.to_bytes(),
+    };
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &instruction_data(*UpdateConfig::DISCRIMINATOR, &data),
+        admin_accounts(admin),
+    )
+}
+
+pub fn transfer_admin(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    let data = TransferAdminInstructionData {
+        new_admin: new_admin.to_bytes(),
+    };
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &instruction_data(*TransferAdmin::DISCRIMINATOR, &data),
+        admin_accounts(admin),
+    )
+}
+
+pub fn set_paused(admin: &Pubkey, paused: bool) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[*SetPaused::DISCRIMINATOR, paused as u8],
+        admin_accounts(admin),
+    )
+}
+
+pub fn set_mint_list_mode(admin: &Pubkey, mode: MintListMode) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[*SetMintListMode::DISCRIMINATOR, mode as u8],
+        admin_accounts(admin),
+    )
+}
+
+/// Sets the lock duration bounds of `mint`, 0 falls back to the config.
+pub fn set_mint_config(
+    admin: &Pubkey,
+    mint: &Pubkey,
+    min_lock_duration: i64,
+    max_lock_duration: i64,
+) -> Instruction {
+    let (mint_config, bump) = find_mint_config_address(mint);
+
+    let data = SetMintConfigInstructionData {
+        min_lock_duration,
+        max_lock_duration,
+        bump,
+    };
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &instruction_data(*SetMintConfig::DISCRIMINATOR, &data),
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(find_config_address().0, false),
+            AccountMeta::new(mint_config, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    )
+}
+
+pub fn add_mint_to_list(admin: &Pubkey, mint: &Pubkey) -> Instruction {
+    let (mint_config, bump) = find_mint_config_address(mint);
+
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[*AddMintToList::DISCRIMINATOR, bump],
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(find_config_address().0, false),
+            AccountMeta::new(mint_config, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+    )
+}
+
+pub fn remove_mint_from_list(admin: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        PROGRAM_ID,
+        &[*RemoveMintFromList::DISCRIMINATOR],
+        vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(find_config_address().0, false),
+            AccountMeta::new(find_mint_config_address(mint).0, false),
+        ],
+    )
+}
@@ -0,0 +1,83 @@
+use std::{vec, vec::Vec};
+
+use solana_pubkey::Pubkey;
+
+use crate::states::MerkleVault;
+
+/// A `(recipient, amount, unlock_timestamp)` leaf of a merkle vault.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleLeaf {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub unlock_timestamp: i64,
+}
+
+impl MerkleLeaf {
+    pub fn hash(&self) -> [u8; 32] {
+        MerkleVault::leaf_hash(
+            &self.recipient.to_bytes(),
+            self.amount,
+            self.unlock_timestamp,
+        )
+    }
+}
+
+/// The tree of the leaves of a merkle vault, hashed the way the program
+/// verifies claims.
+///
+/// A node without a sibling moves up a level unchanged, so a proof has at
+/// most one hash per level.
+pub struct MerkleTree {
+    /// The hashes of each level, from the leaves to the root
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    /// Builds the tree of `leaves`.
+    ///
+    /// # Panics
+    ///
+    /// If `leaves` is empty.
+    pub fn new(leaves: &[MerkleLeaf]) -> Self {
+        assert!(!leaves.is_empty(), "a merkle tree needs at least one leaf");
+
+        let mut levels = vec![leaves.iter().map(MerkleLeaf::hash).collect::<Vec<_>>()];
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => MerkleVault::node_hash(left, right),
+                    [node] => *node,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        MerkleTree { levels }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels[self.levels.len() - 1][0]
+    }
+
+    /// Returns the proof of the leaf at `index`, the sibling hashes from the
+    /// leaf up.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn proof(&self, index: usize) -> Vec<[u8; 32]> {
+        assert!(index < self.levels[0].len(), "leaf index out of bounds");
+
+        let mut proof = Vec::with_capacity(self.levels.len() - 1);
+        let mut index = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        proof
+    }
+}
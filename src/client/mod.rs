@@ -0,0 +1,31 @@
+//! Instruction builders and account decoders for off-chain clients.
+//!
+//! Only available with the `client` feature, which pulls in `std` and the
+//! Solana SDK types.
+
+pub mod accounts;
+pub mod filters;
+pub mod instructions;
+pub mod merkle;
+pub mod pda;
+
+pub use accounts::*;
+pub use instructions::*;
+pub use merkle::*;
+pub use pda::*;
+
+use solana_pubkey::Pubkey;
+
+/// The program id.
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(crate::ID);
+
+pub const SYSTEM_PROGRAM_ID: Pubkey = solana_pubkey::pubkey!("11111111111111111111111111111111");
+
+pub const TOKEN_PROGRAM_ID: Pubkey =
+    solana_pubkey::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    solana_pubkey::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+pub const BPF_LOADER_UPGRADEABLE_ID: Pubkey =
+    Pubkey::new_from_array(crate::instructions::BPF_LOADER_UPGRADEABLE_ID);
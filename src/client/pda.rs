@@ -0,0 +1,98 @@
+use solana_pubkey::Pubkey;
+
+use crate::{
+    client::{
+        ASSOCIATED_TOKEN_PROGRAM_ID, BPF_LOADER_UPGRADEABLE_ID, PROGRAM_ID, TOKEN_PROGRAM_ID,
+    },
+    events::{EVENT_AUTHORITY, EVENT_AUTHORITY_BUMP},
+    states::{ClaimReceipt, Config, LockedBucket, MerkleVault, MintConfig, UserRegistry, Vault},
+};
+
+/// Returns the vault PDA of `owner` and its bump, `mint` is `None` for SOL
+/// vaults.
+pub fn find_vault_address(
+    owner: &Pubkey,
+    mint: Option<&Pubkey>,
+    amount: u64,
+    unlock_timestamp: i64,
+) -> (Pubkey, u8) {
+    let (vault, bump) = Vault::find_address(
+        owner.as_array(),
+        mint.map(Pubkey::as_array),
+        amount,
+        unlock_timestamp,
+    );
+    (Pubkey::new_from_array(vault), bump)
+}
+
+pub fn find_registry_address(owner: &Pubkey) -> (Pubkey, u8) {
+    let (registry, bump) = UserRegistry::find_address(owner.as_array());
+    (Pubkey::new_from_array(registry), bump)
+}
+
+pub fn find_config_address() -> (Pubkey, u8) {
+    let (config, bump) = Config::find_address();
+    (Pubkey::new_from_array(config), bump)
+}
+
+pub fn find_mint_config_address(mint: &Pubkey) -> (Pubkey, u8) {
+    let (mint_config, bump) = MintConfig::find_address(mint.as_array());
+    (Pubkey::new_from_array(mint_config), bump)
+}
+
+/// Returns the merkle vault PDA of `funder` for the tree of `root`.
+pub fn find_merkle_vault_address(funder: &Pubkey, mint: &Pubkey, root: &[u8; 32]) -> (Pubkey, u8) {
+    let (merkle_vault, bump) = MerkleVault::find_address(funder.as_array(), mint.as_array(), root);
+    (Pubkey::new_from_array(merkle_vault), bump)
+}
+
+/// Returns the claim receipt PDA of the leaf hashed to `leaf`.
+pub fn find_claim_receipt_address(merkle_vault: &Pubkey, leaf: &[u8; 32]) -> (Pubkey, u8) {
+    let (claim_receipt, bump) = ClaimReceipt::find_address(merkle_vault.as_array(), leaf);
+    (Pubkey::new_from_array(claim_receipt), bump)
+}
+
+/// Returns the receipt mint PDA of `vault`.
+pub fn find_receipt_mint_address(vault: &Pubkey) -> (Pubkey, u8) {
+    let (receipt_mint, bump) = Vault::find_receipt_address(vault.as_array());
+    (Pubkey::new_from_array(receipt_mint), bump)
+}
+
+/// Returns the locked bucket PDA pooling deposits of `mint` until
+/// `unlock_timestamp`.
+pub fn find_locked_bucket_address(mint: &Pubkey, unlock_timestamp: i64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            LockedBucket::SEED,
+            mint.as_ref(),
+            &unlock_timestamp.to_le_bytes(),
+        ],
+        &PROGRAM_ID,
+    )
+}
+
+/// Returns the receipt mint PDA of the locked `bucket`.
+pub fn find_bucket_receipt_mint_address(bucket: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LockedBucket::RECEIPT_SEED, bucket.as_ref()], &PROGRAM_ID)
+}
+
+pub fn event_authority_address() -> (Pubkey, u8) {
+    (
+        Pubkey::new_from_array(EVENT_AUTHORITY),
+        EVENT_AUTHORITY_BUMP,
+    )
+}
+
+/// Returns the program data account of the program.
+pub fn find_program_data_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_ID.as_ref()], &BPF_LOADER_UPGRADEABLE_ID)
+}
+
+/// Returns the associated token account of `wallet` for `mint`.
+pub fn get_associated_token_address(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
@@ -0,0 +1,14 @@
+//! Calls into the program from other programs. Build them with the `cpi`
+//! feature and without the default `entrypoint` feature.
+//!
+//! The instruction layouts and the return data of the queries only change
+//! with a new discriminator, so other programs can rely on them. Sign with
+//! `invoke_signed` when the vault owner is a PDA of the calling program.
+
+pub mod query;
+pub mod sol;
+pub mod spl;
+
+pub use query::*;
+pub use sol::*;
+pub use spl::*;
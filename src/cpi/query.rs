@@ -0,0 +1,94 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::{get_return_data, invoke},
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+};
+
+use crate::{
+    instructions::{self, GetVotingPowerInstructionData, VaultStatus, VotingPower},
+    utils::{load_acc_unchecked, to_bytes, DataLen},
+};
+
+/// Invokes the query of `data` on `vault` and returns the `T` it set as
+/// return data.
+fn query<T: DataLen + Copy>(vault: &AccountInfo, data: &[u8]) -> Result<T, ProgramError> {
+    let instruction = Instruction {
+        program_id: &crate::ID,
+        data,
+        accounts: &query_account_metas(vault),
+    };
+    invoke(&instruction, &[vault])?;
+
+    let return_data = get_return_data().ok_or(ProgramError::InvalidAccountData)?;
+    if return_data.program_id().ne(&crate::ID) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(*unsafe { load_acc_unchecked::<T>(return_data.as_slice()) }?)
+}
+
+/// The only account of a query, the vault it reads.
+fn query_account_metas(vault: &AccountInfo) -> [AccountMeta<'_>; 1] {
+    [AccountMeta::readonly(vault.key())]
+}
+
+/// Queries the [`VotingPower`] of a vault, see
+/// [`GetVotingPower`](instructions::GetVotingPower).
+pub struct GetVotingPower<'a> {
+    /// The vault to weigh
+    pub vault: &'a AccountInfo,
+    /// The multiplier in basis points for a remaining lock time of
+    /// `max_lock_duration` or more, at least 10,000 (1x)
+    pub max_multiplier_bps: u16,
+    /// The remaining lock time in seconds from which the multiplier stops
+    /// growing
+    pub max_lock_duration: i64,
+}
+
+impl GetVotingPower<'_> {
+    /// The accounts in the order the program reads them.
+    pub fn account_metas(&self) -> [AccountMeta<'_>; 1] {
+        query_account_metas(self.vault)
+    }
+
+    /// The discriminator followed by the arguments.
+    pub fn instruction_data(&self) -> [u8; 1 + GetVotingPowerInstructionData::LEN] {
+        let mut data = [0u8; 1 + GetVotingPowerInstructionData::LEN];
+        data[0] = *instructions::GetVotingPower::DISCRIMINATOR;
+        data[1..].copy_from_slice(unsafe {
+            to_bytes(&GetVotingPowerInstructionData {
+                max_multiplier_bps: self.max_multiplier_bps,
+                max_lock_duration: self.max_lock_duration,
+            })
+        });
+        data
+    }
+
+    pub fn invoke(&self) -> Result<VotingPower, ProgramError> {
+        query(self.vault, &self.instruction_data())
+    }
+}
+
+/// Queries the [`VaultStatus`] of a vault, see
+/// [`GetVaultStatus`](instructions::GetVaultStatus).
+pub struct GetVaultStatus<'a> {
+    /// The vault to query
+    pub vault: &'a AccountInfo,
+}
+
+impl GetVaultStatus<'_> {
+    /// The accounts in the order the program reads them.
+    pub fn account_metas(&self) -> [AccountMeta<'_>; 1] {
+        query_account_metas(self.vault)
+    }
+
+    /// The discriminator, the query has no arguments.
+    pub fn instruction_data(&self) -> [u8; 1] {
+        [*instructions::GetVaultStatus::DISCRIMINATOR]
+    }
+
+    pub fn invoke(&self) -> Result<VaultStatus, ProgramError> {
+        query(self.vault, &self.instruction_data())
+    }
+}
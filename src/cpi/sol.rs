@@ -0,0 +1,165 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::invoke_signed,
+    instruction::{AccountMeta, Instruction, Signer},
+    ProgramResult,
+};
+
+use crate::{
+    instructions::{self, InitializeSolVaultInstructionData},
+    utils::{to_bytes, DataLen},
+};
+
+/// Locks lamports of `signer` in a new vault, see
+/// [`InitializeSolVault`](instructions::InitializeSolVault).
+pub struct InitializeSolVault<'a> {
+    /// The vault owner, pays for the vault and the lamports locked
+    pub signer: &'a AccountInfo,
+    /// The vault PDA account to be created
+    pub vault: &'a AccountInfo,
+    /// The Solana System Program
+    pub system_program: &'a AccountInfo,
+    /// The signer's user registry PDA (created on first use)
+    pub registry: &'a AccountInfo,
+    /// The program config PDA
+    pub config: &'a AccountInfo,
+    /// The treasury recorded in the config
+    pub treasury: &'a AccountInfo,
+    /// The event authority PDA
+    pub event_authority: &'a AccountInfo,
+    /// This program
+    pub program: &'a AccountInfo,
+    /// Amount of lamports to lock
+    pub amount: u64,
+    /// Unlock timestamp of the vault
+    pub unlock_timestamp: i64,
+    /// Bump seed for the vault PDA
+    pub bump: u8,
+    /// Bump seed for the user registry PDA
+    pub registry_bump: u8,
+    /// Penalty in basis points for withdrawing before the unlock timestamp
+    /// (0 = not allowed)
+    pub early_exit_penalty_bps: u16,
+}
+
+impl InitializeSolVault<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    /// The accounts in the order the program reads them.
+    pub fn account_metas(&self) -> [AccountMeta<'_>; 8] {
+        [
+            AccountMeta::writable_signer(self.signer.key()),
+            AccountMeta::writable(self.vault.key()),
+            AccountMeta::readonly(self.system_program.key()),
+            AccountMeta::writable(self.registry.key()),
+            AccountMeta::readonly(self.config.key()),
+            AccountMeta::writable(self.treasury.key()),
+            AccountMeta::readonly(self.event_authority.key()),
+            AccountMeta::readonly(self.program.key()),
+        ]
+    }
+
+    /// The discriminator followed by the arguments.
+    pub fn instruction_data(&self) -> [u8; 1 + InitializeSolVaultInstructionData::LEN] {
+        let mut data = [0u8; 1 + InitializeSolVaultInstructionData::LEN];
+        data[0] = *instructions::InitializeSolVault::DISCRIMINATOR;
+        data[1..].copy_from_slice(unsafe {
+            to_bytes(&InitializeSolVaultInstructionData {
+                amount: self.amount,
+                unlock_timestamp: self.unlock_timestamp,
+                bump: self.bump,
+                registry_bump: self.registry_bump,
+                early_exit_penalty_bps: self.early_exit_penalty_bps,
+            })
+        });
+        data
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_metas = self.account_metas();
+        let data = self.instruction_data();
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: &data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.signer,
+                self.vault,
+                self.system_program,
+                self.registry,
+                self.config,
+                self.treasury,
+                self.event_authority,
+                self.program,
+            ],
+            signers,
+        )
+    }
+}
+
+/// Releases the lamports of an unlocked vault to `signer` and closes it, see
+/// [`WithdrawSolVault`](instructions::WithdrawSolVault).
+pub struct WithdrawSolVault<'a> {
+    /// The vault owner, receives the lamports
+    pub signer: &'a AccountInfo,
+    /// The vault account to withdraw from
+    pub vault: &'a AccountInfo,
+    /// The vault owner's user registry PDA
+    pub registry: &'a AccountInfo,
+    /// The event authority PDA
+    pub event_authority: &'a AccountInfo,
+    /// This program
+    pub program: &'a AccountInfo,
+}
+
+impl WithdrawSolVault<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    /// The accounts in the order the program reads them.
+    pub fn account_metas(&self) -> [AccountMeta<'_>; 5] {
+        [
+            AccountMeta::writable_signer(self.signer.key()),
+            AccountMeta::writable(self.vault.key()),
+            AccountMeta::writable(self.registry.key()),
+            AccountMeta::readonly(self.event_authority.key()),
+            AccountMeta::readonly(self.program.key()),
+        ]
+    }
+
+    /// The discriminator, the instruction has no arguments.
+    pub fn instruction_data(&self) -> [u8; 1] {
+        [*instructions::WithdrawSolVault::DISCRIMINATOR]
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_metas = self.account_metas();
+        let data = self.instruction_data();
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: &data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.signer,
+                self.vault,
+                self.registry,
+                self.event_authority,
+                self.program,
+            ],
+            signers,
+        )
+    }
+}
@@ -0,0 +1,216 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::invoke_signed,
+    instruction::{AccountMeta, Instruction, Signer},
+    ProgramResult,
+};
+
+use crate::{
+    instructions::{self, InitializeSplVaultInstructionData},
+    utils::{to_bytes, DataLen},
+};
+
+/// Locks tokens of `user_ata` in a new vault owned by `signer`, see
+/// [`InitializeSplVault`](instructions::InitializeSplVault).
+pub struct InitializeSplVault<'a> {
+    /// The vault owner, pays for the vault accounts
+    pub signer: &'a AccountInfo,
+    /// The vault PDA account to be created
+    pub vault: &'a AccountInfo,
+    /// The SPL token mint account
+    pub mint: &'a AccountInfo,
+    /// Signer's token account the tokens are taken from
+    pub user_ata: &'a AccountInfo,
+    /// Vault's associated token account (created by instruction)
+    pub vault_ata: &'a AccountInfo,
+    /// The SPL Token Program
+    pub token_program: &'a AccountInfo,
+    /// The Associated Token Program
+    pub associated_token_program: &'a AccountInfo,
+    /// The Solana System Program
+    pub system_program: &'a AccountInfo,
+    /// The signer's user registry PDA (created on first use)
+    pub registry: &'a AccountInfo,
+    /// The program config PDA
+    pub config: &'a AccountInfo,
+    /// The treasury recorded in the config
+    pub treasury: &'a AccountInfo,
+    /// Treasury's token account for the mint
+    pub treasury_ata: &'a AccountInfo,
+    /// The mint config PDA of the mint (may be uninitialized)
+    pub mint_config: &'a AccountInfo,
+    /// The event authority PDA
+    pub event_authority: &'a AccountInfo,
+    /// This program
+    pub program: &'a AccountInfo,
+    /// Amount of tokens to lock
+    pub amount: u64,
+    /// Unlock timestamp of the vault
+    pub unlock_timestamp: i64,
+    /// Bump seed for the vault PDA
+    pub bump: u8,
+    /// Bump seed for the user registry PDA
+    pub registry_bump: u8,
+    /// Penalty in basis points for withdrawing before the unlock timestamp
+    /// (0 = not allowed)
+    pub early_exit_penalty_bps: u16,
+    /// Bump seed for the mint config PDA
+    pub mint_config_bump: u8,
+}
+
+impl InitializeSplVault<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    /// The accounts in the order the program reads them.
+    pub fn account_metas(&self) -> [AccountMeta<'_>; 15] {
+        [
+            AccountMeta::writable_signer(self.signer.key()),
+            AccountMeta::writable(self.vault.key()),
+            AccountMeta::readonly(self.mint.key()),
+            AccountMeta::writable(self.user_ata.key()),
+            AccountMeta::writable(self.vault_ata.key()),
+            AccountMeta::readonly(self.token_program.key()),
+            AccountMeta::readonly(self.associated_token_program.key()),
+            AccountMeta::readonly(self.system_program.key()),
+            AccountMeta::writable(self.registry.key()),
+            AccountMeta::readonly(self.config.key()),
+            AccountMeta::writable(self.treasury.key()),
+            AccountMeta::writable(self.treasury_ata.key()),
+            AccountMeta::readonly(self.mint_config.key()),
+            AccountMeta::readonly(self.event_authority.key()),
+            AccountMeta::readonly(self.program.key()),
+        ]
+    }
+
+    /// The discriminator followed by the arguments.
+    pub fn instruction_data(&self) -> [u8; 1 + InitializeSplVaultInstructionData::LEN] {
+        let mut data = [0u8; 1 + InitializeSplVaultInstructionData::LEN];
+        data[0] = *instructions::InitializeSplVault::DISCRIMINATOR;
+        data[1..].copy_from_slice(unsafe {
+            to_bytes(&InitializeSplVaultInstructionData {
+                amount: self.amount,
+                unlock_timestamp: self.unlock_timestamp,
+                bump: self.bump,
+                registry_bump: self.registry_bump,
+                early_exit_penalty_bps: self.early_exit_penalty_bps,
+                mint_config_bump: self.mint_config_bump,
+            })
+        });
+        data
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_metas = self.account_metas();
+        let data = self.instruction_data();
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: &data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.signer,
+                self.vault,
+                self.mint,
+                self.user_ata,
+                self.vault_ata,
+                self.token_program,
+                self.associated_token_program,
+                self.system_program,
+                self.registry,
+                self.config,
+                self.treasury,
+                self.treasury_ata,
+                self.mint_config,
+                self.event_authority,
+                self.program,
+            ],
+            signers,
+        )
+    }
+}
+
+/// Releases the tokens of an unlocked vault to `user_ata` and closes it, see
+/// [`WithdrawSplVault`](instructions::WithdrawSplVault).
+pub struct WithdrawSplVault<'a> {
+    /// The vault owner, receives the rent of the vault accounts
+    pub signer: &'a AccountInfo,
+    /// The vault account to withdraw from
+    pub vault: &'a AccountInfo,
+    /// The SPL token mint account
+    pub mint: &'a AccountInfo,
+    /// Signer's token account receiving the tokens
+    pub user_ata: &'a AccountInfo,
+    /// Vault's associated token account
+    pub vault_ata: &'a AccountInfo,
+    /// The SPL Token Program
+    pub token_program: &'a AccountInfo,
+    /// The Solana System Program
+    pub system_program: &'a AccountInfo,
+    /// The vault owner's user registry PDA
+    pub registry: &'a AccountInfo,
+    /// The event authority PDA
+    pub event_authority: &'a AccountInfo,
+    /// This program
+    pub program: &'a AccountInfo,
+}
+
+impl WithdrawSplVault<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    /// The accounts in the order the program reads them.
+    pub fn account_metas(&self) -> [AccountMeta<'_>; 10] {
+        [
+            AccountMeta::writable_signer(self.signer.key()),
+            AccountMeta::writable(self.vault.key()),
+            AccountMeta::writable(self.mint.key()),
+            AccountMeta::writable(self.user_ata.key()),
+            AccountMeta::writable(self.vault_ata.key()),
+            AccountMeta::readonly(self.token_program.key()),
+            AccountMeta::readonly(self.system_program.key()),
+            AccountMeta::writable(self.registry.key()),
+            AccountMeta::readonly(self.event_authority.key()),
+            AccountMeta::readonly(self.program.key()),
+        ]
+    }
+
+    /// The discriminator, the instruction has no arguments.
+    pub fn instruction_data(&self) -> [u8; 1] {
+        [*instructions::WithdrawSplVault::DISCRIMINATOR]
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_metas = self.account_metas();
+        let data = self.instruction_data();
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            accounts: &account_metas,
+            data: &data,
+        };
+
+        invoke_signed(
+            &instruction,
+            &[
+                self.signer,
+                self.vault,
+                self.mint,
+                self.user_ata,
+                self.vault_ata,
+                self.token_program,
+                self.system_program,
+                self.registry,
+                self.event_authority,
+                self.program,
+            ],
+            signers,
+        )
+    }
+}
@@ -7,6 +7,25 @@ pub enum TimeBaseVaultError {
     Unauthorized,
     VaultLocking,
     InvalidVaultMint,
+    RegistryFull,
+    InvalidConfig,
+    ProgramPaused,
+    FeeOverflow,
+    InvalidTreasury,
+    InvalidEarlyExitPenalty,
+    InvalidPenaltyRecipient,
+    LockDurationTooShort,
+    LockDurationTooLong,
+    InvalidBatchSize,
+    InvalidMerkleProof,
+    AlreadyClaimed,
+    InvalidSplitAmount,
+    UnlockTimestampTooEarly,
+    EarlyExitPenaltyMismatch,
+    ReceiptRequired,
+    InvalidVotingPowerConfig,
+    NonCanonicalBump,
+    InvalidReclaimTimestamp,
 }
 
 impl From<TimeBaseVaultError> for ProgramError {
@@ -14,3 +33,44 @@ impl From<TimeBaseVaultError> for ProgramError {
         Self::Custom(e as u32)
     }
 }
+
+#[cfg(feature = "idl")]
+macro_rules! idl_errors {
+    ($($name:ident: $msg:literal),* $(,)?) => {
+        &[$(crate::idl::IdlError {
+            name: stringify!($name),
+            code: TimeBaseVaultError::$name as u32,
+            msg: $msg,
+        }),*]
+    };
+}
+
+#[cfg(feature = "idl")]
+impl TimeBaseVaultError {
+    pub const IDL: &'static [crate::idl::IdlError] = idl_errors!(
+        UnlockTimestampMustBeInFuture: "The unlock timestamp must be in the future",
+        AmountMustBeGreaterThanZero: "The amount must be greater than zero",
+        Unauthorized: "Only the vault owner can withdraw",
+        VaultLocking: "Cannot withdraw before unlock timestamp",
+        InvalidVaultMint: "Invalid mint address for SPL vault",
+        RegistryFull: "The registry tracks too many mints",
+        InvalidConfig: "The config settings are out of range",
+        ProgramPaused: "The creation of new vaults is paused",
+        FeeOverflow: "The creation fee overflows",
+        InvalidTreasury: "The treasury does not match the config",
+        InvalidEarlyExitPenalty: "The early exit penalty exceeds 10,000 bps",
+        InvalidPenaltyRecipient: "The penalty recipient does not match",
+        LockDurationTooShort: "The lock duration is below the minimum",
+        LockDurationTooLong: "The lock duration exceeds the maximum",
+        InvalidBatchSize: "The batch is empty or exceeds 16 vaults",
+        InvalidMerkleProof: "The leaf is not in the merkle vault's tree",
+        AlreadyClaimed: "The leaf was already claimed",
+        InvalidSplitAmount: "The split amount must be below the vault amount",
+        UnlockTimestampTooEarly: "A vault can not unlock before the vault its funds come from",
+        EarlyExitPenaltyMismatch: "The destination vault allows an earlier exit than the source vault",
+        ReceiptRequired: "The vault can only be withdrawn by the holder of its receipt token",
+        InvalidVotingPowerConfig: "The max multiplier is below 10,000 bps or the max lock duration is not positive",
+        NonCanonicalBump: "The bump is not the canonical bump of the account address",
+        InvalidReclaimTimestamp: "The reclaim timestamp leaves the recipients less than the claim window after the max lock duration",
+    );
+}
@@ -0,0 +1,499 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::invoke_signed,
+    instruction::{AccountMeta, Instruction, Seed, Signer},
+    log::sol_log_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+use pinocchio_pubkey::derive_address_const;
+
+use crate::{
+    instructions::EmitEvent,
+    utils::{to_bytes, DataLen},
+};
+
+/// The version of the event layouts, bumped on every breaking change.
+pub const EVENT_VERSION: u8 = 1;
+
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"event_authority";
+pub const EVENT_AUTHORITY_BUMP: u8 = 254;
+
+/// The PDA signing the `EmitEvent` self-CPIs.
+pub const EVENT_AUTHORITY: Pubkey = derive_address_const(
+    &[EVENT_AUTHORITY_SEED],
+    Some(EVENT_AUTHORITY_BUMP),
+    &crate::ID,
+);
+
+/// The maximum length of the `EmitEvent` instruction data.
+const MAX_EMIT_DATA_LEN: usize = 256;
+
+/// Verifies the accounts an instruction needs to emit events.
+pub fn check_event_accounts(
+    event_authority: &AccountInfo,
+    program: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if event_authority.key().ne(&EVENT_AUTHORITY) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if program.key().ne(&crate::ID) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(())
+}
+
+/// An event serialized as `[EVENT_VERSION, DISCRIMINATOR]` followed by the
+/// bytes of the event.
+///
+/// Events are logged with `sol_log_data` and also passed as the data of an
+/// `EmitEvent` self-CPI, as logs can be truncated while inner instructions
+/// can not.
+pub trait Event: DataLen + Sized {
+    const DISCRIMINATOR: u8;
+
+    fn emit(&self, event_authority: &AccountInfo) -> ProgramResult {
+        const { assert!(Self::LEN + 3 <= MAX_EMIT_DATA_LEN) };
+
+        let bytes = unsafe { to_bytes(self) };
+        sol_log_data(&[&[EVENT_VERSION, Self::DISCRIMINATOR], bytes]);
+
+        let mut data = [0u8; MAX_EMIT_DATA_LEN];
+        data[0] = *EmitEvent::DISCRIMINATOR;
+        data[1] = EVENT_VERSION;
+        data[2] = Self::DISCRIMINATOR;
+        data[3..Self::LEN + 3].copy_from_slice(bytes);
+
+        let instruction = Instruction {
+            program_id: &crate::ID,
+            data: &data[..Self::LEN + 3],
+            accounts: &[AccountMeta::readonly_signer(event_authority.key())],
+        };
+        let bump_binding = [EVENT_AUTHORITY_BUMP];
+        let seed = [Seed::from(EVENT_AUTHORITY_SEED), Seed::from(&bump_binding)];
+
+        invoke_signed(&instruction, &[event_authority], &[Signer::from(&seed)])
+    }
+}
+
+/// Emitted when a vault is created.
+#[repr(C)]
+pub struct VaultCreated {
+    /// The vault address
+    pub vault: Pubkey,
+    /// The vault owner
+    pub owner: Pubkey,
+    /// The token mint (None for SOL vaults)
+    pub mint: Option<Pubkey>,
+    /// The vault id in the owner's registry
+    pub id: [u8; 8], // u64 as bytes
+    /// The amount locked
+    pub amount: [u8; 8], // u64 as bytes
+    /// The unlock timestamp
+    pub unlock_timestamp: [u8; 8], // i64 as bytes
+    /// The timestamp of the creation
+    pub timestamp: [u8; 8], // i64 as bytes
+}
+
+impl DataLen for VaultCreated {
+    const LEN: usize = core::mem::size_of::<VaultCreated>();
+}
+
+#[cfg(feature = "idl")]
+impl VaultCreated {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "VaultCreated",
+            docs: "Emitted when a vault is created",
+            len: Self::LEN,
+            fields: idl_fields!(VaultCreated {
+                vault: IdlType::PublicKey,
+                owner: IdlType::PublicKey,
+                mint: IdlType::Option(&IdlType::PublicKey),
+                id: IdlType::U64,
+                amount: IdlType::U64,
+                unlock_timestamp: IdlType::I64,
+                timestamp: IdlType::I64,
+            }),
+        }
+    };
+}
+
+impl Event for VaultCreated {
+    const DISCRIMINATOR: u8 = 0;
+}
+
+/// Emitted when a vault is withdrawn and closed.
+#[repr(C)]
+pub struct VaultWithdrawn {
+    /// The vault address
+    pub vault: Pubkey,
+    /// The vault owner
+    pub owner: Pubkey,
+    /// The token mint (None for SOL vaults)
+    pub mint: Option<Pubkey>,
+    /// The vault id in the owner's registry
+    pub id: [u8; 8], // u64 as bytes
+    /// The amount locked
+    pub amount: [u8; 8], // u64 as bytes
+    /// The early withdrawal penalty taken from the amount
+    pub penalty: [u8; 8], // u64 as bytes
+    /// The unlock timestamp
+    pub unlock_timestamp: [u8; 8], // i64 as bytes
+    /// The timestamp of the withdrawal
+    pub timestamp: [u8; 8], // i64 as bytes
+}
+
+impl DataLen for VaultWithdrawn {
+    const LEN: usize = core::mem::size_of::<VaultWithdrawn>();
+}
+
+#[cfg(feature = "idl")]
+impl VaultWithdrawn {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "VaultWithdrawn",
+            docs: "Emitted when a vault is withdrawn and closed",
+            len: Self::LEN,
+            fields: idl_fields!(VaultWithdrawn {
+                vault: IdlType::PublicKey,
+                owner: IdlType::PublicKey,
+                mint: IdlType::Option(&IdlType::PublicKey),
+                id: IdlType::U64,
+                amount: IdlType::U64,
+                penalty: IdlType::U64,
+                unlock_timestamp: IdlType::I64,
+                timestamp: IdlType::I64,
+            }),
+        }
+    };
+}
+
+impl Event for VaultWithdrawn {
+    const DISCRIMINATOR: u8 = 1;
+}
+
+/// Emitted when a leaf of a merkle vault is claimed.
+#[repr(C)]
+pub struct MerkleClaimed {
+    /// The merkle vault address
+    pub merkle_vault: Pubkey,
+    /// The recipient of the leaf
+    pub recipient: Pubkey,
+    /// The token mint
+    pub mint: Pubkey,
+    /// The amount claimed
+    pub amount: [u8; 8], // u64 as bytes
+    /// The unlock timestamp of the leaf
+    pub unlock_timestamp: [u8; 8], // i64 as bytes
+    /// The timestamp of the claim
+    pub timestamp: [u8; 8], // i64 as bytes
+}
+
+impl DataLen for MerkleClaimed {
+    const LEN: usize = core::mem::size_of::<MerkleClaimed>();
+}
+
+#[cfg(feature = "idl")]
+impl MerkleClaimed {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "MerkleClaimed",
+            docs: "Emitted when a leaf of a merkle vault is claimed",
+            len: Self::LEN,
+            fields: idl_fields!(MerkleClaimed {
+                merkle_vault: IdlType::PublicKey,
+                recipient: IdlType::PublicKey,
+                mint: IdlType::PublicKey,
+                amount: IdlType::U64,
+                unlock_timestamp: IdlType::I64,
+                timestamp: IdlType::I64,
+            }),
+        }
+    };
+}
+
+impl Event for MerkleClaimed {
+    const DISCRIMINATOR: u8 = 2;
+}
+
+/// Emitted when part of a vault is moved into a new vault.
+#[repr(C)]
+pub struct VaultSplit {
+    /// The vault the amount was taken from
+    pub vault: Pubkey,
+    /// The vault created with the amount
+    pub new_vault: Pubkey,
+    /// The token mint (None for SOL vaults)
+    pub mint: Option<Pubkey>,
+    /// The amount moved
+    pub amount: [u8; 8], // u64 as bytes
+    /// The amount left in the vault
+    pub remaining: [u8; 8], // u64 as bytes
+    /// The timestamp of the split
+    pub timestamp: [u8; 8], // i64 as bytes
+}
+
+impl DataLen for VaultSplit {
+    const LEN: usize = core::mem::size_of::<VaultSplit>();
+}
+
+#[cfg(feature = "idl")]
+impl VaultSplit {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "VaultSplit",
+            docs: "Emitted when part of a vault is moved into a new vault",
+            len: Self::LEN,
+            fields: idl_fields!(VaultSplit {
+                vault: IdlType::PublicKey,
+                new_vault: IdlType::PublicKey,
+                mint: IdlType::Option(&IdlType::PublicKey),
+                amount: IdlType::U64,
+                remaining: IdlType::U64,
+                timestamp: IdlType::I64,
+            }),
+        }
+    };
+}
+
+impl Event for VaultSplit {
+    const DISCRIMINATOR: u8 = 3;
+}
+
+/// Emitted when a vault is merged into another vault of its owner.
+#[repr(C)]
+pub struct VaultsMerged {
+    /// The vault that was closed
+    pub source: Pubkey,
+    /// The vault that received the amount
+    pub destination: Pubkey,
+    /// The token mint (None for SOL vaults)
+    pub mint: Option<Pubkey>,
+    /// The amount moved
+    pub amount: [u8; 8], // u64 as bytes
+    /// The amount of the destination vault after the merge
+    pub total: [u8; 8], // u64 as bytes
+    /// The timestamp of the merge
+    pub timestamp: [u8; 8], // i64 as bytes
+}
+
+impl DataLen for VaultsMerged {
+    const LEN: usize = core::mem::size_of::<VaultsMerged>();
+}
+
+#[cfg(feature = "idl")]
+impl VaultsMerged {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "VaultsMerged",
+            docs: "Emitted when a vault is merged into another vault of its owner",
+            len: Self::LEN,
+            fields: idl_fields!(VaultsMerged {
+                source: IdlType::PublicKey,
+                destination: IdlType::PublicKey,
+                mint: IdlType::Option(&IdlType::PublicKey),
+                amount: IdlType::U64,
+                total: IdlType::U64,
+                timestamp: IdlType::I64,
+            }),
+        }
+    };
+}
+
+impl Event for VaultsMerged {
+    const DISCRIMINATOR: u8 = 4;
+}
+
+/// Emitted when tokens are deposited into a locked bucket.
+#[repr(C)]
+pub struct BucketDeposited {
+    /// The bucket address
+    pub bucket: Pubkey,
+    /// The account that deposited the tokens and received the receipts
+    pub depositor: Pubkey,
+    /// The token mint
+    pub mint: Pubkey,
+    /// The amount deposited
+    pub amount: [u8; 8], // u64 as bytes
+    /// The unlock timestamp of the bucket
+    pub unlock_timestamp: [u8; 8], // i64 as bytes
+    /// The timestamp of the deposit
+    pub timestamp: [u8; 8], // i64 as bytes
+}
+
+impl DataLen for BucketDeposited {
+    const LEN: usize = core::mem::size_of::<BucketDeposited>();
+}
+
+#[cfg(feature = "idl")]
+impl BucketDeposited {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "BucketDeposited",
+            docs: "Emitted when tokens are deposited into a locked bucket",
+            len: Self::LEN,
+            fields: idl_fields!(BucketDeposited {
+                bucket: IdlType::PublicKey,
+                depositor: IdlType::PublicKey,
+                mint: IdlType::PublicKey,
+                amount: IdlType::U64,
+                unlock_timestamp: IdlType::I64,
+                timestamp: IdlType::I64,
+            }),
+        }
+    };
+}
+
+impl Event for BucketDeposited {
+    const DISCRIMINATOR: u8 = 5;
+}
+
+/// Emitted when receipts of a locked bucket are redeemed.
+#[repr(C)]
+pub struct BucketRedeemed {
+    /// The bucket address
+    pub bucket: Pubkey,
+    /// The account that burnt the receipts
+    pub holder: Pubkey,
+    /// The token mint
+    pub mint: Pubkey,
+    /// The amount redeemed
+    pub amount: [u8; 8], // u64 as bytes
+    /// The timestamp of the redemption
+    pub timestamp: [u8; 8], // i64 as bytes
+}
+
+impl DataLen for BucketRedeemed {
+    const LEN: usize = core::mem::size_of::<BucketRedeemed>();
+}
+
+#[cfg(feature = "idl")]
+impl BucketRedeemed {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "BucketRedeemed",
+            docs: "Emitted when receipts of a locked bucket are redeemed",
+            len: Self::LEN,
+            fields: idl_fields!(BucketRedeemed {
+                bucket: IdlType::PublicKey,
+                holder: IdlType::PublicKey,
+                mint: IdlType::PublicKey,
+                amount: IdlType::U64,
+                timestamp: IdlType::I64,
+            }),
+        }
+    };
+}
+
+impl Event for BucketRedeemed {
+    const DISCRIMINATOR: u8 = 6;
+}
+
+/// Emitted when the funder of a merkle vault takes back the unclaimed amount.
+#[repr(C)]
+pub struct MerkleReclaimed {
+    /// The merkle vault address
+    pub merkle_vault: Pubkey,
+    /// The funder of the merkle vault
+    pub funder: Pubkey,
+    /// The token mint
+    pub mint: Pubkey,
+    /// The amount returned to the funder
+    pub amount: [u8; 8], // u64 as bytes
+    /// The timestamp of the reclaim
+    pub timestamp: [u8; 8], // i64 as bytes
+}
+
+impl DataLen for MerkleReclaimed {
+    const LEN: usize = core::mem::size_of::<MerkleReclaimed>();
+}
+
+#[cfg(feature = "idl")]
+impl MerkleReclaimed {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "MerkleReclaimed",
+            docs: "Emitted when the funder of a merkle vault takes back the unclaimed amount",
+            len: Self::LEN,
+            fields: idl_fields!(MerkleReclaimed {
+                merkle_vault: IdlType::PublicKey,
+                funder: IdlType::PublicKey,
+                mint: IdlType::PublicKey,
+                amount: IdlType::U64,
+                timestamp: IdlType::I64,
+            }),
+        }
+    };
+}
+
+impl Event for MerkleReclaimed {
+    const DISCRIMINATOR: u8 = 7;
+}
+
+/// Emitted when a merkle vault is created.
+#[repr(C)]
+pub struct MerkleVaultCreated {
+    /// The merkle vault address
+    pub merkle_vault: Pubkey,
+    /// The funder of the merkle vault
+    pub funder: Pubkey,
+    /// The token mint
+    pub mint: Pubkey,
+    /// The root of the tree of leaves
+    pub root: [u8; 32],
+    /// The amount locked for the leaves
+    pub total: [u8; 8], // u64 as bytes
+    /// The timestamp from which the funder can reclaim the unclaimed amount
+    pub reclaim_timestamp: [u8; 8], // i64 as bytes
+    /// The timestamp of the creation
+    pub timestamp: [u8; 8], // i64 as bytes
+}
+
+impl DataLen for MerkleVaultCreated {
+    const LEN: usize = core::mem::size_of::<MerkleVaultCreated>();
+}
+
+#[cfg(feature = "idl")]
+impl MerkleVaultCreated {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "MerkleVaultCreated",
+            docs: "Emitted when a merkle vault is created",
+            len: Self::LEN,
+            fields: idl_fields!(MerkleVaultCreated {
+                merkle_vault: IdlType::PublicKey,
+                funder: IdlType::PublicKey,
+                mint: IdlType::PublicKey,
+                root: IdlType::Array(&IdlType::U8, 32),
+                total: IdlType::U64,
+                reclaim_timestamp: IdlType::I64,
+                timestamp: IdlType::I64,
+            }),
+        }
+    };
+}
+
+impl Event for MerkleVaultCreated {
+    const DISCRIMINATOR: u8 = 8;
+}
@@ -0,0 +1,423 @@
+//! The program interface as data, rendered as a Shank-style JSON IDL by
+//! [`generate`].
+//!
+//! Each instruction and account type carries an `IDL` const next to its
+//! definition. Field offsets are taken with `offset_of!`, so renaming or
+//! removing a field breaks the build instead of silently changing the IDL.
+
+use std::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::{
+    errors::TimeBaseVaultError,
+    events::{
+        BucketDeposited, BucketRedeemed, Event, MerkleClaimed, MerkleReclaimed, MerkleVaultCreated,
+        VaultCreated, VaultSplit, VaultWithdrawn, VaultsMerged, EVENT_VERSION,
+    },
+    instructions::{
+        AddMintToList, BatchInitializeSplVault, BatchVaultEntry, BatchWithdraw, ClaimMerkleVault,
+        DepositLockedBucket, EarlyWithdrawSolVault, EarlyWithdrawSplVault, EmitEvent,
+        GetVaultStatus, GetVotingPower, InitializeConfig, InitializeMerkleVault,
+        InitializeSolVault, InitializeSplVault, MergeVaults, ReclaimMerkleVault,
+        RedeemLockedBucket, RemoveMintFromList, SetMintConfig, SetMintListMode, SetPaused,
+        SplitVault, TransferAdmin, UpdateConfig, VaultStatus, VotingPower, WithdrawSolVault,
+        WithdrawSplVault,
+    },
+    states::{
+        ClaimReceipt, Config, LockedBucket, MerkleVault, MintConfig, MintTotal, UserRegistry, Vault,
+    },
+};
+
+/// The type of an instruction argument or account field.
+pub enum IdlType {
+    Bool,
+    U8,
+    U16,
+    U64,
+    I64,
+    PublicKey,
+    /// An option stored as a one byte tag followed by the value, which takes
+    /// its full size even when the tag is 0
+    Option(&'static IdlType),
+    Array(&'static IdlType, usize),
+    Defined(&'static IdlStruct),
+}
+
+impl IdlType {
+    /// The number of bytes the type takes in the program's layouts.
+    pub const fn size(&self) -> usize {
+        match self {
+            IdlType::Bool | IdlType::U8 => 1,
+            IdlType::U16 => 2,
+            IdlType::U64 | IdlType::I64 => 8,
+            IdlType::PublicKey => 32,
+            IdlType::Option(ty) => 1 + ty.size(),
+            IdlType::Array(ty, len) => ty.size() * *len,
+            IdlType::Defined(def) => def.len,
+        }
+    }
+}
+
+pub struct IdlField {
+    pub name: &'static str,
+    pub ty: IdlType,
+    /// The offset of the field in its struct or instruction arguments
+    pub offset: usize,
+}
+
+/// A `#[repr(C)]` account, event or nested type.
+pub struct IdlStruct {
+    pub name: &'static str,
+    pub docs: &'static str,
+    pub len: usize,
+    pub fields: &'static [IdlField],
+}
+
+pub struct IdlAccountItem {
+    pub name: &'static str,
+    pub writable: bool,
+    pub signer: bool,
+    pub docs: &'static str,
+}
+
+pub struct IdlInstruction {
+    pub name: &'static str,
+    pub docs: &'static str,
+    pub discriminator: u8,
+    pub accounts: &'static [IdlAccountItem],
+    /// The arguments following the discriminator in the instruction data
+    pub args: &'static [IdlField],
+}
+
+pub struct IdlError {
+    pub name: &'static str,
+    pub code: u32,
+    pub msg: &'static str,
+}
+
+/// Lists the fields of `$ty` with their `offset_of!` offsets.
+macro_rules! idl_fields {
+    ($ty:ty { $($field:ident: $idl:expr),* $(,)? }) => {
+        &[$($crate::idl::IdlField {
+            name: stringify!($field),
+            ty: $idl,
+            offset: core::mem::offset_of!($ty, $field),
+        }),*]
+    };
+}
+
+pub(crate) use idl_fields;
+
+impl IdlAccountItem {
+    pub const fn readonly(name: &'static str, docs: &'static str) -> Self {
+        Self::new(name, false, false, docs)
+    }
+
+    pub const fn readonly_signer(name: &'static str, docs: &'static str) -> Self {
+        Self::new(name, false, true, docs)
+    }
+
+    pub const fn writable(name: &'static str, docs: &'static str) -> Self {
+        Self::new(name, true, false, docs)
+    }
+
+    pub const fn writable_signer(name: &'static str, docs: &'static str) -> Self {
+        Self::new(name, true, true, docs)
+    }
+
+    const fn new(name: &'static str, writable: bool, signer: bool, docs: &'static str) -> Self {
+        IdlAccountItem {
+            name,
+            writable,
+            signer,
+            docs,
+        }
+    }
+}
+
+/// The `event_authority` and `program` accounts of the instructions emitting
+/// events.
+pub(crate) const EVENT_AUTHORITY_ACCOUNT: IdlAccountItem =
+    IdlAccountItem::readonly("event_authority", "The event authority PDA");
+pub(crate) const PROGRAM_ACCOUNT: IdlAccountItem =
+    IdlAccountItem::readonly("program", "This program");
+
+pub const INSTRUCTIONS: &[&IdlInstruction] = &[
+    &InitializeSolVault::IDL,
+    &WithdrawSolVault::IDL,
+    &InitializeSplVault::IDL,
+    &WithdrawSplVault::IDL,
+    &InitializeConfig::IDL,
+    &UpdateConfig::IDL,
+    &TransferAdmin::IDL,
+    &SetPaused::IDL,
+    &EarlyWithdrawSolVault::IDL,
+    &EarlyWithdrawSplVault::IDL,
+    &SetMintConfig::IDL,
+    &SetMintListMode::IDL,
+    &AddMintToList::IDL,
+    &RemoveMintFromList::IDL,
+    &EmitEvent::IDL,
+    &BatchInitializeSplVault::IDL,
+    &BatchWithdraw::IDL,
+    &InitializeMerkleVault::IDL,
+    &ClaimMerkleVault::IDL,
+    &SplitVault::IDL,
+    &MergeVaults::IDL,
+    &DepositLockedBucket::IDL,
+    &RedeemLockedBucket::IDL,
+    &GetVotingPower::IDL,
+    &GetVaultStatus::IDL,
+    &ReclaimMerkleVault::IDL,
+];
+
+pub const ACCOUNTS: &[&IdlStruct] = &[
+    &Vault::IDL,
+    &UserRegistry::IDL,
+    &Config::IDL,
+    &MintConfig::IDL,
+    &MerkleVault::IDL,
+    &ClaimReceipt::IDL,
+    &LockedBucket::IDL,
+];
+
+pub const TYPES: &[&IdlStruct] = &[
+    &MintTotal::IDL,
+    &BatchVaultEntry::IDL,
+    &VotingPower::IDL,
+    &VaultStatus::IDL,
+];
+
+/// The events with their discriminators.
+pub const EVENTS: &[(u8, &IdlStruct)] = &[
+    (<VaultCreated as Event>::DISCRIMINATOR, &VaultCreated::IDL),
+    (
+        <VaultWithdrawn as Event>::DISCRIMINATOR,
+        &VaultWithdrawn::IDL,
+    ),
+    (<MerkleClaimed as Event>::DISCRIMINATOR, &MerkleClaimed::IDL),
+    (<VaultSplit as Event>::DISCRIMINATOR, &VaultSplit::IDL),
+    (<VaultsMerged as Event>::DISCRIMINATOR, &VaultsMerged::IDL),
+    (
+        <BucketDeposited as Event>::DISCRIMINATOR,
+        &BucketDeposited::IDL,
+    ),
+    (
+        <BucketRedeemed as Event>::DISCRIMINATOR,
+        &BucketRedeemed::IDL,
+    ),
+    (
+        <MerkleReclaimed as Event>::DISCRIMINATOR,
+        &MerkleReclaimed::IDL,
+    ),
+    (
+        <MerkleVaultCreated as Event>::DISCRIMINATOR,
+        &MerkleVaultCreated::IDL,
+    ),
+];
+
+pub const ERRORS: &[IdlError] = TimeBaseVaultError::IDL;
+
+/// Renders the IDL as pretty-printed JSON.
+pub fn generate() -> String {
+    let idl = Json::Object(vec![
+        ("version", Json::Str(env!("CARGO_PKG_VERSION"))),
+        ("name", Json::Str("pinocchio_timebase_vault")),
+        (
+            "instructions",
+            Json::Array(INSTRUCTIONS.iter().map(|ix| instruction(ix)).collect()),
+        ),
+        (
+            "accounts",
+            Json::Array(ACCOUNTS.iter().map(|def| struct_def(def, None)).collect()),
+        ),
+        (
+            "types",
+            Json::Array(TYPES.iter().map(|def| struct_def(def, None)).collect()),
+        ),
+        (
+            "events",
+            Json::Array(
+                EVENTS
+                    .iter()
+                    .map(|(discriminator, def)| struct_def(def, Some(*discriminator)))
+                    .collect(),
+            ),
+        ),
+        ("errors", Json::Array(ERRORS.iter().map(error).collect())),
+        (
+            "metadata",
+            Json::Object(vec![
+                ("origin", Json::Str("shank")),
+                (
+                    "address",
+                    Json::String(solana_pubkey::Pubkey::new_from_array(crate::ID).to_string()),
+                ),
+                ("eventVersion", Json::Num(EVENT_VERSION as u64)),
+            ]),
+        ),
+    ]);
+
+    let mut out = String::new();
+    idl.write(&mut out, 0);
+    out.push('\n');
+    out
+}
+
+fn instruction(ix: &IdlInstruction) -> Json {
+    Json::Object(vec![
+        ("name", Json::Str(ix.name)),
+        ("docs", Json::Array(vec![Json::Str(ix.docs)])),
+        (
+            "accounts",
+            Json::Array(
+                ix.accounts
+                    .iter()
+                    .map(|account| {
+                        Json::Object(vec![
+                            ("name", Json::Str(account.name)),
+                            ("isMut", Json::Bool(account.writable)),
+                            ("isSigner", Json::Bool(account.signer)),
+                            ("docs", Json::Array(vec![Json::Str(account.docs)])),
+                        ])
+                    })
+                    .collect(),
+            ),
+        ),
+        ("args", Json::Array(ix.args.iter().map(field).collect())),
+        (
+            "discriminant",
+            Json::Object(vec![
+                ("type", Json::Str("u8")),
+                ("value", Json::Num(ix.discriminator as u64)),
+            ]),
+        ),
+    ])
+}
+
+fn struct_def(def: &IdlStruct, discriminator: Option<u8>) -> Json {
+    let mut entries = vec![
+        ("name", Json::Str(def.name)),
+        ("docs", Json::Array(vec![Json::Str(def.docs)])),
+    ];
+    if let Some(discriminator) = discriminator {
+        entries.push(("discriminator", Json::Num(discriminator as u64)));
+    }
+    entries.push((
+        "type",
+        Json::Object(vec![
+            ("kind", Json::Str("struct")),
+            (
+                "fields",
+                Json::Array(def.fields.iter().map(field).collect()),
+            ),
+        ]),
+    ));
+    entries.push(("size", Json::Num(def.len as u64)));
+
+    Json::Object(entries)
+}
+
+fn field(field: &IdlField) -> Json {
+    Json::Object(vec![
+        ("name", Json::Str(field.name)),
+        ("type", ty(&field.ty)),
+        ("offset", Json::Num(field.offset as u64)),
+    ])
+}
+
+fn ty(ty: &IdlType) -> Json {
+    match ty {
+        IdlType::Bool => Json::Str("bool"),
+        IdlType::U8 => Json::Str("u8"),
+        IdlType::U16 => Json::Str("u16"),
+        IdlType::U64 => Json::Str("u64"),
+        IdlType::I64 => Json::Str("i64"),
+        IdlType::PublicKey => Json::Str("publicKey"),
+        IdlType::Option(inner) => Json::Object(vec![("option", self::ty(inner))]),
+        IdlType::Array(inner, len) => Json::Object(vec![(
+            "array",
+            Json::Array(vec![self::ty(inner), Json::Num(*len as u64)]),
+        )]),
+        IdlType::Defined(def) => Json::Object(vec![("defined", Json::Str(def.name))]),
+    }
+}
+
+fn error(error: &IdlError) -> Json {
+    Json::Object(vec![
+        ("code", Json::Num(error.code as u64)),
+        ("name", Json::Str(error.name)),
+        ("msg", Json::Str(error.msg)),
+    ])
+}
+
+/// The subset of JSON the IDL needs, printed with two space indentation.
+enum Json {
+    Str(&'static str),
+    String(String),
+    Num(u64),
+    Bool(bool),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl Json {
+    fn write(&self, out: &mut String, indent: usize) {
+        match self {
+            Json::Str(value) => write_str(out, value),
+            Json::String(value) => write_str(out, value),
+            Json::Num(value) => out.push_str(&format!("{value}")),
+            Json::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+            Json::Array(items) if items.is_empty() => out.push_str("[]"),
+            Json::Array(items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    out.push_str(if i == 0 { "\n" } else { ",\n" });
+                    push_indent(out, indent + 1);
+                    item.write(out, indent + 1);
+                }
+                out.push('\n');
+                push_indent(out, indent);
+                out.push(']');
+            }
+            Json::Object(entries) if entries.is_empty() => out.push_str("{}"),
+            Json::Object(entries) => {
+                out.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    out.push_str(if i == 0 { "\n" } else { ",\n" });
+                    push_indent(out, indent + 1);
+                    write_str(out, key);
+                    out.push_str(": ");
+                    value.write(out, indent + 1);
+                }
+                out.push('\n');
+                push_indent(out, indent);
+                out.push('}');
+            }
+        }
+    }
+}
+
+fn push_indent(out: &mut String, indent: usize) {
+    for _ in 0..indent {
+        out.push_str("  ");
+    }
+}
+
+fn write_str(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
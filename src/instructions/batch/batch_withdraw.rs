@@ -0,0 +1,182 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    errors::TimeBaseVaultError,
+    events::{check_event_accounts, Event},
+    instructions::{authorize_withdrawal, WithdrawSolVault, WithdrawSplVault},
+    states::Vault,
+    utils::load_acc_unchecked,
+};
+
+pub struct BatchWithdrawAccounts<'info> {
+    pub signer: &'info AccountInfo,
+    pub registry: &'info AccountInfo,
+    pub token_program: &'info AccountInfo,
+    pub event_authority: &'info AccountInfo,
+    /// `[vault]` for each SOL vault and `[vault, mint, user_ata, vault_ata]`
+    /// for each SPL vault
+    pub vaults: &'info [AccountInfo],
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for BatchWithdrawAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [signer, registry, token_program, event_authority, program, vaults @ ..] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !signer.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // verify registry account
+        if !registry.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !registry.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // verify event accounts
+        check_event_accounts(event_authority, program)?;
+
+        if vaults.is_empty() {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        Ok(BatchWithdrawAccounts {
+            signer,
+            registry,
+            token_program,
+            event_authority,
+            vaults,
+        })
+    }
+}
+
+pub struct BatchWithdraw<'info> {
+    pub accounts: BatchWithdrawAccounts<'info>,
+    /// Whether locked vaults are skipped instead of failing the instruction
+    pub skip_locked: bool,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for BatchWithdraw<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'info [u8], &'info [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = BatchWithdrawAccounts::try_from(accounts)?;
+        let skip_locked = match data {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok(BatchWithdraw {
+            accounts,
+            skip_locked,
+        })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl BatchWithdraw<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{
+            IdlAccountItem, IdlField, IdlInstruction, IdlType, EVENT_AUTHORITY_ACCOUNT,
+            PROGRAM_ACCOUNT,
+        };
+
+        IdlInstruction {
+            name: "BatchWithdraw",
+            docs: "Withdraws unlocked vaults of the signer. The accounts are followed by [vault] for each SOL vault and [vault, mint, user_ata, vault_ata] for each SPL vault",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer("signer", "The vault owner"),
+                IdlAccountItem::writable("registry", "The signer's user registry PDA"),
+                IdlAccountItem::readonly("token_program", "The SPL Token Program"),
+                EVENT_AUTHORITY_ACCOUNT,
+                PROGRAM_ACCOUNT,
+            ],
+            args: &[IdlField {
+                name: "skip_locked",
+                ty: IdlType::Bool,
+                offset: 0,
+            }],
+        }
+    };
+}
+
+impl<'info> BatchWithdraw<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &16;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let mut vaults = self.accounts.vaults;
+
+        while let Some((vault, rest)) = vaults.split_first() {
+            if !vault.is_writable() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if !vault.is_owned_by(&crate::ID) {
+                return Err(ProgramError::InvalidAccountOwner);
+            }
+            let (mint, locked) = {
+                let vault = unsafe { load_acc_unchecked::<Vault>(vault.borrow_data_unchecked()) }?;
+                (
+                    vault.mint,
+                    i64::from_le_bytes(vault.unlock_timestamp).gt(&current_timestamp),
+                )
+            };
+            if locked && !self.skip_locked {
+                return Err(TimeBaseVaultError::VaultLocking.into());
+            }
+            // vaults with a receipt token are withdrawn one by one with the
+            // receipt accounts
+            authorize_withdrawal(vault, self.accounts.signer, None)?;
+
+            vaults = match mint {
+                None => {
+                    if !locked {
+                        WithdrawSolVault::withdraw(
+                            self.accounts.signer,
+                            vault,
+                            self.accounts.registry,
+                            current_timestamp,
+                        )?
+                        .emit(self.accounts.event_authority)?;
+                    }
+                    rest
+                }
+                Some(_) => {
+                    let [mint, user_ata, vault_ata, rest @ ..] = rest else {
+                        return Err(ProgramError::NotEnoughAccountKeys);
+                    };
+                    if !locked {
+                        WithdrawSplVault::withdraw(
+                            self.accounts.signer,
+                            vault,
+                            mint,
+                            user_ata,
+                            vault_ata,
+                            self.accounts.registry,
+                            current_timestamp,
+                        )?
+                        .emit(self.accounts.event_authority)?;
+                    }
+                    rest
+                }
+            };
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod batch_withdraw;
+pub use batch_withdraw::*;
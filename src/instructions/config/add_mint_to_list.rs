@@ -0,0 +1,112 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{
+    states::{Config, MintConfig},
+    utils::{load_acc_mut_unchecked, load_acc_unchecked},
+};
+
+pub struct AddMintToListAccounts<'info> {
+    pub admin: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+    pub mint_config: &'info AccountInfo,
+    pub mint: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for AddMintToListAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config, mint_config, mint, _] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // verify config account
+        Config::check(config)?;
+
+        // verify mint config account
+        if !mint_config.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(AddMintToListAccounts {
+            admin,
+            config,
+            mint_config,
+            mint,
+        })
+    }
+}
+
+pub struct AddMintToList<'info> {
+    pub accounts: AddMintToListAccounts<'info>,
+    pub bump: u8,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for AddMintToList<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'info [u8], &'info [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = AddMintToListAccounts::try_from(accounts)?;
+        let bump = match data {
+            [bump] => *bump,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok(AddMintToList { accounts, bump })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl AddMintToList<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{IdlAccountItem, IdlField, IdlInstruction, IdlType};
+
+        IdlInstruction {
+            name: "AddMintToList",
+            docs: "Puts a mint on the mint list",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer("admin", "The config admin"),
+                IdlAccountItem::readonly("config", "The program config PDA"),
+                IdlAccountItem::writable(
+                    "mint_config",
+                    "The mint config PDA (created on first use)",
+                ),
+                IdlAccountItem::readonly("mint", "The SPL token mint account"),
+                IdlAccountItem::readonly("system_program", "The Solana System Program"),
+            ],
+            args: &[IdlField {
+                name: "bump",
+                ty: IdlType::U8,
+                offset: 0,
+            }],
+        }
+    };
+}
+
+impl<'info> AddMintToList<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &12;
+
+    pub fn process(&mut self) -> ProgramResult {
+        {
+            let data = self.accounts.config.try_borrow_data()?;
+            let config = unsafe { load_acc_unchecked::<Config>(&data) }?;
+            config.check_admin(self.accounts.admin)?;
+        }
+
+        MintConfig::initialize_if_needed(
+            self.accounts.admin,
+            self.accounts.mint.key(),
+            self.accounts.mint_config,
+            self.bump,
+        )?;
+
+        let mut data = self.accounts.mint_config.try_borrow_mut_data()?;
+        let mint_config = unsafe { load_acc_mut_unchecked::<MintConfig>(&mut data) }?;
+        mint_config.listed = [1];
+
+        Ok(())
+    }
+}
@@ -0,0 +1,216 @@
+use core::mem::transmute;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    errors::TimeBaseVaultError,
+    states::{Config, MintListMode},
+    utils::{create_pda_account, load_acc_mut_unchecked, DataLen},
+};
+
+/// The BPF upgradeable loader owning the program and its program data account.
+pub const BPF_LOADER_UPGRADEABLE_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+
+pub struct InitializeConfigAccounts<'info> {
+    pub admin: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+    pub program: &'info AccountInfo,
+    pub program_data: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for InitializeConfigAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config, program, program_data, _] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !admin.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // verify config account
+        if !config.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !config.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // verify program accounts
+        if program.key().ne(&crate::ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if !program.is_owned_by(&BPF_LOADER_UPGRADEABLE_ID)
+            || !program_data.is_owned_by(&BPF_LOADER_UPGRADEABLE_ID)
+        {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        Ok(InitializeConfigAccounts {
+            admin,
+            config,
+            program,
+            program_data,
+        })
+    }
+}
+
+#[repr(C, packed)]
+pub struct InitializeConfigInstructionData {
+    pub min_lock_duration: i64,
+    pub max_lock_duration: i64,
+    pub fee_bps: u16,
+    pub flat_fee: u64,
+    pub treasury: Pubkey,
+    pub penalty_recipient: Pubkey,
+    pub bump: u8,
+}
+
+impl DataLen for InitializeConfigInstructionData {
+    const LEN: usize = core::mem::size_of::<InitializeConfigInstructionData>();
+}
+
+impl<'info> TryFrom<&'info [u8]> for InitializeConfigInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(unsafe {
+            transmute::<[u8; Self::LEN], Self>(
+                TryInto::<[u8; Self::LEN]>::try_into(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            )
+        })
+    }
+}
+
+pub struct InitializeConfig<'info> {
+    pub accounts: InitializeConfigAccounts<'info>,
+    pub instruction_data: InitializeConfigInstructionData,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for InitializeConfig<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'info [u8], &'info [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = InitializeConfigAccounts::try_from(accounts)?;
+        let instruction_data = InitializeConfigInstructionData::try_from(data)?;
+
+        Ok(InitializeConfig {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl InitializeConfig<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{idl_fields, IdlAccountItem, IdlInstruction, IdlType};
+
+        IdlInstruction {
+            name: "InitializeConfig",
+            docs: "Creates the program config, signed by the upgrade authority of the program",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer("admin", "The program upgrade authority"),
+                IdlAccountItem::writable("config", "The config PDA account to be created"),
+                IdlAccountItem::readonly("program", "The program account"),
+                IdlAccountItem::readonly("program_data", "The program data account of the program"),
+                IdlAccountItem::readonly("system_program", "The Solana System Program"),
+            ],
+            args: idl_fields!(InitializeConfigInstructionData {
+                min_lock_duration: IdlType::I64,
+                max_lock_duration: IdlType::I64,
+                fee_bps: IdlType::U16,
+                flat_fee: IdlType::U64,
+                treasury: IdlType::PublicKey,
+                penalty_recipient: IdlType::PublicKey,
+                bump: IdlType::U8,
+            }),
+        }
+    };
+}
+
+impl<'info> InitializeConfig<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &4;
+
+    pub fn process(&mut self) -> ProgramResult {
+        self.check_upgrade_authority()?;
+
+        let min_lock_duration = self.instruction_data.min_lock_duration;
+        let max_lock_duration = self.instruction_data.max_lock_duration;
+        let fee_bps = self.instruction_data.fee_bps;
+        Config::validate_settings(min_lock_duration, max_lock_duration, fee_bps)?;
+
+        Config::validate_pda(self.accounts.config.key(), self.instruction_data.bump)?;
+
+        {
+            // create and init config account
+            let bump_binding = [self.instruction_data.bump];
+            let seed = [Seed::from(Config::SEED), Seed::from(&bump_binding)];
+            let signer_seeds = Signer::from(&seed);
+
+            create_pda_account(
+                self.accounts.admin,
+                self.accounts.config,
+                Config::LEN,
+                &crate::ID,
+                &[signer_seeds],
+            )?;
+
+            let mut data = self.accounts.config.try_borrow_mut_data()?;
+            let config = unsafe { load_acc_mut_unchecked::<Config>(&mut data) }?;
+
+            config.admin = *self.accounts.admin.key();
+            config.bump = [self.instruction_data.bump];
+            config.paused = [0];
+            config.mint_list_mode = [MintListMode::Open as u8];
+            config.min_lock_duration = min_lock_duration.to_le_bytes();
+            config.max_lock_duration = max_lock_duration.to_le_bytes();
+            config.fee_bps = fee_bps.to_le_bytes();
+            config.flat_fee = self.instruction_data.flat_fee.to_le_bytes();
+            config.treasury = self.instruction_data.treasury;
+            config.penalty_recipient = self.instruction_data.penalty_recipient;
+        }
+
+        Ok(())
+    }
+
+    /// Verifies that the signer is the upgrade authority of the program.
+    fn check_upgrade_authority(&self) -> ProgramResult {
+        // UpgradeableLoaderState::Program { programdata_address }
+        let program = self.accounts.program.try_borrow_data()?;
+        if program.len() < 36 || program[..4] != 2u32.to_le_bytes() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if program[4..36] != self.accounts.program_data.key()[..] {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // UpgradeableLoaderState::ProgramData { slot, upgrade_authority_address }
+        let program_data = self.accounts.program_data.try_borrow_data()?;
+        if program_data.len() < 45 || program_data[..4] != 3u32.to_le_bytes() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if program_data[12] != 1 || program_data[13..45] != self.accounts.admin.key()[..] {
+            return Err(TimeBaseVaultError::Unauthorized.into());
+        }
+
+        Ok(())
+    }
+}
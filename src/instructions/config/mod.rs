@@ -0,0 +1,23 @@
+pub mod initialize_config;
+pub use initialize_config::*;
+
+pub mod update_config;
+pub use update_config::*;
+
+pub mod transfer_admin;
+pub use transfer_admin::*;
+
+pub mod set_paused;
+pub use set_paused::*;
+
+pub mod set_mint_config;
+pub use set_mint_config::*;
+
+pub mod set_mint_list_mode;
+pub use set_mint_list_mode::*;
+
+pub mod add_mint_to_list;
+pub use add_mint_to_list::*;
+
+pub mod remove_mint_from_list;
+pub use remove_mint_from_list::*;
@@ -0,0 +1,95 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{
+    states::{Config, MintConfig},
+    utils::{load_acc_mut_unchecked, load_acc_unchecked},
+};
+
+pub struct RemoveMintFromListAccounts<'info> {
+    pub admin: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+    pub mint_config: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for RemoveMintFromListAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config, mint_config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // verify config account
+        Config::check(config)?;
+
+        // verify mint config account
+        if !mint_config.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !mint_config.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        Ok(RemoveMintFromListAccounts {
+            admin,
+            config,
+            mint_config,
+        })
+    }
+}
+
+pub struct RemoveMintFromList<'info> {
+    pub accounts: RemoveMintFromListAccounts<'info>,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for RemoveMintFromList<'info> {
+    type Error = ProgramError;
+
+    fn try_from((_, accounts): (&'info [u8], &'info [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = RemoveMintFromListAccounts::try_from(accounts)?;
+
+        Ok(RemoveMintFromList { accounts })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl RemoveMintFromList<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{IdlAccountItem, IdlInstruction};
+
+        IdlInstruction {
+            name: "RemoveMintFromList",
+            docs: "Takes a mint off the mint list",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::readonly_signer("admin", "The config admin"),
+                IdlAccountItem::readonly("config", "The program config PDA"),
+                IdlAccountItem::writable("mint_config", "The mint config PDA"),
+            ],
+            args: &[],
+        }
+    };
+}
+
+impl<'info> RemoveMintFromList<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &13;
+
+    pub fn process(&mut self) -> ProgramResult {
+        {
+            let data = self.accounts.config.try_borrow_data()?;
+            let config = unsafe { load_acc_unchecked::<Config>(&data) }?;
+            config.check_admin(self.accounts.admin)?;
+        }
+
+        let mut data = self.accounts.mint_config.try_borrow_mut_data()?;
+        let mint_config = unsafe { load_acc_mut_unchecked::<MintConfig>(&mut data) }?;
+        MintConfig::validate_pda(
+            self.accounts.mint_config.key(),
+            &mint_config.mint,
+            mint_config.bump[0],
+        )?;
+        mint_config.listed = [0];
+
+        Ok(())
+    }
+}
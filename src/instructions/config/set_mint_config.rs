@@ -0,0 +1,148 @@
+use core::mem::transmute;
+
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{
+    states::{Config, MintConfig},
+    utils::{load_acc_mut_unchecked, load_acc_unchecked, DataLen},
+};
+
+pub struct SetMintConfigAccounts<'info> {
+    pub admin: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+    pub mint_config: &'info AccountInfo,
+    pub mint: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for SetMintConfigAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config, mint_config, mint, _] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // verify config account
+        Config::check(config)?;
+
+        // verify mint config account
+        if !mint_config.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(SetMintConfigAccounts {
+            admin,
+            config,
+            mint_config,
+            mint,
+        })
+    }
+}
+
+#[repr(C, packed)]
+pub struct SetMintConfigInstructionData {
+    pub min_lock_duration: i64,
+    pub max_lock_duration: i64,
+    pub bump: u8,
+}
+
+impl DataLen for SetMintConfigInstructionData {
+    const LEN: usize = core::mem::size_of::<SetMintConfigInstructionData>();
+}
+
+impl<'info> TryFrom<&'info [u8]> for SetMintConfigInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(unsafe {
+            transmute::<[u8; Self::LEN], Self>(
+                TryInto::<[u8; Self::LEN]>::try_into(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            )
+        })
+    }
+}
+
+pub struct SetMintConfig<'info> {
+    pub accounts: SetMintConfigAccounts<'info>,
+    pub instruction_data: SetMintConfigInstructionData,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for SetMintConfig<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'info [u8], &'info [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = SetMintConfigAccounts::try_from(accounts)?;
+        let instruction_data = SetMintConfigInstructionData::try_from(data)?;
+
+        Ok(SetMintConfig {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl SetMintConfig<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{idl_fields, IdlAccountItem, IdlInstruction, IdlType};
+
+        IdlInstruction {
+            name: "SetMintConfig",
+            docs: "Creates or updates the lock duration bounds of a mint",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer("admin", "The config admin"),
+                IdlAccountItem::readonly("config", "The program config PDA"),
+                IdlAccountItem::writable(
+                    "mint_config",
+                    "The mint config PDA (created on first use)",
+                ),
+                IdlAccountItem::readonly("mint", "The SPL token mint account"),
+                IdlAccountItem::readonly("system_program", "The Solana System Program"),
+            ],
+            args: idl_fields!(SetMintConfigInstructionData {
+                min_lock_duration: IdlType::I64,
+                max_lock_duration: IdlType::I64,
+                bump: IdlType::U8,
+            }),
+        }
+    };
+}
+
+impl<'info> SetMintConfig<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &10;
+
+    pub fn process(&mut self) -> ProgramResult {
+        {
+            let data = self.accounts.config.try_borrow_data()?;
+            let config = unsafe { load_acc_unchecked::<Config>(&data) }?;
+            config.check_admin(self.accounts.admin)?;
+        }
+
+        let min_lock_duration = self.instruction_data.min_lock_duration;
+        let max_lock_duration = self.instruction_data.max_lock_duration;
+        Config::validate_settings(min_lock_duration, max_lock_duration, 0)?;
+
+        MintConfig::initialize_if_needed(
+            self.accounts.admin,
+            self.accounts.mint.key(),
+            self.accounts.mint_config,
+            self.instruction_data.bump,
+        )?;
+
+        let mut data = self.accounts.mint_config.try_borrow_mut_data()?;
+        let mint_config = unsafe { load_acc_mut_unchecked::<MintConfig>(&mut data) }?;
+
+        mint_config.min_lock_duration = min_lock_duration.to_le_bytes();
+        mint_config.max_lock_duration = max_lock_duration.to_le_bytes();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{
+    states::{Config, MintListMode},
+    utils::load_acc_mut_unchecked,
+};
+
+pub struct SetMintListModeAccounts<'info> {
+    pub admin: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for SetMintListModeAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // verify config account
+        if !config.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Config::check(config)?;
+
+        Ok(SetMintListModeAccounts { admin, config })
+    }
+}
+
+pub struct SetMintListMode<'info> {
+    pub accounts: SetMintListModeAccounts<'info>,
+    pub mode: MintListMode,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for SetMintListMode<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'info [u8], &'info [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = SetMintListModeAccounts::try_from(accounts)?;
+        let mode = match data {
+            [mode] => MintListMode::try_from(*mode)?,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok(SetMintListMode { accounts, mode })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl SetMintListMode<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{IdlAccountItem, IdlField, IdlInstruction, IdlType};
+
+        IdlInstruction {
+            name: "SetMintListMode",
+            docs: "Sets which mints SPL vaults accept (0 = open, 1 = allow-list, 2 = deny-list)",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::readonly_signer("admin", "The config admin"),
+                IdlAccountItem::writable("config", "The config PDA account"),
+            ],
+            args: &[IdlField {
+                name: "mint_list_mode",
+                ty: IdlType::U8,
+                offset: 0,
+            }],
+        }
+    };
+}
+
+impl<'info> SetMintListMode<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &11;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.config.try_borrow_mut_data()?;
+        let config = unsafe { load_acc_mut_unchecked::<Config>(&mut data) }?;
+        config.check_admin(self.accounts.admin)?;
+
+        config.mint_list_mode = [self.mode as u8];
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{states::Config, utils::load_acc_mut_unchecked};
+
+pub struct SetPausedAccounts<'info> {
+    pub admin: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for SetPausedAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // verify config account
+        if !config.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Config::check(config)?;
+
+        Ok(SetPausedAccounts { admin, config })
+    }
+}
+
+pub struct SetPaused<'info> {
+    pub accounts: SetPausedAccounts<'info>,
+    pub paused: bool,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for SetPaused<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'info [u8], &'info [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = SetPausedAccounts::try_from(accounts)?;
+        let paused = match data {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok(SetPaused { accounts, paused })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl SetPaused<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{IdlAccountItem, IdlField, IdlInstruction, IdlType};
+
+        IdlInstruction {
+            name: "SetPaused",
+            docs: "Pauses or resumes the creation of new vaults, along with splits and merges",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::readonly_signer("admin", "The config admin"),
+                IdlAccountItem::writable("config", "The config PDA account"),
+            ],
+            args: &[IdlField {
+                name: "paused",
+                ty: IdlType::Bool,
+                offset: 0,
+            }],
+        }
+    };
+}
+
+impl<'info> SetPaused<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &7;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.config.try_borrow_mut_data()?;
+        let config = unsafe { load_acc_mut_unchecked::<Config>(&mut data) }?;
+        config.check_admin(self.accounts.admin)?;
+
+        config.paused = [self.paused as u8];
+
+        Ok(())
+    }
+}
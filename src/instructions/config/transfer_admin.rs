@@ -0,0 +1,114 @@
+use core::mem::transmute;
+
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    states::Config,
+    utils::{load_acc_mut_unchecked, DataLen},
+};
+
+pub struct TransferAdminAccounts<'info> {
+    pub admin: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for TransferAdminAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // verify config account
+        if !config.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Config::check(config)?;
+
+        Ok(TransferAdminAccounts { admin, config })
+    }
+}
+
+#[repr(C, packed)]
+pub struct TransferAdminInstructionData {
+    pub new_admin: Pubkey,
+}
+
+impl DataLen for TransferAdminInstructionData {
+    const LEN: usize = core::mem::size_of::<TransferAdminInstructionData>();
+}
+
+impl<'info> TryFrom<&'info [u8]> for TransferAdminInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(unsafe {
+            transmute::<[u8; Self::LEN], Self>(
+                TryInto::<[u8; Self::LEN]>::try_into(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            )
+        })
+    }
+}
+
+pub struct TransferAdmin<'info> {
+    pub accounts: TransferAdminAccounts<'info>,
+    pub instruction_data: TransferAdminInstructionData,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for TransferAdmin<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'info [u8], &'info [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = TransferAdminAccounts::try_from(accounts)?;
+        let instruction_data = TransferAdminInstructionData::try_from(data)?;
+
+        Ok(TransferAdmin {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl TransferAdmin<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{idl_fields, IdlAccountItem, IdlInstruction, IdlType};
+
+        IdlInstruction {
+            name: "TransferAdmin",
+            docs: "Hands the config over to a new admin",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::readonly_signer("admin", "The config admin"),
+                IdlAccountItem::writable("config", "The config PDA account"),
+            ],
+            args: idl_fields!(TransferAdminInstructionData {
+                new_admin: IdlType::PublicKey,
+            }),
+        }
+    };
+}
+
+impl<'info> TransferAdmin<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &6;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut data = self.accounts.config.try_borrow_mut_data()?;
+        let config = unsafe { load_acc_mut_unchecked::<Config>(&mut data) }?;
+        config.check_admin(self.accounts.admin)?;
+
+        config.admin = self.instruction_data.new_admin;
+
+        Ok(())
+    }
+}
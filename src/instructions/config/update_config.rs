@@ -0,0 +1,134 @@
+use core::mem::transmute;
+
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+
+use crate::{
+    states::Config,
+    utils::{load_acc_mut_unchecked, DataLen},
+};
+
+pub struct UpdateConfigAccounts<'info> {
+    pub admin: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for UpdateConfigAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // verify config account
+        if !config.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Config::check(config)?;
+
+        Ok(UpdateConfigAccounts { admin, config })
+    }
+}
+
+#[repr(C, packed)]
+pub struct UpdateConfigInstructionData {
+    pub min_lock_duration: i64,
+    pub max_lock_duration: i64,
+    pub fee_bps: u16,
+    pub flat_fee: u64,
+    pub treasury: Pubkey,
+    pub penalty_recipient: Pubkey,
+}
+
+impl DataLen for UpdateConfigInstructionData {
+    const LEN: usize = core::mem::size_of::<UpdateConfigInstructionData>();
+}
+
+impl<'info> TryFrom<&'info [u8]> for UpdateConfigInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(unsafe {
+            transmute::<[u8; Self::LEN], Self>(
+                TryInto::<[u8; Self::LEN]>::try_into(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            )
+        })
+    }
+}
+
+pub struct UpdateConfig<'info> {
+    pub accounts: UpdateConfigAccounts<'info>,
+    pub instruction_data: UpdateConfigInstructionData,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for UpdateConfig<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'info [u8], &'info [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = UpdateConfigAccounts::try_from(accounts)?;
+        let instruction_data = UpdateConfigInstructionData::try_from(data)?;
+
+        Ok(UpdateConfig {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl UpdateConfig<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{idl_fields, IdlAccountItem, IdlInstruction, IdlType};
+
+        IdlInstruction {
+            name: "UpdateConfig",
+            docs: "Replaces the config settings",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::readonly_signer("admin", "The config admin"),
+                IdlAccountItem::writable("config", "The config PDA account"),
+            ],
+            args: idl_fields!(UpdateConfigInstructionData {
+                min_lock_duration: IdlType::I64,
+                max_lock_duration: IdlType::I64,
+                fee_bps: IdlType::U16,
+                flat_fee: IdlType::U64,
+                treasury: IdlType::PublicKey,
+                penalty_recipient: IdlType::PublicKey,
+            }),
+        }
+    };
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &5;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let min_lock_duration = self.instruction_data.min_lock_duration;
+        let max_lock_duration = self.instruction_data.max_lock_duration;
+        let fee_bps = self.instruction_data.fee_bps;
+        Config::validate_settings(min_lock_duration, max_lock_duration, fee_bps)?;
+
+        let mut data = self.accounts.config.try_borrow_mut_data()?;
+        let config = unsafe { load_acc_mut_unchecked::<Config>(&mut data) }?;
+        config.check_admin(self.accounts.admin)?;
+
+        config.min_lock_duration = min_lock_duration.to_le_bytes();
+        config.max_lock_duration = max_lock_duration.to_le_bytes();
+        config.fee_bps = fee_bps.to_le_bytes();
+        config.flat_fee = self.instruction_data.flat_fee.to_le_bytes();
+        config.treasury = self.instruction_data.treasury;
+        config.penalty_recipient = self.instruction_data.penalty_recipient;
+
+        Ok(())
+    }
+}
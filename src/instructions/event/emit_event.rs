@@ -0,0 +1,69 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::events::EVENT_AUTHORITY;
+
+pub struct EmitEventAccounts<'info> {
+    pub event_authority: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for EmitEventAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [event_authority] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // only the program itself can sign for the event authority
+        if !event_authority.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if event_authority.key().ne(&EVENT_AUTHORITY) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(EmitEventAccounts { event_authority })
+    }
+}
+
+/// Does nothing, the event is carried by the instruction data so indexers
+/// can read it from the inner instructions of the transaction.
+pub struct EmitEvent<'info> {
+    pub accounts: EmitEventAccounts<'info>,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for EmitEvent<'info> {
+    type Error = ProgramError;
+
+    fn try_from((_, accounts): (&'info [u8], &'info [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = EmitEventAccounts::try_from(accounts)?;
+
+        Ok(EmitEvent { accounts })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl EmitEvent<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{IdlAccountItem, IdlInstruction};
+
+        IdlInstruction {
+            name: "EmitEvent",
+            docs: "Carries an event as instruction data, only invoked by the program itself",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[IdlAccountItem::readonly_signer(
+                "event_authority",
+                "The event authority PDA",
+            )],
+            args: &[],
+        }
+    };
+}
+
+impl<'info> EmitEvent<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &14;
+
+    pub fn process(&mut self) -> ProgramResult {
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod emit_event;
+pub use emit_event::*;
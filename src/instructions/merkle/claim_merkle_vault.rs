@@ -0,0 +1,285 @@
+use core::mem::transmute;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::state::Mint;
+
+use crate::{
+    errors::TimeBaseVaultError,
+    events::{check_event_accounts, Event, MerkleClaimed},
+    states::{ClaimReceipt, MerkleVault},
+    utils::{create_pda_account, load_acc_mut, load_acc_mut_unchecked, DataLen, Discriminator},
+};
+
+pub struct ClaimMerkleVaultAccounts<'info> {
+    pub recipient: &'info AccountInfo,
+    pub merkle_vault: &'info AccountInfo,
+    pub mint: &'info AccountInfo,
+    pub recipient_ata: &'info AccountInfo,
+    pub vault_ata: &'info AccountInfo,
+    pub claim_receipt: &'info AccountInfo,
+    pub token_program: &'info AccountInfo,
+    pub system_program: &'info AccountInfo,
+    pub event_authority: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for ClaimMerkleVaultAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [recipient, merkle_vault, mint, recipient_ata, vault_ata, claim_receipt, token_program, system_program, event_authority, program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !recipient.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // verify merkle vault account
+        if !merkle_vault.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !merkle_vault.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // verify claim receipt account
+        if !claim_receipt.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !claim_receipt.data_is_empty() {
+            return Err(TimeBaseVaultError::AlreadyClaimed.into());
+        }
+
+        // verify event accounts
+        check_event_accounts(event_authority, program)?;
+
+        Ok(ClaimMerkleVaultAccounts {
+            recipient,
+            merkle_vault,
+            mint,
+            recipient_ata,
+            vault_ata,
+            claim_receipt,
+            token_program,
+            system_program,
+            event_authority,
+        })
+    }
+}
+
+#[repr(C, packed)]
+pub struct ClaimMerkleVaultInstructionData {
+    pub amount: u64,
+    pub unlock_timestamp: i64,
+    pub receipt_bump: u8,
+}
+
+impl DataLen for ClaimMerkleVaultInstructionData {
+    const LEN: usize = core::mem::size_of::<ClaimMerkleVaultInstructionData>();
+}
+
+impl<'info> TryFrom<&'info [u8]> for ClaimMerkleVaultInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(unsafe {
+            transmute::<[u8; Self::LEN], Self>(
+                TryInto::<[u8; Self::LEN]>::try_into(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            )
+        })
+    }
+}
+
+pub struct ClaimMerkleVault<'info> {
+    pub accounts: ClaimMerkleVaultAccounts<'info>,
+    pub instruction_data: ClaimMerkleVaultInstructionData,
+    pub proof: &'info [[u8; 32]],
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for ClaimMerkleVault<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'info [u8], &'info [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = ClaimMerkleVaultAccounts::try_from(accounts)?;
+
+        if data.len() < ClaimMerkleVaultInstructionData::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (header, proof) = data.split_at(ClaimMerkleVaultInstructionData::LEN);
+        let instruction_data = ClaimMerkleVaultInstructionData::try_from(header)?;
+
+        if proof.len() % 32 != 0 || proof.len() / 32 > MerkleVault::MAX_PROOF_LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        // `[u8; 32]` has an alignment of 1
+        let proof = unsafe {
+            core::slice::from_raw_parts(proof.as_ptr() as *const [u8; 32], proof.len() / 32)
+        };
+
+        Ok(ClaimMerkleVault {
+            accounts,
+            instruction_data,
+            proof,
+        })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl ClaimMerkleVault<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{
+            idl_fields, IdlAccountItem, IdlInstruction, IdlType, EVENT_AUTHORITY_ACCOUNT,
+            PROGRAM_ACCOUNT,
+        };
+
+        IdlInstruction {
+            name: "ClaimMerkleVault",
+            docs: "Claims the leaf of the signer from a merkle vault after its unlock timestamp, if its lock duration is within the bounds recorded on creation. The data is followed by the proof, up to 32 hashes from the leaf up",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer(
+                    "recipient",
+                    "The recipient of the leaf, pays for the claim receipt",
+                ),
+                IdlAccountItem::writable("merkle_vault", "The merkle vault to claim from"),
+                IdlAccountItem::readonly("mint", "The SPL token mint account"),
+                IdlAccountItem::writable("recipient_ata", "Recipient's associated token account"),
+                IdlAccountItem::writable("vault_ata", "Merkle vault's associated token account"),
+                IdlAccountItem::writable(
+                    "claim_receipt",
+                    "The claim receipt PDA of the leaf (created by instruction)",
+                ),
+                IdlAccountItem::readonly("token_program", "The SPL Token Program"),
+                IdlAccountItem::readonly("system_program", "The Solana System Program"),
+                EVENT_AUTHORITY_ACCOUNT,
+                PROGRAM_ACCOUNT,
+            ],
+            args: idl_fields!(ClaimMerkleVaultInstructionData {
+                amount: IdlType::U64,
+                unlock_timestamp: IdlType::I64,
+                receipt_bump: IdlType::U8,
+            }),
+        }
+    };
+}
+
+impl<'info> ClaimMerkleVault<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &18;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let amount = self.instruction_data.amount;
+        let unlock_timestamp = self.instruction_data.unlock_timestamp;
+        if unlock_timestamp.gt(&current_timestamp) {
+            return Err(TimeBaseVaultError::VaultLocking.into());
+        }
+
+        let leaf = MerkleVault::leaf_hash(self.accounts.recipient.key(), amount, unlock_timestamp);
+
+        let (funder, root, bump) = {
+            let mut data = self.accounts.merkle_vault.try_borrow_mut_data()?;
+            let merkle_vault = unsafe { load_acc_mut::<MerkleVault>(&mut data) }?;
+            if merkle_vault.mint.ne(self.accounts.mint.key()) {
+                return Err(TimeBaseVaultError::InvalidVaultMint.into());
+            }
+            if !MerkleVault::verify_proof(&merkle_vault.root, leaf, self.proof) {
+                return Err(TimeBaseVaultError::InvalidMerkleProof.into());
+            }
+            merkle_vault.validate_lock_duration(unlock_timestamp)?;
+
+            // a tree whose leaves add up to more than the deposit can not
+            // drain tokens sent to the vault token account by others
+            let claimed = u64::from_le_bytes(merkle_vault.claimed)
+                .checked_add(amount)
+                .filter(|claimed| *claimed <= u64::from_le_bytes(merkle_vault.total))
+                .ok_or(ProgramError::InsufficientFunds)?;
+            merkle_vault.claimed = claimed.to_le_bytes();
+
+            (merkle_vault.funder, merkle_vault.root, merkle_vault.bump)
+        };
+
+        ClaimReceipt::validate_pda(
+            self.accounts.claim_receipt.key(),
+            self.accounts.merkle_vault.key(),
+            &leaf,
+            self.instruction_data.receipt_bump,
+        )?;
+
+        {
+            // create the claim receipt, which fails a second claim of the leaf
+            let bump_binding = [self.instruction_data.receipt_bump];
+            let seed = [
+                Seed::from(ClaimReceipt::SEED),
+                Seed::from(self.accounts.merkle_vault.key()),
+                Seed::from(&leaf),
+                Seed::from(&bump_binding),
+            ];
+            let signer_seeds = Signer::from(&seed);
+
+            create_pda_account(
+                self.accounts.recipient,
+                self.accounts.claim_receipt,
+                ClaimReceipt::LEN,
+                &crate::ID,
+                &[signer_seeds],
+            )?;
+
+            let mut data = self.accounts.claim_receipt.try_borrow_mut_data()?;
+            let receipt = unsafe { load_acc_mut_unchecked::<ClaimReceipt>(&mut data) }?;
+
+            receipt.discriminator = [ClaimReceipt::DISCRIMINATOR];
+            receipt.merkle_vault = *self.accounts.merkle_vault.key();
+            receipt.recipient = *self.accounts.recipient.key();
+            receipt.amount = amount.to_le_bytes();
+            receipt.claimed_at = current_timestamp.to_le_bytes();
+            receipt.bump = bump_binding;
+        }
+
+        {
+            let seed = [
+                Seed::from(MerkleVault::SEED),
+                Seed::from(&funder),
+                Seed::from(self.accounts.mint.key()),
+                Seed::from(&root),
+                Seed::from(&bump),
+            ];
+            let signer_seeds = Signer::from(&seed);
+
+            // transfer spl token to recipient
+            pinocchio_token::instructions::TransferChecked {
+                mint: self.accounts.mint,
+                from: self.accounts.vault_ata,
+                to: self.accounts.recipient_ata,
+                amount,
+                authority: self.accounts.merkle_vault,
+                decimals: Mint::from_account_info(self.accounts.mint)?.decimals(),
+            }
+            .invoke_signed(&[signer_seeds])?;
+        }
+
+        MerkleClaimed {
+            merkle_vault: *self.accounts.merkle_vault.key(),
+            recipient: *self.accounts.recipient.key(),
+            mint: *self.accounts.mint.key(),
+            amount: amount.to_le_bytes(),
+            unlock_timestamp: unlock_timestamp.to_le_bytes(),
+            timestamp: current_timestamp.to_le_bytes(),
+        }
+        .emit(self.accounts.event_authority)
+    }
+}
@@ -0,0 +1,341 @@
+use core::mem::transmute;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::state::Mint;
+
+use crate::{
+    errors::TimeBaseVaultError,
+    events::{check_event_accounts, Event, MerkleVaultCreated},
+    states::{Config, MerkleVault, MintConfig},
+    utils::{
+        create_pda_account, load_acc_mut_unchecked, load_acc_unchecked, DataLen, Discriminator,
+    },
+};
+
+pub struct InitializeMerkleVaultAccounts<'info> {
+    pub signer: &'info AccountInfo,
+    pub merkle_vault: &'info AccountInfo,
+    pub mint: &'info AccountInfo,
+    pub signer_ata: &'info AccountInfo,
+    pub vault_ata: &'info AccountInfo,
+    pub token_program: &'info AccountInfo,
+    pub associated_token_program: &'info AccountInfo,
+    pub system_program: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+    pub treasury: &'info AccountInfo,
+    pub treasury_ata: &'info AccountInfo,
+    pub mint_config: &'info AccountInfo,
+    pub event_authority: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for InitializeMerkleVaultAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [signer, merkle_vault, mint, signer_ata, vault_ata, token_program, associated_token_program, system_program, config, treasury, treasury_ata, mint_config, event_authority, program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !signer.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // verify merkle vault account
+        if !merkle_vault.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !merkle_vault.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // verify config account
+        Config::check(config)?;
+
+        // verify event accounts
+        check_event_accounts(event_authority, program)?;
+
+        Ok(InitializeMerkleVaultAccounts {
+            signer,
+            merkle_vault,
+            mint,
+            signer_ata,
+            vault_ata,
+            token_program,
+            associated_token_program,
+            system_program,
+            config,
+            treasury,
+            treasury_ata,
+            mint_config,
+            event_authority,
+        })
+    }
+}
+
+#[repr(C, packed)]
+pub struct InitializeMerkleVaultInstructionData {
+    pub root: [u8; 32],
+    pub total: u64,
+    pub reclaim_timestamp: i64,
+    pub bump: u8,
+    pub mint_config_bump: u8,
+}
+
+impl DataLen for InitializeMerkleVaultInstructionData {
+    const LEN: usize = core::mem::size_of::<InitializeMerkleVaultInstructionData>();
+}
+
+impl<'info> TryFrom<&'info [u8]> for InitializeMerkleVaultInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(unsafe {
+            transmute::<[u8; Self::LEN], Self>(
+                TryInto::<[u8; Self::LEN]>::try_into(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            )
+        })
+    }
+}
+
+pub struct InitializeMerkleVault<'info> {
+    pub accounts: InitializeMerkleVaultAccounts<'info>,
+    pub instruction_data: InitializeMerkleVaultInstructionData,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for InitializeMerkleVault<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'info [u8], &'info [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = InitializeMerkleVaultAccounts::try_from(accounts)?;
+        let instruction_data = InitializeMerkleVaultInstructionData::try_from(data)?;
+
+        Ok(InitializeMerkleVault {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl InitializeMerkleVault<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{
+            idl_fields, IdlAccountItem, IdlInstruction, IdlType, EVENT_AUTHORITY_ACCOUNT,
+            PROGRAM_ACCOUNT,
+        };
+
+        IdlInstruction {
+            name: "InitializeMerkleVault",
+            docs: "Locks SPL tokens for the leaves of a merkle root, claimed by each recipient after its unlock timestamp within the lock duration bounds in force on creation",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer("signer", "The funder of the vault"),
+                IdlAccountItem::writable(
+                    "merkle_vault",
+                    "The merkle vault PDA account to be created",
+                ),
+                IdlAccountItem::readonly("mint", "The SPL token mint account"),
+                IdlAccountItem::writable("signer_ata", "Funder's associated token account"),
+                IdlAccountItem::writable(
+                    "vault_ata",
+                    "Merkle vault's associated token account (created by instruction)",
+                ),
+                IdlAccountItem::readonly("token_program", "The SPL Token Program"),
+                IdlAccountItem::readonly(
+                    "associated_token_program",
+                    "The Associated Token Program",
+                ),
+                IdlAccountItem::readonly("system_program", "The Solana System Program"),
+                IdlAccountItem::readonly("config", "The program config PDA"),
+                IdlAccountItem::writable(
+                    "treasury",
+                    "The treasury recorded in the config, receives the flat creation fee",
+                ),
+                IdlAccountItem::writable(
+                    "treasury_ata",
+                    "Treasury's token account for the mint, receives the basis-point creation fee, any account when that fee is zero",
+                ),
+                IdlAccountItem::readonly(
+                    "mint_config",
+                    "The mint config PDA of the mint (may be uninitialized)",
+                ),
+                EVENT_AUTHORITY_ACCOUNT,
+                PROGRAM_ACCOUNT,
+            ],
+            args: idl_fields!(InitializeMerkleVaultInstructionData {
+                root: IdlType::Array(&IdlType::U8, 32),
+                total: IdlType::U64,
+                reclaim_timestamp: IdlType::I64,
+                bump: IdlType::U8,
+                mint_config_bump: IdlType::U8,
+            }),
+        }
+    };
+}
+
+impl<'info> InitializeMerkleVault<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &17;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let root = self.instruction_data.root;
+        let total = self.instruction_data.total;
+        let reclaim_timestamp = self.instruction_data.reclaim_timestamp;
+        if total.eq(&0) {
+            return Err(TimeBaseVaultError::AmountMustBeGreaterThanZero.into());
+        }
+
+        MintConfig::check(
+            self.accounts.mint_config,
+            self.accounts.mint.key(),
+            self.instruction_data.mint_config_bump,
+        )?;
+
+        // the unlock timestamps are in the leaves, so the lock duration bounds
+        // are recorded and enforced on each claim
+        let (fee, flat_fee, (min_lock_duration, max_lock_duration)) = {
+            let data = self.accounts.config.try_borrow_data()?;
+            let config = unsafe { load_acc_unchecked::<Config>(&data) }?;
+            let mint_config_data = self.accounts.mint_config.try_borrow_data()?;
+            let mint_config = if mint_config_data.is_empty() {
+                None
+            } else {
+                Some(unsafe { load_acc_unchecked::<MintConfig>(&mint_config_data) }?)
+            };
+            config.check_not_paused()?;
+            config.check_treasury(self.accounts.treasury)?;
+            config.check_mint_listed(mint_config)?;
+
+            let (fee, flat_fee) = config.creation_fee(total)?;
+            if fee > 0 {
+                config.check_treasury_ata(self.accounts.treasury_ata, self.accounts.mint.key())?;
+            }
+
+            (fee, flat_fee, config.lock_duration_bounds(mint_config))
+        };
+        MerkleVault::check_reclaim_timestamp(
+            current_timestamp,
+            max_lock_duration,
+            reclaim_timestamp,
+        )?;
+
+        MerkleVault::validate_pda(
+            self.accounts.merkle_vault.key(),
+            self.accounts.signer.key(),
+            self.accounts.mint.key(),
+            &root,
+            self.instruction_data.bump,
+        )?;
+
+        {
+            // create and init merkle vault account
+            let bump_binding = [self.instruction_data.bump];
+            let seed = [
+                Seed::from(MerkleVault::SEED),
+                Seed::from(self.accounts.signer.key()),
+                Seed::from(self.accounts.mint.key()),
+                Seed::from(&root),
+                Seed::from(&bump_binding),
+            ];
+            let signer_seeds = Signer::from(&seed);
+
+            create_pda_account(
+                self.accounts.signer,
+                self.accounts.merkle_vault,
+                MerkleVault::LEN,
+                &crate::ID,
+                &[signer_seeds],
+            )?;
+
+            let mut data = self.accounts.merkle_vault.try_borrow_mut_data()?;
+            let merkle_vault = unsafe { load_acc_mut_unchecked::<MerkleVault>(&mut data) }?;
+
+            merkle_vault.discriminator = [MerkleVault::DISCRIMINATOR];
+            merkle_vault.funder = *self.accounts.signer.key();
+            merkle_vault.mint = *self.accounts.mint.key();
+            merkle_vault.root = root;
+            merkle_vault.total = total.to_le_bytes();
+            merkle_vault.claimed = 0u64.to_le_bytes();
+            merkle_vault.created_at = current_timestamp.to_le_bytes();
+            merkle_vault.min_lock_duration = min_lock_duration.to_le_bytes();
+            merkle_vault.max_lock_duration = max_lock_duration.to_le_bytes();
+            merkle_vault.reclaim_timestamp = reclaim_timestamp.to_le_bytes();
+            merkle_vault.bump = [self.instruction_data.bump];
+        }
+
+        {
+            // create associated token account for merkle vault
+            pinocchio_associated_token_account::instructions::Create {
+                account: self.accounts.vault_ata,
+                mint: self.accounts.mint,
+                funding_account: self.accounts.signer,
+                system_program: self.accounts.system_program,
+                token_program: self.accounts.token_program,
+                wallet: self.accounts.merkle_vault,
+            }
+            .invoke()?;
+
+            let decimals = Mint::from_account_info(self.accounts.mint)?.decimals();
+
+            // transfer spl token to merkle vault
+            pinocchio_token::instructions::TransferChecked {
+                mint: self.accounts.mint,
+                from: self.accounts.signer_ata,
+                to: self.accounts.vault_ata,
+                amount: total,
+                authority: self.accounts.signer,
+                decimals,
+            }
+            .invoke()?;
+
+            if fee > 0 {
+                // transfer fee to treasury
+                pinocchio_token::instructions::TransferChecked {
+                    mint: self.accounts.mint,
+                    from: self.accounts.signer_ata,
+                    to: self.accounts.treasury_ata,
+                    amount: fee,
+                    authority: self.accounts.signer,
+                    decimals,
+                }
+                .invoke()?;
+            }
+        }
+
+        if flat_fee > 0 {
+            // transfer flat fee to treasury
+            pinocchio_system::instructions::Transfer {
+                from: self.accounts.signer,
+                to: self.accounts.treasury,
+                lamports: flat_fee,
+            }
+            .invoke()?;
+        }
+
+        MerkleVaultCreated {
+            merkle_vault: *self.accounts.merkle_vault.key(),
+            funder: *self.accounts.signer.key(),
+            mint: *self.accounts.mint.key(),
+            root,
+            total: total.to_le_bytes(),
+            reclaim_timestamp: reclaim_timestamp.to_le_bytes(),
+            timestamp: current_timestamp.to_le_bytes(),
+        }
+        .emit(self.accounts.event_authority)
+    }
+}
@@ -0,0 +1,8 @@
+pub mod init_merkle_vault;
+pub use init_merkle_vault::*;
+
+pub mod claim_merkle_vault;
+pub use claim_merkle_vault::*;
+
+pub mod reclaim_merkle_vault;
+pub use reclaim_merkle_vault::*;
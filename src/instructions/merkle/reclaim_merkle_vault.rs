@@ -0,0 +1,168 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::state::Mint;
+
+use crate::{
+    errors::TimeBaseVaultError,
+    events::{check_event_accounts, Event, MerkleReclaimed},
+    states::MerkleVault,
+    utils::load_acc_mut,
+};
+
+pub struct ReclaimMerkleVaultAccounts<'info> {
+    pub funder: &'info AccountInfo,
+    pub merkle_vault: &'info AccountInfo,
+    pub mint: &'info AccountInfo,
+    pub funder_ata: &'info AccountInfo,
+    pub vault_ata: &'info AccountInfo,
+    pub token_program: &'info AccountInfo,
+    pub event_authority: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for ReclaimMerkleVaultAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [funder, merkle_vault, mint, funder_ata, vault_ata, token_program, event_authority, program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !funder.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // verify merkle vault account
+        if !merkle_vault.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !merkle_vault.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // verify event accounts
+        check_event_accounts(event_authority, program)?;
+
+        Ok(ReclaimMerkleVaultAccounts {
+            funder,
+            merkle_vault,
+            mint,
+            funder_ata,
+            vault_ata,
+            token_program,
+            event_authority,
+        })
+    }
+}
+
+pub struct ReclaimMerkleVault<'info> {
+    pub accounts: ReclaimMerkleVaultAccounts<'info>,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for ReclaimMerkleVault<'info> {
+    type Error = ProgramError;
+
+    fn try_from((_, accounts): (&'info [u8], &'info [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = ReclaimMerkleVaultAccounts::try_from(accounts)?;
+
+        Ok(ReclaimMerkleVault { accounts })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl ReclaimMerkleVault<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{
+            IdlAccountItem, IdlInstruction, EVENT_AUTHORITY_ACCOUNT, PROGRAM_ACCOUNT,
+        };
+
+        IdlInstruction {
+            name: "ReclaimMerkleVault",
+            docs: "Returns the unclaimed tokens of a merkle vault to its funder from the reclaim timestamp, after which the remaining leaves can not be claimed",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::readonly_signer("funder", "The funder of the merkle vault"),
+                IdlAccountItem::writable("merkle_vault", "The merkle vault to reclaim from"),
+                IdlAccountItem::readonly("mint", "The SPL token mint account"),
+                IdlAccountItem::writable("funder_ata", "Funder's associated token account"),
+                IdlAccountItem::writable("vault_ata", "Merkle vault's associated token account"),
+                IdlAccountItem::readonly("token_program", "The SPL Token Program"),
+                EVENT_AUTHORITY_ACCOUNT,
+                PROGRAM_ACCOUNT,
+            ],
+            args: &[],
+        }
+    };
+}
+
+impl<'info> ReclaimMerkleVault<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &25;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        let (amount, root, bump) = {
+            let mut data = self.accounts.merkle_vault.try_borrow_mut_data()?;
+            let merkle_vault = unsafe { load_acc_mut::<MerkleVault>(&mut data) }?;
+            if merkle_vault.funder.ne(self.accounts.funder.key()) {
+                return Err(TimeBaseVaultError::Unauthorized.into());
+            }
+            if merkle_vault.mint.ne(self.accounts.mint.key()) {
+                return Err(TimeBaseVaultError::InvalidVaultMint.into());
+            }
+            let reclaim_timestamp = i64::from_le_bytes(merkle_vault.reclaim_timestamp);
+            if reclaim_timestamp.eq(&0) {
+                return Err(TimeBaseVaultError::InvalidReclaimTimestamp.into());
+            }
+            if reclaim_timestamp.gt(&current_timestamp) {
+                return Err(TimeBaseVaultError::VaultLocking.into());
+            }
+
+            let amount = merkle_vault.unclaimed()?;
+            if amount.eq(&0) {
+                return Err(TimeBaseVaultError::AmountMustBeGreaterThanZero.into());
+            }
+            // the vault reads as fully claimed, so the remaining leaves fail
+            merkle_vault.claimed = merkle_vault.total;
+
+            (amount, merkle_vault.root, merkle_vault.bump)
+        };
+
+        {
+            let seed = [
+                Seed::from(MerkleVault::SEED),
+                Seed::from(self.accounts.funder.key()),
+                Seed::from(self.accounts.mint.key()),
+                Seed::from(&root),
+                Seed::from(&bump),
+            ];
+            let signer_seeds = Signer::from(&seed);
+
+            // transfer the unclaimed spl token back to funder
+            pinocchio_token::instructions::TransferChecked {
+                mint: self.accounts.mint,
+                from: self.accounts.vault_ata,
+                to: self.accounts.funder_ata,
+                amount,
+                authority: self.accounts.merkle_vault,
+                decimals: Mint::from_account_info(self.accounts.mint)?.decimals(),
+            }
+            .invoke_signed(&[signer_seeds])?;
+        }
+
+        MerkleReclaimed {
+            merkle_vault: *self.accounts.merkle_vault.key(),
+            funder: *self.accounts.funder.key(),
+            mint: *self.accounts.mint.key(),
+            amount: amount.to_le_bytes(),
+            timestamp: current_timestamp.to_le_bytes(),
+        }
+        .emit(self.accounts.event_authority)
+    }
+}
@@ -3,3 +3,18 @@ pub use sol::*;
 
 pub mod spl;
 pub use spl::*;
+
+pub mod config;
+pub use config::*;
+
+pub mod event;
+pub use event::*;
+
+pub mod batch;
+pub use batch::*;
+
+pub mod merkle;
+pub use merkle::*;
+
+pub mod vault;
+pub use vault::*;
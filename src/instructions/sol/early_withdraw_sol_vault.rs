@@ -0,0 +1,208 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    errors::TimeBaseVaultError,
+    events::{check_event_accounts, Event, VaultWithdrawn},
+    instructions::{authorize_withdrawal, ReceiptAccounts},
+    states::{Config, UserRegistry, Vault},
+    utils::{bps_of, load_acc_mut_unchecked, load_acc_unchecked},
+};
+
+pub struct EarlyWithdrawSolVaultAccounts<'info> {
+    pub signer: &'info AccountInfo,
+    pub vault: &'info AccountInfo,
+    pub registry: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+    pub penalty_recipient: &'info AccountInfo,
+    pub event_authority: &'info AccountInfo,
+    pub receipt: Option<ReceiptAccounts<'info>>,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for EarlyWithdrawSolVaultAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [signer, vault, registry, config, penalty_recipient, event_authority, program, receipt @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !signer.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // verify vault account
+        if !vault.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if vault.data_is_empty() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !vault.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // verify registry account
+        if !registry.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !registry.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // verify event accounts
+        check_event_accounts(event_authority, program)?;
+
+        let receipt = ReceiptAccounts::from_tail(receipt)?;
+
+        Ok(EarlyWithdrawSolVaultAccounts {
+            signer,
+            vault,
+            registry,
+            config,
+            penalty_recipient,
+            event_authority,
+            receipt,
+        })
+    }
+}
+
+pub struct EarlyWithdrawSolVault<'info> {
+    pub accounts: EarlyWithdrawSolVaultAccounts<'info>,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for EarlyWithdrawSolVault<'info> {
+    type Error = ProgramError;
+
+    fn try_from((_, accounts): (&'info [u8], &'info [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = EarlyWithdrawSolVaultAccounts::try_from(accounts)?;
+
+        Ok(EarlyWithdrawSolVault { accounts })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl EarlyWithdrawSolVault<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{
+            IdlAccountItem, IdlInstruction, EVENT_AUTHORITY_ACCOUNT, PROGRAM_ACCOUNT,
+        };
+
+        IdlInstruction {
+            name: "EarlyWithdrawSolVault",
+            docs:
+                "Withdraws a SOL vault before its unlock timestamp, paying its early exit penalty. Vaults with a receipt token append receipt_mint, holder_ata and token_program to the accounts",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer(
+                    "signer",
+                    "The vault owner, or the holder of its receipt token",
+                ),
+                IdlAccountItem::writable("vault", "The vault account to withdraw from"),
+                IdlAccountItem::writable("registry", "The vault owner's user registry PDA"),
+                IdlAccountItem::readonly(
+                    "config",
+                    "The program config PDA, any account when no penalty is charged",
+                ),
+                IdlAccountItem::writable(
+                    "penalty_recipient",
+                    "The penalty recipient recorded in the config, any account when no penalty is charged",
+                ),
+                EVENT_AUTHORITY_ACCOUNT,
+                PROGRAM_ACCOUNT,
+            ],
+            args: &[],
+        }
+    };
+}
+
+impl<'info> EarlyWithdrawSolVault<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &8;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        let vault =
+            unsafe { load_acc_unchecked::<Vault>(self.accounts.vault.borrow_data_unchecked()) }?;
+
+        let unlock_timestamp = i64::from_le_bytes(vault.unlock_timestamp);
+        let amount = u64::from_le_bytes(vault.amount);
+        authorize_withdrawal(
+            self.accounts.vault,
+            self.accounts.signer,
+            self.accounts.receipt.as_ref(),
+        )?;
+        if vault.mint.is_some() {
+            return Err(TimeBaseVaultError::InvalidVaultMint.into());
+        }
+
+        // the penalty only applies while the vault is still locked
+        let penalty = if unlock_timestamp.gt(&current_timestamp) {
+            let early_exit_penalty_bps = u16::from_le_bytes(vault.early_exit_penalty_bps);
+            if early_exit_penalty_bps == 0 {
+                return Err(TimeBaseVaultError::VaultLocking.into());
+            }
+            bps_of(amount, early_exit_penalty_bps).ok_or(ProgramError::ArithmeticOverflow)?
+        } else {
+            0
+        };
+
+        // the config and penalty recipient can be any accounts when no
+        // penalty is charged, as for unlocked vaults
+        if penalty > 0 {
+            Config::check(self.accounts.config)?;
+            let data = self.accounts.config.try_borrow_data()?;
+            let config = unsafe { load_acc_unchecked::<Config>(&data) }?;
+            config.check_penalty_recipient(self.accounts.penalty_recipient)?;
+        }
+
+        let event = VaultWithdrawn {
+            vault: *self.accounts.vault.key(),
+            owner: vault.owner,
+            mint: vault.mint,
+            id: vault.id,
+            amount: vault.amount,
+            penalty: penalty.to_le_bytes(),
+            unlock_timestamp: vault.unlock_timestamp,
+            timestamp: current_timestamp.to_le_bytes(),
+        };
+
+        {
+            let mut data = self.accounts.registry.try_borrow_mut_data()?;
+            let registry = unsafe { load_acc_mut_unchecked::<UserRegistry>(&mut data) }?;
+            if registry.owner.ne(&vault.owner) {
+                return Err(TimeBaseVaultError::Unauthorized.into());
+            }
+            registry.record_withdrawal(vault)?;
+        }
+
+        // close vault account, send the penalty to the recipient and the rest to signer
+        {
+            let mut data = self.accounts.vault.try_borrow_mut_data()?;
+            data[0] = 0xff;
+        }
+
+        let remaining = self
+            .accounts
+            .vault
+            .lamports()
+            .checked_sub(penalty)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if penalty > 0 {
+            *self.accounts.penalty_recipient.try_borrow_mut_lamports()? += penalty;
+        }
+        *self.accounts.signer.try_borrow_mut_lamports()? += remaining;
+        self.accounts.vault.resize(1)?;
+        self.accounts.vault.close()?;
+
+        event.emit(self.accounts.event_authority)?;
+
+        Ok(())
+    }
+}
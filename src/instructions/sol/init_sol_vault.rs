@@ -10,20 +10,30 @@ use pinocchio::{
 
 use crate::{
     errors::TimeBaseVaultError,
-    states::Vault,
-    utils::{load_acc_mut_unchecked, DataLen},
+    events::{check_event_accounts, Event, VaultCreated},
+    instructions::{split_receipt_bump, CreateReceiptAccounts},
+    states::{Config, UserRegistry, Vault},
+    utils::{load_acc_mut_unchecked, load_acc_unchecked, DataLen, MAX_BPS},
 };
 
 pub struct InitializeSolVaultAccounts<'info> {
     pub signer: &'info AccountInfo,
     pub vault: &'info AccountInfo,
+    pub system_program: &'info AccountInfo,
+    pub registry: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+    pub treasury: &'info AccountInfo,
+    pub event_authority: &'info AccountInfo,
+    pub receipt: Option<CreateReceiptAccounts<'info>>,
 }
 
 impl<'info> TryFrom<&'info [AccountInfo]> for InitializeSolVaultAccounts<'info> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
-        let [signer, vault, _] = accounts else {
+        let [signer, vault, system_program, registry, config, treasury, event_authority, program, receipt @ ..] =
+            accounts
+        else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
@@ -39,9 +49,23 @@ impl<'info> TryFrom<&'info [AccountInfo]> for InitializeSolVaultAccounts<'info>
             return Err(ProgramError::AccountAlreadyInitialized);
         }
 
+        // verify config account
+        Config::check(config)?;
+
+        // verify event accounts
+        check_event_accounts(event_authority, program)?;
+
+        let receipt = CreateReceiptAccounts::from_tail(receipt)?;
+
         Ok(InitializeSolVaultAccounts {
-            vault: vault,
-            signer: signer,
+            vault,
+            signer,
+            system_program,
+            registry,
+            config,
+            treasury,
+            event_authority,
+            receipt,
         })
     }
 }
@@ -51,6 +75,8 @@ pub struct InitializeSolVaultInstructionData {
     pub amount: u64,
     pub unlock_timestamp: i64,
     pub bump: u8,
+    pub registry_bump: u8,
+    pub early_exit_penalty_bps: u16,
 }
 
 impl DataLen for InitializeSolVaultInstructionData {
@@ -66,7 +92,7 @@ impl<'info> TryFrom<&'info [u8]> for InitializeSolVaultInstructionData {
         }
 
         Ok(unsafe {
-            transmute(
+            transmute::<[u8; Self::LEN], Self>(
                 TryInto::<[u8; Self::LEN]>::try_into(data)
                     .map_err(|_| ProgramError::InvalidInstructionData)?,
             )
@@ -77,6 +103,7 @@ impl<'info> TryFrom<&'info [u8]> for InitializeSolVaultInstructionData {
 pub struct InitializeSolVault<'info> {
     pub accounts: InitializeSolVaultAccounts<'info>,
     pub instruction_data: InitializeSolVaultInstructionData,
+    pub receipt_bump: Option<u8>,
 }
 
 impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for InitializeSolVault<'info> {
@@ -86,15 +113,60 @@ impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for InitializeSolVault<
         (data, accounts): (&'info [u8], &'info [AccountInfo]),
     ) -> Result<Self, Self::Error> {
         let accounts = InitializeSolVaultAccounts::try_from(accounts)?;
+        let (data, receipt_bump) = split_receipt_bump(
+            data,
+            InitializeSolVaultInstructionData::LEN,
+            accounts.receipt.is_some(),
+        )?;
         let instruction_data = InitializeSolVaultInstructionData::try_from(data)?;
 
         Ok(InitializeSolVault {
             accounts,
             instruction_data,
+            receipt_bump,
         })
     }
 }
 
+#[cfg(feature = "idl")]
+impl InitializeSolVault<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{
+            idl_fields, IdlAccountItem, IdlInstruction, IdlType, EVENT_AUTHORITY_ACCOUNT,
+            PROGRAM_ACCOUNT,
+        };
+
+        IdlInstruction {
+            name: "InitializeSolVault",
+            docs: "Creates a time-locked vault for native SOL. With a receipt token, whose holder withdraws the vault, receipt_mint, holder_ata, token_program and associated_token_program are appended to the accounts and the receipt mint bump to the data",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer("signer", "The user creating the vault"),
+                IdlAccountItem::writable("vault", "The vault PDA account to be created"),
+                IdlAccountItem::readonly("system_program", "The Solana System Program"),
+                IdlAccountItem::writable(
+                    "registry",
+                    "The signer's user registry PDA (created on first use)",
+                ),
+                IdlAccountItem::readonly("config", "The program config PDA"),
+                IdlAccountItem::writable(
+                    "treasury",
+                    "The treasury recorded in the config, receives the creation fee",
+                ),
+                EVENT_AUTHORITY_ACCOUNT,
+                PROGRAM_ACCOUNT,
+            ],
+            args: idl_fields!(InitializeSolVaultInstructionData {
+                amount: IdlType::U64,
+                unlock_timestamp: IdlType::I64,
+                bump: IdlType::U8,
+                registry_bump: IdlType::U8,
+                early_exit_penalty_bps: IdlType::U16,
+            }),
+        }
+    };
+}
+
 impl<'info> InitializeSolVault<'info> {
     pub const DISCRIMINATOR: &'info u8 = &0;
 
@@ -108,6 +180,22 @@ impl<'info> InitializeSolVault<'info> {
         if amount.eq(&0) {
             return Err(TimeBaseVaultError::AmountMustBeGreaterThanZero.into());
         }
+        let early_exit_penalty_bps = self.instruction_data.early_exit_penalty_bps;
+        if early_exit_penalty_bps > MAX_BPS {
+            return Err(TimeBaseVaultError::InvalidEarlyExitPenalty.into());
+        }
+
+        let fee = {
+            let data = self.accounts.config.try_borrow_data()?;
+            let config = unsafe { load_acc_unchecked::<Config>(&data) }?;
+            config.check_not_paused()?;
+            config.check_treasury(self.accounts.treasury)?;
+            config.validate_lock_duration(None, current_timestamp, unlock_timestamp)?;
+
+            let (fee, flat_fee) = config.creation_fee(amount)?;
+            fee.checked_add(flat_fee)
+                .ok_or(TimeBaseVaultError::FeeOverflow)?
+        };
 
         Vault::validate_pda(
             self.accounts.vault.key(),
@@ -118,6 +206,18 @@ impl<'info> InitializeSolVault<'info> {
             None,
         )?;
 
+        UserRegistry::initialize_if_needed(
+            self.accounts.signer,
+            self.accounts.signer.key(),
+            self.accounts.registry,
+            self.instruction_data.registry_bump,
+        )?;
+        let id = {
+            let mut data = self.accounts.registry.try_borrow_mut_data()?;
+            let registry = unsafe { load_acc_mut_unchecked::<UserRegistry>(&mut data) }?;
+            registry.record_deposit(None, amount)?
+        };
+
         {
             // create and init vault account
             let bump_binding = [self.instruction_data.bump];
@@ -145,10 +245,21 @@ impl<'info> InitializeSolVault<'info> {
                 self.accounts.vault.try_borrow_mut_data()?;
             let vault = unsafe { load_acc_mut_unchecked::<Vault>(&mut data) }?;
 
+            vault.owner = *self.accounts.signer.key();
+            vault.id = id.to_le_bytes();
             vault.mint = None;
             vault.unlock_timestamp = self.instruction_data.unlock_timestamp.to_le_bytes();
             vault.amount = self.instruction_data.amount.to_le_bytes();
+            vault.seed_amount = self.instruction_data.amount.to_le_bytes();
             vault.bump = [self.instruction_data.bump];
+            vault.early_exit_penalty_bps = early_exit_penalty_bps.to_le_bytes();
+            vault.receipt_mint = self
+                .accounts
+                .receipt
+                .as_ref()
+                .map(|receipt| *receipt.receipt_mint.key());
+            vault.tracked = [1];
+            vault.withdrawn = 0u64.to_le_bytes();
         }
 
         {
@@ -160,6 +271,37 @@ impl<'info> InitializeSolVault<'info> {
             }
             .invoke()?;
         }
+
+        if let (Some(receipt), Some(bump)) = (&self.accounts.receipt, self.receipt_bump) {
+            receipt.mint_receipt(
+                self.accounts.signer,
+                self.accounts.vault,
+                self.accounts.system_program,
+                bump,
+            )?;
+        }
+
+        if fee > 0 {
+            // transfer fee to treasury
+            pinocchio_system::instructions::Transfer {
+                from: self.accounts.signer,
+                to: self.accounts.treasury,
+                lamports: fee,
+            }
+            .invoke()?;
+        }
+
+        VaultCreated {
+            vault: *self.accounts.vault.key(),
+            owner: *self.accounts.signer.key(),
+            mint: None,
+            id: id.to_le_bytes(),
+            amount: amount.to_le_bytes(),
+            unlock_timestamp: unlock_timestamp.to_le_bytes(),
+            timestamp: current_timestamp.to_le_bytes(),
+        }
+        .emit(self.accounts.event_authority)?;
+
         Ok(())
     }
 }
@@ -3,3 +3,6 @@ pub use init_sol_vault::*;
 
 pub mod withdraw_sol_vault;
 pub use withdraw_sol_vault::*;
+
+pub mod early_withdraw_sol_vault;
+pub use early_withdraw_sol_vault::*;
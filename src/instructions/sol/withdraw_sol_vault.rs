@@ -5,18 +5,27 @@ use pinocchio::{
     ProgramResult,
 };
 
-use crate::{errors::TimeBaseVaultError, states::Vault, utils::load_acc_unchecked};
+use crate::{
+    errors::TimeBaseVaultError,
+    events::{check_event_accounts, Event, VaultWithdrawn},
+    instructions::{authorize_withdrawal, ReceiptAccounts},
+    states::{UserRegistry, Vault},
+    utils::{load_acc_mut_unchecked, load_acc_unchecked},
+};
 
 pub struct WithdrawSolVaultAccounts<'info> {
     pub signer: &'info AccountInfo,
     pub vault: &'info AccountInfo,
+    pub registry: &'info AccountInfo,
+    pub event_authority: &'info AccountInfo,
+    pub receipt: Option<ReceiptAccounts<'info>>,
 }
 
 impl<'info> TryFrom<&'info [AccountInfo]> for WithdrawSolVaultAccounts<'info> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
-        let [signer, vault] = accounts else {
+        let [signer, vault, registry, event_authority, program, receipt @ ..] = accounts else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
@@ -31,10 +40,29 @@ impl<'info> TryFrom<&'info [AccountInfo]> for WithdrawSolVaultAccounts<'info> {
         if vault.data_is_empty() {
             return Err(ProgramError::InvalidAccountData);
         }
+        if !vault.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // verify registry account
+        if !registry.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !registry.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // verify event accounts
+        check_event_accounts(event_authority, program)?;
+
+        let receipt = ReceiptAccounts::from_tail(receipt)?;
 
         Ok(WithdrawSolVaultAccounts {
-            vault: vault,
-            signer: signer,
+            vault,
+            signer,
+            registry,
+            event_authority,
+            receipt,
         })
     }
 }
@@ -53,34 +81,100 @@ impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for WithdrawSolVault<'i
     }
 }
 
+#[cfg(feature = "idl")]
+impl WithdrawSolVault<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{
+            IdlAccountItem, IdlInstruction, EVENT_AUTHORITY_ACCOUNT, PROGRAM_ACCOUNT,
+        };
+
+        IdlInstruction {
+            name: "WithdrawSolVault",
+            docs: "Withdraws all SOL from a vault after the unlock timestamp. Vaults with a receipt token append receipt_mint, holder_ata and token_program to the accounts",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer(
+                    "signer",
+                    "The vault owner, or the holder of its receipt token",
+                ),
+                IdlAccountItem::writable("vault", "The vault account to withdraw from"),
+                IdlAccountItem::writable("registry", "The vault owner's user registry PDA"),
+                EVENT_AUTHORITY_ACCOUNT,
+                PROGRAM_ACCOUNT,
+            ],
+            args: &[],
+        }
+    };
+}
+
 impl<'info> WithdrawSolVault<'info> {
     pub const DISCRIMINATOR: &'info u8 = &1;
 
     pub fn process(&mut self) -> ProgramResult {
         let current_timestamp = Clock::get()?.unix_timestamp;
 
-        let vault =
-            unsafe { load_acc_unchecked::<Vault>(self.accounts.vault.borrow_data_unchecked()) }?;
+        authorize_withdrawal(
+            self.accounts.vault,
+            self.accounts.signer,
+            self.accounts.receipt.as_ref(),
+        )?;
+        Self::withdraw(
+            self.accounts.signer,
+            self.accounts.vault,
+            self.accounts.registry,
+            current_timestamp,
+        )?
+        .emit(self.accounts.event_authority)
+    }
 
+    /// Closes the unlocked SOL `vault` into `signer` and returns the event to
+    /// emit. `signer` must be authorized with [`authorize_withdrawal`] first.
+    pub fn withdraw(
+        signer: &AccountInfo,
+        vault_account: &AccountInfo,
+        registry: &AccountInfo,
+        current_timestamp: i64,
+    ) -> Result<VaultWithdrawn, ProgramError> {
+        let vault = unsafe { load_acc_unchecked::<Vault>(vault_account.borrow_data_unchecked()) }?;
         let unlock_timestamp = i64::from_le_bytes(vault.unlock_timestamp);
-        if vault.owner.ne(self.accounts.signer.key()) {
-            return Err(TimeBaseVaultError::Unauthorized.into());
-        }
+
         if unlock_timestamp.gt(&current_timestamp) {
             return Err(TimeBaseVaultError::VaultLocking.into());
         }
+        if vault.mint.is_some() {
+            return Err(TimeBaseVaultError::InvalidVaultMint.into());
+        }
+
+        let event = VaultWithdrawn {
+            vault: *vault_account.key(),
+            owner: vault.owner,
+            mint: vault.mint,
+            id: vault.id,
+            amount: vault.amount,
+            penalty: 0u64.to_le_bytes(),
+            unlock_timestamp: vault.unlock_timestamp,
+            timestamp: current_timestamp.to_le_bytes(),
+        };
+
+        {
+            let mut data = registry.try_borrow_mut_data()?;
+            let registry = unsafe { load_acc_mut_unchecked::<UserRegistry>(&mut data) }?;
+            if registry.owner.ne(&vault.owner) {
+                return Err(TimeBaseVaultError::Unauthorized.into());
+            }
+            registry.record_withdrawal(vault)?;
+        }
 
         // close vault account and transfer all lamports to signer
         {
-            let mut data = self.accounts.vault.try_borrow_mut_data()?;
+            let mut data = vault_account.try_borrow_mut_data()?;
             data[0] = 0xff;
         }
 
-        *self.accounts.signer.try_borrow_mut_lamports()? +=
-            *self.accounts.vault.try_borrow_lamports()?;
-        self.accounts.vault.resize(1)?;
-        self.accounts.vault.close()?;
+        *signer.try_borrow_mut_lamports()? += *vault_account.try_borrow_lamports()?;
+        vault_account.resize(1)?;
+        vault_account.close()?;
 
-        Ok(())
+        Ok(event)
     }
 }
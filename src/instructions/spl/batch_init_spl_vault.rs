@@ -0,0 +1,506 @@
+use core::mem::transmute;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::state::{Mint, TokenAccount};
+
+use crate::{
+    errors::TimeBaseVaultError,
+    events::{check_event_accounts, Event, VaultCreated},
+    states::{Config, MintConfig, UserRegistry, Vault},
+    utils::{load_acc_mut_unchecked, load_acc_unchecked, DataLen, MAX_BPS},
+};
+
+/// The maximum number of vaults created by one instruction.
+///
+/// Each vault takes 3 accounts and 50 bytes of data on top of the 12 shared
+/// accounts. The limiting factor is the size of the transaction: sent as a
+/// versioned transaction with its accounts in a lookup table and with compute
+/// unit limit and price instructions, a full batch takes 1,180 of the 1,232
+/// bytes of a packet and 61 of the 64 account locks, and a 17th vault would
+/// take 1,236 bytes. The compute units of a full batch are asserted to stay
+/// within the 1,400,000 a transaction can request by
+/// `batch_init_spl_vault_compute_units`.
+pub const MAX_BATCH_SIZE: usize = 16;
+
+/// The accounts of one vault of the batch.
+pub struct BatchVaultAccounts<'info> {
+    pub vault: &'info AccountInfo,
+    pub vault_ata: &'info AccountInfo,
+    pub registry: &'info AccountInfo,
+}
+
+pub struct BatchInitializeSplVaultAccounts<'info> {
+    pub signer: &'info AccountInfo,
+    pub mint: &'info AccountInfo,
+    pub signer_ata: &'info AccountInfo,
+    pub token_program: &'info AccountInfo,
+    pub associated_token_program: &'info AccountInfo,
+    pub system_program: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+    pub treasury: &'info AccountInfo,
+    pub treasury_ata: &'info AccountInfo,
+    pub mint_config: &'info AccountInfo,
+    pub event_authority: &'info AccountInfo,
+    /// The `[vault, vault_ata, registry]` accounts of each entry
+    pub vaults: &'info [AccountInfo],
+}
+
+impl<'info> BatchInitializeSplVaultAccounts<'info> {
+    /// Returns the accounts of the entry at `index`.
+    pub fn vault(&self, index: usize) -> BatchVaultAccounts<'info> {
+        let [vault, vault_ata, registry] = &self.vaults[index * 3..][..3] else {
+            unreachable!();
+        };
+
+        BatchVaultAccounts {
+            vault,
+            vault_ata,
+            registry,
+        }
+    }
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for BatchInitializeSplVaultAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [signer, mint, signer_ata, token_program, associated_token_program, system_program, config, treasury, treasury_ata, mint_config, event_authority, program, vaults @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !signer.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // verify vault accounts
+        if vaults.len() % 3 != 0 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        for vault in vaults.iter().step_by(3) {
+            if !vault.is_writable() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if !vault.data_is_empty() {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+        }
+
+        // verify config account
+        Config::check(config)?;
+
+        // verify event accounts
+        check_event_accounts(event_authority, program)?;
+
+        Ok(BatchInitializeSplVaultAccounts {
+            signer,
+            mint,
+            signer_ata,
+            token_program,
+            associated_token_program,
+            system_program,
+            config,
+            treasury,
+            treasury_ata,
+            mint_config,
+            event_authority,
+            vaults,
+        })
+    }
+}
+
+#[repr(C, packed)]
+pub struct BatchInitializeSplVaultInstructionData {
+    pub early_exit_penalty_bps: u16,
+    pub mint_config_bump: u8,
+}
+
+impl DataLen for BatchInitializeSplVaultInstructionData {
+    const LEN: usize = core::mem::size_of::<BatchInitializeSplVaultInstructionData>();
+}
+
+impl<'info> TryFrom<&'info [u8]> for BatchInitializeSplVaultInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(unsafe {
+            transmute::<[u8; Self::LEN], Self>(
+                TryInto::<[u8; Self::LEN]>::try_into(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            )
+        })
+    }
+}
+
+/// One vault of the batch, following the instruction data header.
+#[repr(C, packed)]
+pub struct BatchVaultEntry {
+    /// The owner of the vault
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub unlock_timestamp: i64,
+    /// The bump seed for the vault PDA
+    pub bump: u8,
+    /// The bump seed for the beneficiary's user registry PDA
+    pub registry_bump: u8,
+}
+
+impl DataLen for BatchVaultEntry {
+    const LEN: usize = core::mem::size_of::<BatchVaultEntry>();
+}
+
+#[cfg(feature = "idl")]
+impl BatchVaultEntry {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "BatchVaultEntry",
+            docs: "One vault of a BatchInitializeSplVault instruction",
+            len: Self::LEN,
+            fields: idl_fields!(BatchVaultEntry {
+                beneficiary: IdlType::PublicKey,
+                amount: IdlType::U64,
+                unlock_timestamp: IdlType::I64,
+                bump: IdlType::U8,
+                registry_bump: IdlType::U8,
+            }),
+        }
+    };
+}
+
+pub struct BatchInitializeSplVault<'info> {
+    pub accounts: BatchInitializeSplVaultAccounts<'info>,
+    pub instruction_data: BatchInitializeSplVaultInstructionData,
+    pub entries: &'info [BatchVaultEntry],
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for BatchInitializeSplVault<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'info [u8], &'info [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = BatchInitializeSplVaultAccounts::try_from(accounts)?;
+
+        if data.len() < BatchInitializeSplVaultInstructionData::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (header, entries) = data.split_at(BatchInitializeSplVaultInstructionData::LEN);
+        let instruction_data = BatchInitializeSplVaultInstructionData::try_from(header)?;
+
+        if entries.len() % BatchVaultEntry::LEN != 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let count = entries.len() / BatchVaultEntry::LEN;
+        if count == 0 || count > MAX_BATCH_SIZE {
+            return Err(TimeBaseVaultError::InvalidBatchSize.into());
+        }
+        if accounts.vaults.len() != count * 3 {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        // `BatchVaultEntry` has an alignment of 1
+        let entries = unsafe {
+            core::slice::from_raw_parts(entries.as_ptr() as *const BatchVaultEntry, count)
+        };
+
+        Ok(BatchInitializeSplVault {
+            accounts,
+            instruction_data,
+            entries,
+        })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl BatchInitializeSplVault<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{
+            idl_fields, IdlAccountItem, IdlInstruction, IdlType, EVENT_AUTHORITY_ACCOUNT,
+            PROGRAM_ACCOUNT,
+        };
+
+        IdlInstruction {
+            name: "BatchInitializeSplVault",
+            docs: "Creates up to 16 SPL vaults for beneficiaries, funded by the signer with a single debit. The data is followed by one BatchVaultEntry per vault and the accounts by its vault, vault_ata and registry",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer("signer", "The funder of the vaults"),
+                IdlAccountItem::readonly("mint", "The SPL token mint account"),
+                IdlAccountItem::writable("signer_ata", "Funder's associated token account"),
+                IdlAccountItem::readonly("token_program", "The SPL Token Program"),
+                IdlAccountItem::readonly(
+                    "associated_token_program",
+                    "The Associated Token Program",
+                ),
+                IdlAccountItem::readonly("system_program", "The Solana System Program"),
+                IdlAccountItem::readonly("config", "The program config PDA"),
+                IdlAccountItem::writable(
+                    "treasury",
+                    "The treasury recorded in the config, receives the flat creation fees",
+                ),
+                IdlAccountItem::writable(
+                    "treasury_ata",
+                    "Treasury's token account for the mint, receives the basis-point creation fees, any account when they are zero",
+                ),
+                IdlAccountItem::readonly(
+                    "mint_config",
+                    "The mint config PDA of the mint (may be uninitialized)",
+                ),
+                EVENT_AUTHORITY_ACCOUNT,
+                PROGRAM_ACCOUNT,
+            ],
+            args: idl_fields!(BatchInitializeSplVaultInstructionData {
+                early_exit_penalty_bps: IdlType::U16,
+                mint_config_bump: IdlType::U8,
+            }),
+        }
+    };
+}
+
+impl<'info> BatchInitializeSplVault<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &15;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let early_exit_penalty_bps = self.instruction_data.early_exit_penalty_bps;
+        if early_exit_penalty_bps > MAX_BPS {
+            return Err(TimeBaseVaultError::InvalidEarlyExitPenalty.into());
+        }
+
+        MintConfig::check(
+            self.accounts.mint_config,
+            self.accounts.mint.key(),
+            self.instruction_data.mint_config_bump,
+        )?;
+
+        // validate all entries and sum the transfers before creating anything
+        let (total_amount, total_fee, total_flat_fee) = {
+            let data = self.accounts.config.try_borrow_data()?;
+            let config = unsafe { load_acc_unchecked::<Config>(&data) }?;
+            let mint_config_data = self.accounts.mint_config.try_borrow_data()?;
+            let mint_config = if mint_config_data.is_empty() {
+                None
+            } else {
+                Some(unsafe { load_acc_unchecked::<MintConfig>(&mint_config_data) }?)
+            };
+            config.check_not_paused()?;
+            config.check_treasury(self.accounts.treasury)?;
+            config.check_mint_listed(mint_config)?;
+
+            let (mut total_amount, mut total_fee, mut total_flat_fee) = (0u64, 0u64, 0u64);
+            for (index, entry) in self.entries.iter().enumerate() {
+                let (amount, unlock_timestamp) = (entry.amount, entry.unlock_timestamp);
+                if unlock_timestamp.lt(&current_timestamp) {
+                    return Err(TimeBaseVaultError::UnlockTimestampMustBeInFuture.into());
+                }
+                if amount.eq(&0) {
+                    return Err(TimeBaseVaultError::AmountMustBeGreaterThanZero.into());
+                }
+                config.validate_lock_duration(mint_config, current_timestamp, unlock_timestamp)?;
+
+                Vault::validate_pda(
+                    self.accounts.vault(index).vault.key(),
+                    &entry.beneficiary,
+                    amount,
+                    unlock_timestamp,
+                    entry.bump,
+                    Some(*self.accounts.mint.key()),
+                )?;
+
+                let (fee, flat_fee) = config.creation_fee(amount)?;
+                total_amount = total_amount
+                    .checked_add(amount)
+                    .ok_or(TimeBaseVaultError::FeeOverflow)?;
+                total_fee = total_fee
+                    .checked_add(fee)
+                    .ok_or(TimeBaseVaultError::FeeOverflow)?;
+                total_flat_fee = total_flat_fee
+                    .checked_add(flat_fee)
+                    .ok_or(TimeBaseVaultError::FeeOverflow)?;
+            }
+            if total_fee > 0 {
+                config.check_treasury_ata(self.accounts.treasury_ata, self.accounts.mint.key())?;
+            }
+
+            (total_amount, total_fee, total_flat_fee)
+        };
+
+        // fail before any account is created when the funder is short
+        let required = total_amount
+            .checked_add(total_fee)
+            .ok_or(TimeBaseVaultError::FeeOverflow)?;
+        if TokenAccount::from_account_info(self.accounts.signer_ata)?.amount() < required {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        let decimals = Mint::from_account_info(self.accounts.mint)?.decimals();
+        let vault_rent = Rent::get()?.minimum_balance(Vault::LEN);
+
+        let mut ids = [0u64; MAX_BATCH_SIZE];
+        for (index, entry) in self.entries.iter().enumerate() {
+            let accounts = self.accounts.vault(index);
+            let (amount, unlock_timestamp) = (entry.amount, entry.unlock_timestamp);
+
+            // the beneficiary does not sign, so their vault is left out of
+            // the mint totals of their registry unless they fund it themselves
+            let tracked = entry.beneficiary.eq(self.accounts.signer.key());
+            UserRegistry::initialize_if_needed(
+                self.accounts.signer,
+                &entry.beneficiary,
+                accounts.registry,
+                entry.registry_bump,
+            )?;
+            ids[index] = {
+                let mut data = accounts.registry.try_borrow_mut_data()?;
+                let registry = unsafe { load_acc_mut_unchecked::<UserRegistry>(&mut data) }?;
+                if tracked {
+                    registry.record_deposit(Some(*self.accounts.mint.key()), amount)?
+                } else {
+                    registry.record_untracked_deposit()?
+                }
+            };
+
+            {
+                // create and init vault account
+                let bump_binding = [entry.bump];
+                let amount_bytes = amount.to_le_bytes();
+                let unlock_timestamp_bytes = unlock_timestamp.to_le_bytes();
+                let seed = [
+                    Seed::from(Vault::SEED),
+                    Seed::from(&entry.beneficiary),
+                    Seed::from(self.accounts.mint.key()),
+                    Seed::from(&amount_bytes),
+                    Seed::from(&unlock_timestamp_bytes),
+                    Seed::from(&bump_binding),
+                ];
+                let signer_seeds = Signer::from(&seed);
+
+                pinocchio_system::instructions::CreateAccount {
+                    from: self.accounts.signer,
+                    to: accounts.vault,
+                    space: Vault::LEN as u64,
+                    lamports: vault_rent,
+                    owner: &crate::ID,
+                }
+                .invoke_signed(&[signer_seeds])?;
+
+                let mut data = accounts.vault.try_borrow_mut_data()?;
+                let vault = unsafe { load_acc_mut_unchecked::<Vault>(&mut data) }?;
+
+                vault.owner = entry.beneficiary;
+                vault.id = ids[index].to_le_bytes();
+                vault.mint = Some(*self.accounts.mint.key());
+                vault.unlock_timestamp = unlock_timestamp.to_le_bytes();
+                vault.amount = amount.to_le_bytes();
+                vault.seed_amount = amount.to_le_bytes();
+                vault.receipt_mint = None;
+                vault.bump = [entry.bump];
+                vault.early_exit_penalty_bps = early_exit_penalty_bps.to_le_bytes();
+                vault.tracked = [tracked as u8];
+                vault.withdrawn = 0u64.to_le_bytes();
+            }
+
+            // create associated token account for vault
+            pinocchio_associated_token_account::instructions::Create {
+                account: accounts.vault_ata,
+                mint: self.accounts.mint,
+                funding_account: self.accounts.signer,
+                system_program: self.accounts.system_program,
+                token_program: self.accounts.token_program,
+                wallet: accounts.vault,
+            }
+            .invoke()?;
+        }
+
+        // debit the funder once, into the token account of the first vault
+        let first = self.accounts.vault(0);
+        pinocchio_token::instructions::TransferChecked {
+            mint: self.accounts.mint,
+            from: self.accounts.signer_ata,
+            to: first.vault_ata,
+            amount: required,
+            authority: self.accounts.signer,
+            decimals,
+        }
+        .invoke()?;
+
+        {
+            // the first vault hands the other vaults their tokens and the
+            // treasury the fees of all vaults
+            let entry = &self.entries[0];
+            let bump_binding = [entry.bump];
+            let amount_bytes = entry.amount.to_le_bytes();
+            let unlock_timestamp_bytes = entry.unlock_timestamp.to_le_bytes();
+            let seed = [
+                Seed::from(Vault::SEED),
+                Seed::from(&entry.beneficiary),
+                Seed::from(self.accounts.mint.key()),
+                Seed::from(&amount_bytes),
+                Seed::from(&unlock_timestamp_bytes),
+                Seed::from(&bump_binding),
+            ];
+
+            for (index, entry) in self.entries.iter().enumerate().skip(1) {
+                pinocchio_token::instructions::TransferChecked {
+                    mint: self.accounts.mint,
+                    from: first.vault_ata,
+                    to: self.accounts.vault(index).vault_ata,
+                    amount: entry.amount,
+                    authority: first.vault,
+                    decimals,
+                }
+                .invoke_signed(&[Signer::from(&seed)])?;
+            }
+
+            if total_fee > 0 {
+                pinocchio_token::instructions::TransferChecked {
+                    mint: self.accounts.mint,
+                    from: first.vault_ata,
+                    to: self.accounts.treasury_ata,
+                    amount: total_fee,
+                    authority: first.vault,
+                    decimals,
+                }
+                .invoke_signed(&[Signer::from(&seed)])?;
+            }
+        }
+
+        if total_flat_fee > 0 {
+            // transfer flat fees to treasury
+            pinocchio_system::instructions::Transfer {
+                from: self.accounts.signer,
+                to: self.accounts.treasury,
+                lamports: total_flat_fee,
+            }
+            .invoke()?;
+        }
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            VaultCreated {
+                vault: *self.accounts.vault(index).vault.key(),
+                owner: entry.beneficiary,
+                mint: Some(*self.accounts.mint.key()),
+                id: ids[index].to_le_bytes(),
+                amount: entry.amount.to_le_bytes(),
+                unlock_timestamp: entry.unlock_timestamp.to_le_bytes(),
+                timestamp: current_timestamp.to_le_bytes(),
+            }
+            .emit(self.accounts.event_authority)?;
+        }
+
+        Ok(())
+    }
+}
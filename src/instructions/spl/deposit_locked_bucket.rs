@@ -0,0 +1,420 @@
+use core::mem::transmute;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::state::{Mint, TokenAccount};
+
+use crate::{
+    errors::TimeBaseVaultError,
+    events::{check_event_accounts, BucketDeposited, Event},
+    states::{Config, LockedBucket, MintConfig},
+    utils::{
+        create_pda_account, load_acc_mut, load_acc_mut_unchecked, load_acc_unchecked, DataLen,
+        Discriminator,
+    },
+};
+
+pub struct DepositLockedBucketAccounts<'info> {
+    pub signer: &'info AccountInfo,
+    pub bucket: &'info AccountInfo,
+    pub mint: &'info AccountInfo,
+    pub receipt_mint: &'info AccountInfo,
+    pub user_ata: &'info AccountInfo,
+    pub bucket_ata: &'info AccountInfo,
+    pub user_receipt_ata: &'info AccountInfo,
+    pub token_program: &'info AccountInfo,
+    pub associated_token_program: &'info AccountInfo,
+    pub system_program: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+    pub treasury: &'info AccountInfo,
+    pub treasury_ata: &'info AccountInfo,
+    pub mint_config: &'info AccountInfo,
+    pub event_authority: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for DepositLockedBucketAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [signer, bucket, mint, receipt_mint, user_ata, bucket_ata, user_receipt_ata, token_program, associated_token_program, system_program, config, treasury, treasury_ata, mint_config, event_authority, program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !signer.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // verify bucket account, which is created by the first deposit
+        if !bucket.is_writable() || !receipt_mint.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !bucket.data_is_empty() && !bucket.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // verify config account
+        Config::check(config)?;
+
+        // verify event accounts
+        check_event_accounts(event_authority, program)?;
+
+        Ok(DepositLockedBucketAccounts {
+            signer,
+            bucket,
+            mint,
+            receipt_mint,
+            user_ata,
+            bucket_ata,
+            user_receipt_ata,
+            token_program,
+            associated_token_program,
+            system_program,
+            config,
+            treasury,
+            treasury_ata,
+            mint_config,
+            event_authority,
+        })
+    }
+}
+
+#[repr(C, packed)]
+pub struct DepositLockedBucketInstructionData {
+    pub amount: u64,
+    pub unlock_timestamp: i64,
+    pub bump: u8,
+    pub receipt_mint_bump: u8,
+    pub mint_config_bump: u8,
+}
+
+impl DataLen for DepositLockedBucketInstructionData {
+    const LEN: usize = core::mem::size_of::<DepositLockedBucketInstructionData>();
+}
+
+impl<'info> TryFrom<&'info [u8]> for DepositLockedBucketInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(unsafe {
+            transmute::<[u8; Self::LEN], Self>(
+                TryInto::<[u8; Self::LEN]>::try_into(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            )
+        })
+    }
+}
+
+pub struct DepositLockedBucket<'info> {
+    pub accounts: DepositLockedBucketAccounts<'info>,
+    pub instruction_data: DepositLockedBucketInstructionData,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for DepositLockedBucket<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'info [u8], &'info [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = DepositLockedBucketAccounts::try_from(accounts)?;
+        let instruction_data = DepositLockedBucketInstructionData::try_from(data)?;
+
+        Ok(DepositLockedBucket {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl DepositLockedBucket<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{
+            idl_fields, IdlAccountItem, IdlInstruction, IdlType, EVENT_AUTHORITY_ACCOUNT,
+            PROGRAM_ACCOUNT,
+        };
+
+        IdlInstruction {
+            name: "DepositLockedBucket",
+            docs: "Deposits SPL tokens into the shared bucket of their mint and unlock timestamp and mints as many receipts to the signer",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer("signer", "The depositor"),
+                IdlAccountItem::writable(
+                    "bucket",
+                    "The locked bucket PDA (created by the first deposit)",
+                ),
+                IdlAccountItem::readonly("mint", "The SPL token mint account"),
+                IdlAccountItem::writable(
+                    "receipt_mint",
+                    "The receipt mint PDA of the bucket (created by the first deposit)",
+                ),
+                IdlAccountItem::writable("user_ata", "User's associated token account"),
+                IdlAccountItem::writable(
+                    "bucket_ata",
+                    "Bucket's associated token account (created by the first deposit)",
+                ),
+                IdlAccountItem::writable(
+                    "user_receipt_ata",
+                    "User's associated token account for the receipts (created if needed)",
+                ),
+                IdlAccountItem::readonly("token_program", "The SPL Token Program"),
+                IdlAccountItem::readonly(
+                    "associated_token_program",
+                    "The Associated Token Program",
+                ),
+                IdlAccountItem::readonly("system_program", "The Solana System Program"),
+                IdlAccountItem::readonly("config", "The program config PDA"),
+                IdlAccountItem::writable(
+                    "treasury",
+                    "The treasury recorded in the config, receives the flat creation fee",
+                ),
+                IdlAccountItem::writable(
+                    "treasury_ata",
+                    "Treasury's token account for the mint, receives the basis-point creation fee, any account when that fee is zero",
+                ),
+                IdlAccountItem::readonly(
+                    "mint_config",
+                    "The mint config PDA of the mint (may be uninitialized)",
+                ),
+                EVENT_AUTHORITY_ACCOUNT,
+                PROGRAM_ACCOUNT,
+            ],
+            args: idl_fields!(DepositLockedBucketInstructionData {
+                amount: IdlType::U64,
+                unlock_timestamp: IdlType::I64,
+                bump: IdlType::U8,
+                receipt_mint_bump: IdlType::U8,
+                mint_config_bump: IdlType::U8,
+            }),
+        }
+    };
+}
+
+impl<'info> DepositLockedBucket<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &21;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let unlock_timestamp = self.instruction_data.unlock_timestamp;
+        let amount = self.instruction_data.amount;
+        if unlock_timestamp.lt(&current_timestamp) {
+            return Err(TimeBaseVaultError::UnlockTimestampMustBeInFuture.into());
+        }
+        if amount.eq(&0) {
+            return Err(TimeBaseVaultError::AmountMustBeGreaterThanZero.into());
+        }
+
+        MintConfig::check(
+            self.accounts.mint_config,
+            self.accounts.mint.key(),
+            self.instruction_data.mint_config_bump,
+        )?;
+
+        let (fee, flat_fee) = {
+            let data = self.accounts.config.try_borrow_data()?;
+            let config = unsafe { load_acc_unchecked::<Config>(&data) }?;
+            let mint_config_data = self.accounts.mint_config.try_borrow_data()?;
+            let mint_config = if mint_config_data.is_empty() {
+                None
+            } else {
+                Some(unsafe { load_acc_unchecked::<MintConfig>(&mint_config_data) }?)
+            };
+            config.check_not_paused()?;
+            config.check_treasury(self.accounts.treasury)?;
+            config.check_mint_listed(mint_config)?;
+            config.validate_lock_duration(mint_config, current_timestamp, unlock_timestamp)?;
+
+            let (fee, flat_fee) = config.creation_fee(amount)?;
+            if fee > 0 {
+                config.check_treasury_ata(self.accounts.treasury_ata, self.accounts.mint.key())?;
+            }
+
+            (fee, flat_fee)
+        };
+
+        let decimals = Mint::from_account_info(self.accounts.mint)?.decimals();
+        let bucket_bump = [self.instruction_data.bump];
+        let unlock_timestamp_bytes = unlock_timestamp.to_le_bytes();
+        let seed = [
+            Seed::from(LockedBucket::SEED),
+            Seed::from(self.accounts.mint.key()),
+            Seed::from(&unlock_timestamp_bytes),
+            Seed::from(&bucket_bump),
+        ];
+
+        // the bucket signs the receipts minted, so its address is derived on
+        // every deposit, not only when it is created
+        LockedBucket::validate_pda(
+            self.accounts.bucket.key(),
+            self.accounts.mint.key(),
+            unlock_timestamp,
+            self.instruction_data.bump,
+        )?;
+        if self.accounts.bucket.data_is_empty() {
+            self.create_bucket(&seed, decimals)?;
+        }
+
+        {
+            let mut data = self.accounts.bucket.try_borrow_mut_data()?;
+            let bucket = unsafe { load_acc_mut::<LockedBucket>(&mut data) }?;
+            if bucket.mint.ne(self.accounts.mint.key())
+                || i64::from_le_bytes(bucket.unlock_timestamp).ne(&unlock_timestamp)
+            {
+                return Err(TimeBaseVaultError::InvalidVaultMint.into());
+            }
+            if bucket.receipt_mint.ne(self.accounts.receipt_mint.key()) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            // the redemptions are paid out of the bucket's token account, so
+            // every deposit must land there
+            let bucket_ata = TokenAccount::from_account_info(self.accounts.bucket_ata)?;
+            if bucket_ata.owner().ne(self.accounts.bucket.key())
+                || bucket_ata.mint().ne(&bucket.mint)
+            {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            bucket.total = u64::from_le_bytes(bucket.total)
+                .checked_add(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?
+                .to_le_bytes();
+        }
+
+        // transfer spl token to bucket
+        pinocchio_token::instructions::TransferChecked {
+            mint: self.accounts.mint,
+            from: self.accounts.user_ata,
+            to: self.accounts.bucket_ata,
+            amount,
+            authority: self.accounts.signer,
+            decimals,
+        }
+        .invoke()?;
+
+        if fee > 0 {
+            // transfer fee to treasury
+            pinocchio_token::instructions::TransferChecked {
+                mint: self.accounts.mint,
+                from: self.accounts.user_ata,
+                to: self.accounts.treasury_ata,
+                amount: fee,
+                authority: self.accounts.signer,
+                decimals,
+            }
+            .invoke()?;
+        }
+
+        if flat_fee > 0 {
+            // transfer flat fee to treasury
+            pinocchio_system::instructions::Transfer {
+                from: self.accounts.signer,
+                to: self.accounts.treasury,
+                lamports: flat_fee,
+            }
+            .invoke()?;
+        }
+
+        // mint the receipts 1:1
+        pinocchio_associated_token_account::instructions::CreateIdempotent {
+            account: self.accounts.user_receipt_ata,
+            mint: self.accounts.receipt_mint,
+            funding_account: self.accounts.signer,
+            system_program: self.accounts.system_program,
+            token_program: self.accounts.token_program,
+            wallet: self.accounts.signer,
+        }
+        .invoke()?;
+
+        pinocchio_token::instructions::MintTo {
+            mint: self.accounts.receipt_mint,
+            account: self.accounts.user_receipt_ata,
+            mint_authority: self.accounts.bucket,
+            amount,
+        }
+        .invoke_signed(&[Signer::from(&seed)])?;
+
+        BucketDeposited {
+            bucket: *self.accounts.bucket.key(),
+            depositor: *self.accounts.signer.key(),
+            mint: *self.accounts.mint.key(),
+            amount: amount.to_le_bytes(),
+            unlock_timestamp: unlock_timestamp_bytes,
+            timestamp: current_timestamp.to_le_bytes(),
+        }
+        .emit(self.accounts.event_authority)
+    }
+
+    /// Creates the bucket signed with `seed`, its receipt mint with the
+    /// `decimals` of the pooled mint and its token account.
+    fn create_bucket(&self, seed: &[Seed], decimals: u8) -> ProgramResult {
+        LockedBucket::validate_receipt_pda(
+            self.accounts.receipt_mint.key(),
+            self.accounts.bucket.key(),
+            self.instruction_data.receipt_mint_bump,
+        )?;
+
+        create_pda_account(
+            self.accounts.signer,
+            self.accounts.bucket,
+            LockedBucket::LEN,
+            &crate::ID,
+            &[Signer::from(seed)],
+        )?;
+
+        {
+            let mut data = self.accounts.bucket.try_borrow_mut_data()?;
+            let bucket = unsafe { load_acc_mut_unchecked::<LockedBucket>(&mut data) }?;
+
+            bucket.discriminator = [LockedBucket::DISCRIMINATOR];
+            bucket.mint = *self.accounts.mint.key();
+            bucket.unlock_timestamp = self.instruction_data.unlock_timestamp.to_le_bytes();
+            bucket.receipt_mint = *self.accounts.receipt_mint.key();
+            bucket.total = 0u64.to_le_bytes();
+            bucket.bump = [self.instruction_data.bump];
+        }
+
+        // create the receipt mint, whose authority the bucket keeps
+        let receipt_mint_bump = [self.instruction_data.receipt_mint_bump];
+        let receipt_seed = [
+            Seed::from(LockedBucket::RECEIPT_SEED),
+            Seed::from(self.accounts.bucket.key()),
+            Seed::from(&receipt_mint_bump),
+        ];
+        create_pda_account(
+            self.accounts.signer,
+            self.accounts.receipt_mint,
+            Mint::LEN,
+            &pinocchio_token::ID,
+            &[Signer::from(&receipt_seed)],
+        )?;
+
+        pinocchio_token::instructions::InitializeMint2 {
+            mint: self.accounts.receipt_mint,
+            decimals,
+            mint_authority: self.accounts.bucket.key(),
+            freeze_authority: None,
+        }
+        .invoke()?;
+
+        // create associated token account for bucket
+        pinocchio_associated_token_account::instructions::Create {
+            account: self.accounts.bucket_ata,
+            mint: self.accounts.mint,
+            funding_account: self.accounts.signer,
+            system_program: self.accounts.system_program,
+            token_program: self.accounts.token_program,
+            wallet: self.accounts.bucket,
+        }
+        .invoke()
+    }
+}
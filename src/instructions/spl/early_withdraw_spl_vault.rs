@@ -0,0 +1,238 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::state::Mint;
+
+use crate::{
+    errors::TimeBaseVaultError,
+    events::{check_event_accounts, Event, VaultWithdrawn},
+    instructions::{authorize_withdrawal, ReceiptAccounts},
+    states::{UserRegistry, Vault},
+    utils::{bps_of, load_acc_mut_unchecked, load_acc_unchecked},
+};
+
+pub struct EarlyWithdrawSplVaultAccounts<'info> {
+    pub signer: &'info AccountInfo,
+    pub vault: &'info AccountInfo,
+    pub mint: &'info AccountInfo,
+    pub user_ata: &'info AccountInfo,
+    pub vault_ata: &'info AccountInfo,
+    pub token_program: &'info AccountInfo,
+    pub registry: &'info AccountInfo,
+    pub event_authority: &'info AccountInfo,
+    pub receipt: Option<ReceiptAccounts<'info>>,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for EarlyWithdrawSplVaultAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [signer, vault, mint, user_ata, vault_ata, token_program, _, registry, event_authority, program, receipt @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !signer.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // verify vault account
+        if !vault.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if vault.data_is_empty() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !vault.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // verify registry account
+        if !registry.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !registry.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // verify event accounts
+        check_event_accounts(event_authority, program)?;
+
+        let receipt = ReceiptAccounts::from_tail(receipt)?;
+
+        Ok(EarlyWithdrawSplVaultAccounts {
+            signer,
+            vault,
+            mint,
+            user_ata,
+            vault_ata,
+            token_program,
+            registry,
+            event_authority,
+            receipt,
+        })
+    }
+}
+
+pub struct EarlyWithdrawSplVault<'info> {
+    pub accounts: EarlyWithdrawSplVaultAccounts<'info>,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for EarlyWithdrawSplVault<'info> {
+    type Error = ProgramError;
+
+    fn try_from((_, accounts): (&'info [u8], &'info [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = EarlyWithdrawSplVaultAccounts::try_from(accounts)?;
+
+        Ok(EarlyWithdrawSplVault { accounts })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl EarlyWithdrawSplVault<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{
+            IdlAccountItem, IdlInstruction, EVENT_AUTHORITY_ACCOUNT, PROGRAM_ACCOUNT,
+        };
+
+        IdlInstruction {
+            name: "EarlyWithdrawSplVault",
+            docs:
+                "Withdraws an SPL vault before its unlock timestamp, burning its early exit penalty. Vaults with a receipt token append receipt_mint, holder_ata and token_program to the accounts",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer(
+                    "signer",
+                    "The vault owner, or the holder of its receipt token",
+                ),
+                IdlAccountItem::writable("vault", "The vault account to withdraw from"),
+                IdlAccountItem::writable("mint", "The SPL token mint account"),
+                IdlAccountItem::writable("user_ata", "User's associated token account"),
+                IdlAccountItem::writable("vault_ata", "Vault's associated token account"),
+                IdlAccountItem::readonly("token_program", "The SPL Token Program"),
+                IdlAccountItem::readonly("system_program", "The Solana System Program"),
+                IdlAccountItem::writable("registry", "The vault owner's user registry PDA"),
+                EVENT_AUTHORITY_ACCOUNT,
+                PROGRAM_ACCOUNT,
+            ],
+            args: &[],
+        }
+    };
+}
+
+impl<'info> EarlyWithdrawSplVault<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &9;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        let vault =
+            unsafe { load_acc_unchecked::<Vault>(self.accounts.vault.borrow_data_unchecked()) }?;
+
+        let unlock_timestamp = i64::from_le_bytes(vault.unlock_timestamp);
+        let amount = u64::from_le_bytes(vault.amount);
+        authorize_withdrawal(
+            self.accounts.vault,
+            self.accounts.signer,
+            self.accounts.receipt.as_ref(),
+        )?;
+        if vault.mint.ne(&Some(*self.accounts.mint.key())) {
+            return Err(TimeBaseVaultError::InvalidVaultMint.into());
+        }
+
+        // the penalty only applies while the vault is still locked
+        let penalty = if unlock_timestamp.gt(&current_timestamp) {
+            let early_exit_penalty_bps = u16::from_le_bytes(vault.early_exit_penalty_bps);
+            if early_exit_penalty_bps == 0 {
+                return Err(TimeBaseVaultError::VaultLocking.into());
+            }
+            bps_of(amount, early_exit_penalty_bps).ok_or(ProgramError::ArithmeticOverflow)?
+        } else {
+            0
+        };
+
+        let event = VaultWithdrawn {
+            vault: *self.accounts.vault.key(),
+            owner: vault.owner,
+            mint: vault.mint,
+            id: vault.id,
+            amount: vault.amount,
+            penalty: penalty.to_le_bytes(),
+            unlock_timestamp: vault.unlock_timestamp,
+            timestamp: current_timestamp.to_le_bytes(),
+        };
+
+        {
+            let mut data = self.accounts.registry.try_borrow_mut_data()?;
+            let registry = unsafe { load_acc_mut_unchecked::<UserRegistry>(&mut data) }?;
+            if registry.owner.ne(&vault.owner) {
+                return Err(TimeBaseVaultError::Unauthorized.into());
+            }
+            registry.record_withdrawal(vault)?;
+        }
+
+        {
+            let amount_bytes = vault.seed_amount;
+            let unlock_timestamp_bytes = vault.unlock_timestamp;
+            let bump_binding = vault.bump;
+            let seed = [
+                Seed::from(Vault::SEED),
+                Seed::from(&vault.owner),
+                Seed::from(self.accounts.mint.key()),
+                Seed::from(&amount_bytes),
+                Seed::from(&unlock_timestamp_bytes),
+                Seed::from(&bump_binding),
+            ];
+            let signer_seeds = Signer::from(&seed);
+
+            if penalty > 0 {
+                // burn the penalty
+                pinocchio_token::instructions::Burn {
+                    account: self.accounts.vault_ata,
+                    mint: self.accounts.mint,
+                    authority: self.accounts.vault,
+                    amount: penalty,
+                }
+                .invoke_signed(core::slice::from_ref(&signer_seeds))?;
+            }
+
+            // transfer the rest of the spl token to user
+            pinocchio_token::instructions::TransferChecked {
+                mint: self.accounts.mint,
+                from: self.accounts.vault_ata,
+                to: self.accounts.user_ata,
+                amount: amount - penalty,
+                authority: self.accounts.vault,
+                decimals: Mint::from_account_info(self.accounts.mint)?.decimals(),
+            }
+            .invoke_signed(core::slice::from_ref(&signer_seeds))?;
+
+            pinocchio_token::instructions::CloseAccount {
+                account: self.accounts.vault_ata,
+                destination: self.accounts.signer,
+                authority: self.accounts.vault,
+            }
+            .invoke_signed(&[signer_seeds])?;
+        }
+
+        // close vault account and transfer all lamports to signer
+        {
+            let mut data = self.accounts.vault.try_borrow_mut_data()?;
+            data[0] = 0xff;
+        }
+
+        *self.accounts.signer.try_borrow_mut_lamports()? +=
+            *self.accounts.vault.try_borrow_lamports()?;
+        self.accounts.vault.resize(1)?;
+        self.accounts.vault.close()?;
+
+        event.emit(self.accounts.event_authority)?;
+
+        Ok(())
+    }
+}
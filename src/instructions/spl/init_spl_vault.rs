@@ -11,8 +11,10 @@ use pinocchio_token::state::Mint;
 
 use crate::{
     errors::TimeBaseVaultError,
-    states::Vault,
-    utils::{load_acc_mut_unchecked, DataLen},
+    events::{check_event_accounts, Event, VaultCreated},
+    instructions::{split_receipt_bump, CreateReceiptAccounts},
+    states::{Config, MintConfig, UserRegistry, Vault},
+    utils::{load_acc_mut_unchecked, load_acc_unchecked, DataLen, MAX_BPS},
 };
 
 pub struct InitializeSplVaultAccounts<'info> {
@@ -24,13 +26,20 @@ pub struct InitializeSplVaultAccounts<'info> {
     pub token_program: &'info AccountInfo,
     pub associated_token_program: &'info AccountInfo,
     pub system_program: &'info AccountInfo,
+    pub registry: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+    pub treasury: &'info AccountInfo,
+    pub treasury_ata: &'info AccountInfo,
+    pub mint_config: &'info AccountInfo,
+    pub event_authority: &'info AccountInfo,
+    pub receipt: Option<CreateReceiptAccounts<'info>>,
 }
 
 impl<'info> TryFrom<&'info [AccountInfo]> for InitializeSplVaultAccounts<'info> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
-        let [signer, vault, mint, user_ata, vault_ata, token_program, associated_token_program, system_program] =
+        let [signer, vault, mint, user_ata, vault_ata, token_program, associated_token_program, system_program, registry, config, treasury, treasury_ata, mint_config, event_authority, program, receipt @ ..] =
             accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
@@ -48,6 +57,14 @@ impl<'info> TryFrom<&'info [AccountInfo]> for InitializeSplVaultAccounts<'info>
             return Err(ProgramError::AccountAlreadyInitialized);
         }
 
+        // verify config account
+        Config::check(config)?;
+
+        // verify event accounts
+        check_event_accounts(event_authority, program)?;
+
+        let receipt = CreateReceiptAccounts::from_tail(receipt)?;
+
         Ok(InitializeSplVaultAccounts {
             vault,
             signer,
@@ -57,6 +74,13 @@ impl<'info> TryFrom<&'info [AccountInfo]> for InitializeSplVaultAccounts<'info>
             token_program,
             associated_token_program,
             system_program,
+            registry,
+            config,
+            treasury,
+            treasury_ata,
+            mint_config,
+            event_authority,
+            receipt,
         })
     }
 }
@@ -66,6 +90,9 @@ pub struct InitializeSplVaultInstructionData {
     pub amount: u64,
     pub unlock_timestamp: i64,
     pub bump: u8,
+    pub registry_bump: u8,
+    pub early_exit_penalty_bps: u16,
+    pub mint_config_bump: u8,
 }
 
 impl DataLen for InitializeSplVaultInstructionData {
@@ -81,7 +108,7 @@ impl<'info> TryFrom<&'info [u8]> for InitializeSplVaultInstructionData {
         }
 
         Ok(unsafe {
-            transmute(
+            transmute::<[u8; Self::LEN], Self>(
                 TryInto::<[u8; Self::LEN]>::try_into(data)
                     .map_err(|_| ProgramError::InvalidInstructionData)?,
             )
@@ -92,6 +119,7 @@ impl<'info> TryFrom<&'info [u8]> for InitializeSplVaultInstructionData {
 pub struct InitializeSplVault<'info> {
     pub accounts: InitializeSplVaultAccounts<'info>,
     pub instruction_data: InitializeSplVaultInstructionData,
+    pub receipt_bump: Option<u8>,
 }
 
 impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for InitializeSplVault<'info> {
@@ -101,15 +129,80 @@ impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for InitializeSplVault<
         (data, accounts): (&'info [u8], &'info [AccountInfo]),
     ) -> Result<Self, Self::Error> {
         let accounts = InitializeSplVaultAccounts::try_from(accounts)?;
+        let (data, receipt_bump) = split_receipt_bump(
+            data,
+            InitializeSplVaultInstructionData::LEN,
+            accounts.receipt.is_some(),
+        )?;
         let instruction_data = InitializeSplVaultInstructionData::try_from(data)?;
 
         Ok(InitializeSplVault {
             accounts,
             instruction_data,
+            receipt_bump,
         })
     }
 }
 
+#[cfg(feature = "idl")]
+impl InitializeSplVault<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{
+            idl_fields, IdlAccountItem, IdlInstruction, IdlType, EVENT_AUTHORITY_ACCOUNT,
+            PROGRAM_ACCOUNT,
+        };
+
+        IdlInstruction {
+            name: "InitializeSplVault",
+            docs: "Creates a time-locked vault for SPL tokens. With a receipt token, whose holder withdraws the vault, receipt_mint, holder_ata, token_program and associated_token_program are appended to the accounts and the receipt mint bump to the data",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer("signer", "The user creating the vault"),
+                IdlAccountItem::writable("vault", "The vault PDA account to be created"),
+                IdlAccountItem::readonly("mint", "The SPL token mint account"),
+                IdlAccountItem::writable("user_ata", "User's associated token account"),
+                IdlAccountItem::writable(
+                    "vault_ata",
+                    "Vault's associated token account (created by instruction)",
+                ),
+                IdlAccountItem::readonly("token_program", "The SPL Token Program"),
+                IdlAccountItem::readonly(
+                    "associated_token_program",
+                    "The Associated Token Program",
+                ),
+                IdlAccountItem::readonly("system_program", "The Solana System Program"),
+                IdlAccountItem::writable(
+                    "registry",
+                    "The signer's user registry PDA (created on first use)",
+                ),
+                IdlAccountItem::readonly("config", "The program config PDA"),
+                IdlAccountItem::writable(
+                    "treasury",
+                    "The treasury recorded in the config, receives the flat creation fee",
+                ),
+                IdlAccountItem::writable(
+                    "treasury_ata",
+                    "Treasury's token account for the mint, receives the basis-point creation fee, any account when that fee is zero",
+                ),
+                IdlAccountItem::readonly(
+                    "mint_config",
+                    "The mint config PDA of the mint (may be uninitialized)",
+                ),
+                EVENT_AUTHORITY_ACCOUNT,
+                PROGRAM_ACCOUNT,
+            ],
+            args: idl_fields!(InitializeSplVaultInstructionData {
+                amount: IdlType::U64,
+                unlock_timestamp: IdlType::I64,
+                bump: IdlType::U8,
+                registry_bump: IdlType::U8,
+                early_exit_penalty_bps: IdlType::U16,
+                mint_config_bump: IdlType::U8,
+            }),
+        }
+    };
+}
+
 impl<'info> InitializeSplVault<'info> {
     pub const DISCRIMINATOR: &'info u8 = &2;
 
@@ -123,6 +216,38 @@ impl<'info> InitializeSplVault<'info> {
         if amount.eq(&0) {
             return Err(TimeBaseVaultError::AmountMustBeGreaterThanZero.into());
         }
+        let early_exit_penalty_bps = self.instruction_data.early_exit_penalty_bps;
+        if early_exit_penalty_bps > MAX_BPS {
+            return Err(TimeBaseVaultError::InvalidEarlyExitPenalty.into());
+        }
+
+        MintConfig::check(
+            self.accounts.mint_config,
+            self.accounts.mint.key(),
+            self.instruction_data.mint_config_bump,
+        )?;
+
+        let (fee, flat_fee) = {
+            let data = self.accounts.config.try_borrow_data()?;
+            let config = unsafe { load_acc_unchecked::<Config>(&data) }?;
+            let mint_config_data = self.accounts.mint_config.try_borrow_data()?;
+            let mint_config = if mint_config_data.is_empty() {
+                None
+            } else {
+                Some(unsafe { load_acc_unchecked::<MintConfig>(&mint_config_data) }?)
+            };
+            config.check_not_paused()?;
+            config.check_treasury(self.accounts.treasury)?;
+            config.check_mint_listed(mint_config)?;
+            config.validate_lock_duration(mint_config, current_timestamp, unlock_timestamp)?;
+
+            let (fee, flat_fee) = config.creation_fee(amount)?;
+            if fee > 0 {
+                config.check_treasury_ata(self.accounts.treasury_ata, self.accounts.mint.key())?;
+            }
+
+            (fee, flat_fee)
+        };
 
         Vault::validate_pda(
             self.accounts.vault.key(),
@@ -133,6 +258,18 @@ impl<'info> InitializeSplVault<'info> {
             Some(*self.accounts.mint.key()),
         )?;
 
+        UserRegistry::initialize_if_needed(
+            self.accounts.signer,
+            self.accounts.signer.key(),
+            self.accounts.registry,
+            self.instruction_data.registry_bump,
+        )?;
+        let id = {
+            let mut data = self.accounts.registry.try_borrow_mut_data()?;
+            let registry = unsafe { load_acc_mut_unchecked::<UserRegistry>(&mut data) }?;
+            registry.record_deposit(Some(*self.accounts.mint.key()), amount)?
+        };
+
         {
             // create and init vault account
             let bump_binding = [self.instruction_data.bump];
@@ -161,10 +298,21 @@ impl<'info> InitializeSplVault<'info> {
                 self.accounts.vault.try_borrow_mut_data()?;
             let vault = unsafe { load_acc_mut_unchecked::<Vault>(&mut data) }?;
 
+            vault.owner = *self.accounts.signer.key();
+            vault.id = id.to_le_bytes();
             vault.mint = Some(*self.accounts.mint.key());
             vault.unlock_timestamp = self.instruction_data.unlock_timestamp.to_le_bytes();
             vault.amount = self.instruction_data.amount.to_le_bytes();
+            vault.seed_amount = self.instruction_data.amount.to_le_bytes();
             vault.bump = [self.instruction_data.bump];
+            vault.early_exit_penalty_bps = early_exit_penalty_bps.to_le_bytes();
+            vault.receipt_mint = self
+                .accounts
+                .receipt
+                .as_ref()
+                .map(|receipt| *receipt.receipt_mint.key());
+            vault.tracked = [1];
+            vault.withdrawn = 0u64.to_le_bytes();
         }
 
         {
@@ -179,6 +327,8 @@ impl<'info> InitializeSplVault<'info> {
             }
             .invoke()?;
 
+            let decimals = Mint::from_account_info(self.accounts.mint)?.decimals();
+
             // transfer spl token to vault
             pinocchio_token::instructions::TransferChecked {
                 mint: self.accounts.mint,
@@ -186,10 +336,54 @@ impl<'info> InitializeSplVault<'info> {
                 to: self.accounts.vault_ata,
                 amount: self.instruction_data.amount,
                 authority: self.accounts.signer,
-                decimals: Mint::from_account_info(self.accounts.mint)?.decimals(),
+                decimals,
             }
             .invoke()?;
+
+            if fee > 0 {
+                // transfer fee to treasury
+                pinocchio_token::instructions::TransferChecked {
+                    mint: self.accounts.mint,
+                    from: self.accounts.user_ata,
+                    to: self.accounts.treasury_ata,
+                    amount: fee,
+                    authority: self.accounts.signer,
+                    decimals,
+                }
+                .invoke()?;
+            }
+        }
+
+        if let (Some(receipt), Some(bump)) = (&self.accounts.receipt, self.receipt_bump) {
+            receipt.mint_receipt(
+                self.accounts.signer,
+                self.accounts.vault,
+                self.accounts.system_program,
+                bump,
+            )?;
         }
+
+        if flat_fee > 0 {
+            // transfer flat fee to treasury
+            pinocchio_system::instructions::Transfer {
+                from: self.accounts.signer,
+                to: self.accounts.treasury,
+                lamports: flat_fee,
+            }
+            .invoke()?;
+        }
+
+        VaultCreated {
+            vault: *self.accounts.vault.key(),
+            owner: *self.accounts.signer.key(),
+            mint: Some(*self.accounts.mint.key()),
+            id: id.to_le_bytes(),
+            amount: amount.to_le_bytes(),
+            unlock_timestamp: unlock_timestamp.to_le_bytes(),
+            timestamp: current_timestamp.to_le_bytes(),
+        }
+        .emit(self.accounts.event_authority)?;
+
         Ok(())
     }
 }
@@ -3,3 +3,15 @@ pub use init_spl_vault::*;
 
 pub mod withdraw_spl_vault;
 pub use withdraw_spl_vault::*;
+
+pub mod early_withdraw_spl_vault;
+pub use early_withdraw_spl_vault::*;
+
+pub mod batch_init_spl_vault;
+pub use batch_init_spl_vault::*;
+
+pub mod deposit_locked_bucket;
+pub use deposit_locked_bucket::*;
+
+pub mod redeem_locked_bucket;
+pub use redeem_locked_bucket::*;
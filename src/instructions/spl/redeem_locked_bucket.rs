@@ -0,0 +1,220 @@
+use core::mem::transmute;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::state::Mint;
+
+use crate::{
+    errors::TimeBaseVaultError,
+    events::{check_event_accounts, BucketRedeemed, Event},
+    states::LockedBucket,
+    utils::{load_acc_mut, DataLen},
+};
+
+pub struct RedeemLockedBucketAccounts<'info> {
+    pub signer: &'info AccountInfo,
+    pub bucket: &'info AccountInfo,
+    pub mint: &'info AccountInfo,
+    pub receipt_mint: &'info AccountInfo,
+    pub holder_receipt_ata: &'info AccountInfo,
+    pub user_ata: &'info AccountInfo,
+    pub bucket_ata: &'info AccountInfo,
+    pub token_program: &'info AccountInfo,
+    pub event_authority: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for RedeemLockedBucketAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [signer, bucket, mint, receipt_mint, holder_receipt_ata, user_ata, bucket_ata, token_program, event_authority, program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !signer.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // verify bucket account
+        if !bucket.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !bucket.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // verify event accounts
+        check_event_accounts(event_authority, program)?;
+
+        Ok(RedeemLockedBucketAccounts {
+            signer,
+            bucket,
+            mint,
+            receipt_mint,
+            holder_receipt_ata,
+            user_ata,
+            bucket_ata,
+            token_program,
+            event_authority,
+        })
+    }
+}
+
+#[repr(C, packed)]
+pub struct RedeemLockedBucketInstructionData {
+    pub amount: u64,
+}
+
+impl DataLen for RedeemLockedBucketInstructionData {
+    const LEN: usize = core::mem::size_of::<RedeemLockedBucketInstructionData>();
+}
+
+impl<'info> TryFrom<&'info [u8]> for RedeemLockedBucketInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(unsafe {
+            transmute::<[u8; Self::LEN], Self>(
+                TryInto::<[u8; Self::LEN]>::try_into(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            )
+        })
+    }
+}
+
+pub struct RedeemLockedBucket<'info> {
+    pub accounts: RedeemLockedBucketAccounts<'info>,
+    pub instruction_data: RedeemLockedBucketInstructionData,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for RedeemLockedBucket<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'info [u8], &'info [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = RedeemLockedBucketAccounts::try_from(accounts)?;
+        let instruction_data = RedeemLockedBucketInstructionData::try_from(data)?;
+
+        Ok(RedeemLockedBucket {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl RedeemLockedBucket<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{
+            idl_fields, IdlAccountItem, IdlInstruction, IdlType, EVENT_AUTHORITY_ACCOUNT,
+            PROGRAM_ACCOUNT,
+        };
+
+        IdlInstruction {
+            name: "RedeemLockedBucket",
+            docs: "Burns receipts of a locked bucket after its unlock timestamp and transfers as many pooled tokens to the signer",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer("signer", "The holder of the receipts"),
+                IdlAccountItem::writable("bucket", "The locked bucket to redeem from"),
+                IdlAccountItem::readonly("mint", "The SPL token mint account"),
+                IdlAccountItem::writable("receipt_mint", "The receipt mint PDA of the bucket"),
+                IdlAccountItem::writable(
+                    "holder_receipt_ata",
+                    "Holder's token account for the receipts",
+                ),
+                IdlAccountItem::writable("user_ata", "Holder's associated token account"),
+                IdlAccountItem::writable("bucket_ata", "Bucket's associated token account"),
+                IdlAccountItem::readonly("token_program", "The SPL Token Program"),
+                EVENT_AUTHORITY_ACCOUNT,
+                PROGRAM_ACCOUNT,
+            ],
+            args: idl_fields!(RedeemLockedBucketInstructionData {
+                amount: IdlType::U64,
+            }),
+        }
+    };
+}
+
+impl<'info> RedeemLockedBucket<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &22;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let amount = self.instruction_data.amount;
+        if amount.eq(&0) {
+            return Err(TimeBaseVaultError::AmountMustBeGreaterThanZero.into());
+        }
+
+        let (unlock_timestamp_bytes, bump) = {
+            let mut data = self.accounts.bucket.try_borrow_mut_data()?;
+            let bucket = unsafe { load_acc_mut::<LockedBucket>(&mut data) }?;
+            if bucket.mint.ne(self.accounts.mint.key()) {
+                return Err(TimeBaseVaultError::InvalidVaultMint.into());
+            }
+            if bucket.receipt_mint.ne(self.accounts.receipt_mint.key()) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if i64::from_le_bytes(bucket.unlock_timestamp).gt(&current_timestamp) {
+                return Err(TimeBaseVaultError::VaultLocking.into());
+            }
+            bucket.total = u64::from_le_bytes(bucket.total)
+                .checked_sub(amount)
+                .ok_or(ProgramError::InsufficientFunds)?
+                .to_le_bytes();
+
+            (bucket.unlock_timestamp, bucket.bump)
+        };
+
+        // burn the receipts, which fails if the signer holds fewer
+        pinocchio_token::instructions::Burn {
+            account: self.accounts.holder_receipt_ata,
+            mint: self.accounts.receipt_mint,
+            authority: self.accounts.signer,
+            amount,
+        }
+        .invoke()?;
+
+        {
+            let seed = [
+                Seed::from(LockedBucket::SEED),
+                Seed::from(self.accounts.mint.key()),
+                Seed::from(&unlock_timestamp_bytes),
+                Seed::from(&bump),
+            ];
+            let signer_seeds = Signer::from(&seed);
+
+            // transfer spl token to holder
+            pinocchio_token::instructions::TransferChecked {
+                mint: self.accounts.mint,
+                from: self.accounts.bucket_ata,
+                to: self.accounts.user_ata,
+                amount,
+                authority: self.accounts.bucket,
+                decimals: Mint::from_account_info(self.accounts.mint)?.decimals(),
+            }
+            .invoke_signed(&[signer_seeds])?;
+        }
+
+        BucketRedeemed {
+            bucket: *self.accounts.bucket.key(),
+            holder: *self.accounts.signer.key(),
+            mint: *self.accounts.mint.key(),
+            amount: amount.to_le_bytes(),
+            timestamp: current_timestamp.to_le_bytes(),
+        }
+        .emit(self.accounts.event_authority)
+    }
+}
@@ -7,7 +7,13 @@ use pinocchio::{
 };
 use pinocchio_token::state::Mint;
 
-use crate::{errors::TimeBaseVaultError, states::Vault, utils::load_acc_unchecked};
+use crate::{
+    errors::TimeBaseVaultError,
+    events::{check_event_accounts, Event, VaultWithdrawn},
+    instructions::{authorize_withdrawal, ReceiptAccounts},
+    states::{UserRegistry, Vault},
+    utils::{load_acc_mut_unchecked, load_acc_unchecked},
+};
 
 pub struct WithdrawSplVaultAccounts<'info> {
     pub signer: &'info AccountInfo,
@@ -16,13 +22,18 @@ pub struct WithdrawSplVaultAccounts<'info> {
     pub user_ata: &'info AccountInfo,
     pub vault_ata: &'info AccountInfo,
     pub token_program: &'info AccountInfo,
+    pub registry: &'info AccountInfo,
+    pub event_authority: &'info AccountInfo,
+    pub receipt: Option<ReceiptAccounts<'info>>,
 }
 
 impl<'info> TryFrom<&'info [AccountInfo]> for WithdrawSplVaultAccounts<'info> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
-        let [signer, vault, mint, user_ata, vault_ata, token_program, _] = accounts else {
+        let [signer, vault, mint, user_ata, vault_ata, token_program, _, registry, event_authority, program, receipt @ ..] =
+            accounts
+        else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
@@ -37,6 +48,22 @@ impl<'info> TryFrom<&'info [AccountInfo]> for WithdrawSplVaultAccounts<'info> {
         if vault.data_is_empty() {
             return Err(ProgramError::InvalidAccountData);
         }
+        if !vault.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // verify registry account
+        if !registry.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !registry.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // verify event accounts
+        check_event_accounts(event_authority, program)?;
+
+        let receipt = ReceiptAccounts::from_tail(receipt)?;
 
         Ok(WithdrawSplVaultAccounts {
             vault,
@@ -45,6 +72,9 @@ impl<'info> TryFrom<&'info [AccountInfo]> for WithdrawSplVaultAccounts<'info> {
             user_ata,
             vault_ata,
             token_program,
+            registry,
+            event_authority,
+            receipt,
         })
     }
 }
@@ -63,66 +93,145 @@ impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for WithdrawSplVault<'i
     }
 }
 
+#[cfg(feature = "idl")]
+impl WithdrawSplVault<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{
+            IdlAccountItem, IdlInstruction, EVENT_AUTHORITY_ACCOUNT, PROGRAM_ACCOUNT,
+        };
+
+        IdlInstruction {
+            name: "WithdrawSplVault",
+            docs: "Withdraws all SPL tokens from a vault after the unlock timestamp. Vaults with a receipt token append receipt_mint, holder_ata and token_program to the accounts",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer(
+                    "signer",
+                    "The vault owner, or the holder of its receipt token",
+                ),
+                IdlAccountItem::writable("vault", "The vault account to withdraw from"),
+                IdlAccountItem::writable("mint", "The SPL token mint account"),
+                IdlAccountItem::writable("user_ata", "User's associated token account"),
+                IdlAccountItem::writable("vault_ata", "Vault's associated token account"),
+                IdlAccountItem::readonly("token_program", "The SPL Token Program"),
+                IdlAccountItem::readonly("system_program", "The Solana System Program"),
+                IdlAccountItem::writable("registry", "The vault owner's user registry PDA"),
+                EVENT_AUTHORITY_ACCOUNT,
+                PROGRAM_ACCOUNT,
+            ],
+            args: &[],
+        }
+    };
+}
+
 impl<'info> WithdrawSplVault<'info> {
     pub const DISCRIMINATOR: &'info u8 = &3;
 
     pub fn process(&mut self) -> ProgramResult {
         let current_timestamp = Clock::get()?.unix_timestamp;
 
-        let vault =
-            unsafe { load_acc_unchecked::<Vault>(self.accounts.vault.borrow_data_unchecked()) }?;
+        authorize_withdrawal(
+            self.accounts.vault,
+            self.accounts.signer,
+            self.accounts.receipt.as_ref(),
+        )?;
+        Self::withdraw(
+            self.accounts.signer,
+            self.accounts.vault,
+            self.accounts.mint,
+            self.accounts.user_ata,
+            self.accounts.vault_ata,
+            self.accounts.registry,
+            current_timestamp,
+        )?
+        .emit(self.accounts.event_authority)
+    }
 
+    /// Transfers the tokens of the unlocked SPL `vault` to `user_ata`, closes
+    /// the vault and its token account into `signer` and returns the event to
+    /// emit. `signer` must be authorized with [`authorize_withdrawal`] first.
+    pub fn withdraw(
+        signer: &AccountInfo,
+        vault_account: &AccountInfo,
+        mint: &AccountInfo,
+        user_ata: &AccountInfo,
+        vault_ata: &AccountInfo,
+        registry: &AccountInfo,
+        current_timestamp: i64,
+    ) -> Result<VaultWithdrawn, ProgramError> {
+        let vault = unsafe { load_acc_unchecked::<Vault>(vault_account.borrow_data_unchecked()) }?;
         let unlock_timestamp = i64::from_le_bytes(vault.unlock_timestamp);
-        if vault.owner.ne(self.accounts.signer.key()) {
-            return Err(TimeBaseVaultError::Unauthorized.into());
-        }
+
         if unlock_timestamp.gt(&current_timestamp) {
             return Err(TimeBaseVaultError::VaultLocking.into());
         }
+        if vault.mint.ne(&Some(*mint.key())) {
+            return Err(TimeBaseVaultError::InvalidVaultMint.into());
+        }
+
+        let event = VaultWithdrawn {
+            vault: *vault_account.key(),
+            owner: vault.owner,
+            mint: vault.mint,
+            id: vault.id,
+            amount: vault.amount,
+            penalty: 0u64.to_le_bytes(),
+            unlock_timestamp: vault.unlock_timestamp,
+            timestamp: current_timestamp.to_le_bytes(),
+        };
+
+        {
+            let mut data = registry.try_borrow_mut_data()?;
+            let registry = unsafe { load_acc_mut_unchecked::<UserRegistry>(&mut data) }?;
+            if registry.owner.ne(&vault.owner) {
+                return Err(TimeBaseVaultError::Unauthorized.into());
+            }
+            registry.record_withdrawal(vault)?;
+        }
 
         {
-            let amount_bytes = vault.amount;
+            let amount_bytes = vault.seed_amount;
             let unlock_timestamp_bytes = vault.unlock_timestamp;
             let bump_binding = vault.bump;
             let seed = [
                 Seed::from(Vault::SEED),
-                Seed::from(self.accounts.signer.key()),
-                Seed::from(self.accounts.mint.key()),
+                Seed::from(&vault.owner),
+                Seed::from(mint.key()),
                 Seed::from(&amount_bytes),
                 Seed::from(&unlock_timestamp_bytes),
                 Seed::from(&bump_binding),
             ];
             let signer_seeds = Signer::from(&seed);
+
             // transfer spl token to user
             pinocchio_token::instructions::TransferChecked {
-                mint: self.accounts.mint,
-                from: self.accounts.vault_ata,
-                to: self.accounts.user_ata,
+                mint,
+                from: vault_ata,
+                to: user_ata,
                 amount: u64::from_le_bytes(vault.amount),
-                authority: self.accounts.vault,
-                decimals: Mint::from_account_info(self.accounts.mint)?.decimals(),
+                authority: vault_account,
+                decimals: Mint::from_account_info(mint)?.decimals(),
             }
-            .invoke_signed(&[signer_seeds.clone()])?;
+            .invoke_signed(core::slice::from_ref(&signer_seeds))?;
 
             pinocchio_token::instructions::CloseAccount {
-                account: self.accounts.vault_ata,
-                destination: self.accounts.signer,
-                authority: self.accounts.vault,
+                account: vault_ata,
+                destination: signer,
+                authority: vault_account,
             }
             .invoke_signed(&[signer_seeds])?;
         }
 
         // close vault account and transfer all lamports to signer
         {
-            let mut data = self.accounts.vault.try_borrow_mut_data()?;
+            let mut data = vault_account.try_borrow_mut_data()?;
             data[0] = 0xff;
         }
 
-        *self.accounts.signer.try_borrow_mut_lamports()? +=
-            *self.accounts.vault.try_borrow_lamports()?;
-        self.accounts.vault.resize(1)?;
-        self.accounts.vault.close()?;
+        *signer.try_borrow_mut_lamports()? += *vault_account.try_borrow_lamports()?;
+        vault_account.resize(1)?;
+        vault_account.close()?;
 
-        Ok(())
+        Ok(event)
     }
 }
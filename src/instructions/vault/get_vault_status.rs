@@ -0,0 +1,148 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    states::Vault,
+    utils::{load_acc_unchecked, to_bytes, DataLen},
+};
+
+pub struct GetVaultStatusAccounts<'info> {
+    pub vault: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for GetVaultStatusAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [vault] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // verify vault account
+        if !vault.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        // vaults carry no discriminator, their length tells them apart from
+        // the other accounts of the program
+        if vault.data_len() != Vault::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(GetVaultStatusAccounts { vault })
+    }
+}
+
+/// The return data of [`GetVaultStatus`].
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VaultStatus {
+    /// The vault owner
+    pub owner: Pubkey,
+    /// The token mint (None for SOL vaults)
+    pub mint: Option<Pubkey>,
+    /// The amount in the vault
+    pub amount: [u8; 8], // u64 as bytes
+    /// The amount moved out of the vault by splits, as withdrawing the rest
+    /// closes it
+    pub withdrawn: [u8; 8], // u64 as bytes
+    /// The amount the withdraw instructions release now
+    pub withdrawable: [u8; 8], // u64 as bytes
+    /// The unlock timestamp of the vault
+    pub unlock_timestamp: [u8; 8], // i64 as bytes
+    /// The mint of the receipt token whose holder withdraws the vault in place
+    /// of its owner (if applicable)
+    pub receipt_mint: Option<Pubkey>,
+}
+
+impl DataLen for VaultStatus {
+    const LEN: usize = core::mem::size_of::<VaultStatus>();
+}
+
+#[cfg(feature = "idl")]
+impl VaultStatus {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "VaultStatus",
+            docs: "The return data of GetVaultStatus",
+            len: Self::LEN,
+            fields: idl_fields!(VaultStatus {
+                owner: IdlType::PublicKey,
+                mint: IdlType::Option(&IdlType::PublicKey),
+                amount: IdlType::U64,
+                withdrawn: IdlType::U64,
+                withdrawable: IdlType::U64,
+                unlock_timestamp: IdlType::I64,
+                receipt_mint: IdlType::Option(&IdlType::PublicKey),
+            }),
+        }
+    };
+}
+
+pub struct GetVaultStatus<'info> {
+    pub accounts: GetVaultStatusAccounts<'info>,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for GetVaultStatus<'info> {
+    type Error = ProgramError;
+
+    fn try_from((_, accounts): (&'info [u8], &'info [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = GetVaultStatusAccounts::try_from(accounts)?;
+
+        Ok(GetVaultStatus { accounts })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl GetVaultStatus<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{IdlAccountItem, IdlInstruction};
+
+        IdlInstruction {
+            name: "GetVaultStatus",
+            docs: "Returns the VaultStatus of a vault as return data, after checking the vault is at the PDA of its own seeds",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[IdlAccountItem::readonly("vault", "The vault to query")],
+            args: &[],
+        }
+    };
+}
+
+impl<'info> GetVaultStatus<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &24;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        let data = self.accounts.vault.try_borrow_data()?;
+        let vault = unsafe { load_acc_unchecked::<Vault>(&data) }?;
+        let unlock_timestamp = i64::from_le_bytes(vault.unlock_timestamp);
+
+        vault.check_address(self.accounts.vault.key())?;
+
+        let withdrawable = if unlock_timestamp.gt(&current_timestamp) {
+            0u64.to_le_bytes()
+        } else {
+            vault.amount
+        };
+        let status = VaultStatus {
+            owner: vault.owner,
+            mint: vault.mint,
+            amount: vault.amount,
+            withdrawn: vault.withdrawn,
+            withdrawable,
+            unlock_timestamp: vault.unlock_timestamp,
+            receipt_mint: vault.receipt_mint,
+        };
+        set_return_data(unsafe { to_bytes(&status) });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+use core::mem::transmute;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+
+use crate::{
+    states::Vault,
+    utils::{load_acc_unchecked, to_bytes, DataLen},
+};
+
+pub struct GetVotingPowerAccounts<'info> {
+    pub vault: &'info AccountInfo,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for GetVotingPowerAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [vault] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // verify vault account
+        if !vault.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        // vaults carry no discriminator, their length tells them apart from
+        // the other accounts of the program
+        if vault.data_len() != Vault::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(GetVotingPowerAccounts { vault })
+    }
+}
+
+#[repr(C, packed)]
+pub struct GetVotingPowerInstructionData {
+    pub max_multiplier_bps: u16,
+    pub max_lock_duration: i64,
+}
+
+impl DataLen for GetVotingPowerInstructionData {
+    const LEN: usize = core::mem::size_of::<GetVotingPowerInstructionData>();
+}
+
+impl<'info> TryFrom<&'info [u8]> for GetVotingPowerInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(unsafe {
+            transmute::<[u8; Self::LEN], Self>(
+                TryInto::<[u8; Self::LEN]>::try_into(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            )
+        })
+    }
+}
+
+/// The return data of [`GetVotingPower`].
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VotingPower {
+    /// The vault owner, which can not move a vault created with a receipt
+    /// token: the vote then belongs to the holder of `receipt_mint`
+    pub owner: Pubkey,
+    /// The token mint (None for SOL vaults)
+    pub mint: Option<Pubkey>,
+    /// The amount locked
+    pub amount: [u8; 8], // u64 as bytes
+    /// The unlock timestamp of the vault
+    pub unlock_timestamp: [u8; 8], // i64 as bytes
+    /// The amount weighted by the remaining lock time
+    pub voting_power: [u8; 8], // u64 as bytes
+    /// The mint of the receipt token whose holder withdraws the vault in place
+    /// of its owner (if applicable)
+    pub receipt_mint: Option<Pubkey>,
+}
+
+impl DataLen for VotingPower {
+    const LEN: usize = core::mem::size_of::<VotingPower>();
+}
+
+#[cfg(feature = "idl")]
+impl VotingPower {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "VotingPower",
+            docs: "The return data of GetVotingPower",
+            len: Self::LEN,
+            fields: idl_fields!(VotingPower {
+                owner: IdlType::PublicKey,
+                mint: IdlType::Option(&IdlType::PublicKey),
+                amount: IdlType::U64,
+                unlock_timestamp: IdlType::I64,
+                voting_power: IdlType::U64,
+                receipt_mint: IdlType::Option(&IdlType::PublicKey),
+            }),
+        }
+    };
+}
+
+pub struct GetVotingPower<'info> {
+    pub accounts: GetVotingPowerAccounts<'info>,
+    pub instruction_data: GetVotingPowerInstructionData,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for GetVotingPower<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'info [u8], &'info [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = GetVotingPowerAccounts::try_from(accounts)?;
+        let instruction_data = GetVotingPowerInstructionData::try_from(data)?;
+
+        Ok(GetVotingPower {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl GetVotingPower<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{idl_fields, IdlAccountItem, IdlInstruction, IdlType};
+
+        IdlInstruction {
+            name: "GetVotingPower",
+            docs: "Returns the VotingPower of a vault as return data, its amount weighted from 1x once unlocked up to max_multiplier_bps for a remaining lock time of max_lock_duration or more. The vault must sit at the PDA of its own seeds, and the vote of a vault with a receipt_mint belongs to the receipt holder rather than the owner",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[IdlAccountItem::readonly("vault", "The vault to weigh")],
+            args: idl_fields!(GetVotingPowerInstructionData {
+                max_multiplier_bps: IdlType::U16,
+                max_lock_duration: IdlType::I64,
+            }),
+        }
+    };
+}
+
+impl<'info> GetVotingPower<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &23;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        let data = self.accounts.vault.try_borrow_data()?;
+        let vault = unsafe { load_acc_unchecked::<Vault>(&data) }?;
+
+        vault.check_address(self.accounts.vault.key())?;
+
+        let voting_power = VotingPower {
+            owner: vault.owner,
+            mint: vault.mint,
+            amount: vault.amount,
+            unlock_timestamp: vault.unlock_timestamp,
+            voting_power: vault
+                .voting_power(
+                    current_timestamp,
+                    self.instruction_data.max_multiplier_bps,
+                    self.instruction_data.max_lock_duration,
+                )?
+                .to_le_bytes(),
+            receipt_mint: vault.receipt_mint,
+        };
+        set_return_data(unsafe { to_bytes(&voting_power) });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::state::{Mint, TokenAccount};
+
+use crate::{
+    errors::TimeBaseVaultError,
+    events::{check_event_accounts, Event, VaultsMerged},
+    instructions::check_no_receipt,
+    states::{Config, UserRegistry, Vault},
+    utils::{load_acc_mut_unchecked, load_acc_unchecked},
+};
+
+/// The accounts only SPL vaults need.
+pub struct MergeVaultsTokenAccounts<'info> {
+    pub mint: &'info AccountInfo,
+    pub source_ata: &'info AccountInfo,
+    pub destination_ata: &'info AccountInfo,
+    pub token_program: &'info AccountInfo,
+}
+
+pub struct MergeVaultsAccounts<'info> {
+    pub signer: &'info AccountInfo,
+    pub source: &'info AccountInfo,
+    pub destination: &'info AccountInfo,
+    pub registry: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+    pub event_authority: &'info AccountInfo,
+    pub token_accounts: Option<MergeVaultsTokenAccounts<'info>>,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for MergeVaultsAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [signer, source, destination, registry, config, event_authority, program, token_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !signer.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // verify vault accounts
+        if !source.is_writable() || !destination.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !source.is_owned_by(&crate::ID) || !destination.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if source.key().eq(destination.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // verify registry account
+        if !registry.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !registry.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // verify config account
+        Config::check(config)?;
+
+        // verify event accounts
+        check_event_accounts(event_authority, program)?;
+
+        let token_accounts = match token_accounts {
+            [] => None,
+            [mint, source_ata, destination_ata, token_program] => Some(MergeVaultsTokenAccounts {
+                mint,
+                source_ata,
+                destination_ata,
+                token_program,
+            }),
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+
+        Ok(MergeVaultsAccounts {
+            signer,
+            source,
+            destination,
+            registry,
+            config,
+            event_authority,
+            token_accounts,
+        })
+    }
+}
+
+pub struct MergeVaults<'info> {
+    pub accounts: MergeVaultsAccounts<'info>,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for MergeVaults<'info> {
+    type Error = ProgramError;
+
+    fn try_from((_, accounts): (&'info [u8], &'info [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = MergeVaultsAccounts::try_from(accounts)?;
+
+        Ok(MergeVaults { accounts })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl MergeVaults<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{
+            IdlAccountItem, IdlInstruction, EVENT_AUTHORITY_ACCOUNT, PROGRAM_ACCOUNT,
+        };
+
+        IdlInstruction {
+            name: "MergeVaults",
+            docs: "Folds a vault into another vault of the same owner and mint that unlocks at the same time or later, then closes it. Like a split, it fails while the config is paused. SPL vaults append mint, source_ata, destination_ata and token_program to the accounts",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer("signer", "The owner of both vaults"),
+                IdlAccountItem::writable("source", "The vault to merge and close"),
+                IdlAccountItem::writable("destination", "The vault receiving the amount"),
+                IdlAccountItem::writable("registry", "The signer's user registry PDA"),
+                IdlAccountItem::readonly("config", "The program config PDA"),
+                EVENT_AUTHORITY_ACCOUNT,
+                PROGRAM_ACCOUNT,
+            ],
+            args: &[],
+        }
+    };
+}
+
+impl<'info> MergeVaults<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &20;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+
+        // a merge reshapes locks like a split, so the pause stops both
+        {
+            let data = self.accounts.config.try_borrow_data()?;
+            unsafe { load_acc_unchecked::<Config>(&data) }?.check_not_paused()?;
+        }
+
+        let (
+            mint,
+            amount,
+            seed_amount,
+            source_unlock_timestamp,
+            source_bump,
+            source_penalty,
+            source_tracked,
+        ) = {
+            let mut data = self.accounts.source.try_borrow_mut_data()?;
+            let source = unsafe { load_acc_mut_unchecked::<Vault>(&mut data) }?;
+            if source.owner.ne(self.accounts.signer.key()) {
+                return Err(TimeBaseVaultError::Unauthorized.into());
+            }
+            check_no_receipt(source)?;
+
+            (
+                source.mint,
+                u64::from_le_bytes(source.amount),
+                source.seed_amount,
+                source.unlock_timestamp,
+                source.bump,
+                u16::from_le_bytes(source.early_exit_penalty_bps),
+                source.is_tracked(),
+            )
+        };
+
+        let (total, released) = {
+            let mut data = self.accounts.destination.try_borrow_mut_data()?;
+            let destination = unsafe { load_acc_mut_unchecked::<Vault>(&mut data) }?;
+            if destination.owner.ne(self.accounts.signer.key()) {
+                return Err(TimeBaseVaultError::Unauthorized.into());
+            }
+            check_no_receipt(destination)?;
+            if destination.mint.ne(&mint) {
+                return Err(TimeBaseVaultError::InvalidVaultMint.into());
+            }
+            if i64::from_le_bytes(destination.unlock_timestamp)
+                .lt(&i64::from_le_bytes(source_unlock_timestamp))
+            {
+                return Err(TimeBaseVaultError::UnlockTimestampTooEarly.into());
+            }
+            // the merged amount must not become easier to withdraw early,
+            // a penalty of 0 meaning no early exit at all
+            let destination_penalty = u16::from_le_bytes(destination.early_exit_penalty_bps);
+            if destination_penalty != 0
+                && (source_penalty == 0 || destination_penalty < source_penalty)
+            {
+                return Err(TimeBaseVaultError::EarlyExitPenaltyMismatch.into());
+            }
+
+            // the seed amount is kept, so the destination keeps its address
+            let total = u64::from_le_bytes(destination.amount)
+                .checked_add(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            destination.amount = total.to_le_bytes();
+
+            // a merged vault is only counted in the mint totals when both
+            // vaults were, otherwise the counted amount is released
+            let released = match (source_tracked, destination.is_tracked()) {
+                (true, false) => Some(amount),
+                (false, true) => Some(u64::from_le_bytes(destination.amount) - amount),
+                _ => None,
+            };
+            if released.is_some() {
+                destination.tracked = [0];
+            }
+
+            (total, released)
+        };
+
+        match (&mint, &self.accounts.token_accounts) {
+            (Some(mint), Some(token_accounts)) if mint.eq(token_accounts.mint.key()) => {
+                // the source vault signs the transfer, so the tokens must
+                // only move between the token accounts of both vaults
+                for (token_account, vault) in [
+                    (token_accounts.source_ata, self.accounts.source),
+                    (token_accounts.destination_ata, self.accounts.destination),
+                ] {
+                    let token_account = TokenAccount::from_account_info(token_account)?;
+                    if token_account.owner().ne(vault.key()) || token_account.mint().ne(mint) {
+                        return Err(ProgramError::InvalidAccountData);
+                    }
+                }
+            }
+            (None, None) => {}
+            _ => return Err(TimeBaseVaultError::InvalidVaultMint.into()),
+        }
+
+        {
+            // the amount stays locked, only the source vault goes away
+            let mut data = self.accounts.registry.try_borrow_mut_data()?;
+            let registry = unsafe { load_acc_mut_unchecked::<UserRegistry>(&mut data) }?;
+            if registry.owner.ne(self.accounts.signer.key()) {
+                return Err(TimeBaseVaultError::Unauthorized.into());
+            }
+            if let Some(released) = released {
+                registry.record_release(mint, released)?;
+            }
+            registry.record_close()?;
+        }
+
+        match &self.accounts.token_accounts {
+            Some(token_accounts) => {
+                let seed = [
+                    Seed::from(Vault::SEED),
+                    Seed::from(self.accounts.signer.key()),
+                    Seed::from(token_accounts.mint.key()),
+                    Seed::from(&seed_amount),
+                    Seed::from(&source_unlock_timestamp),
+                    Seed::from(&source_bump),
+                ];
+                let signer_seeds = Signer::from(&seed);
+
+                // transfer spl token to destination vault
+                pinocchio_token::instructions::TransferChecked {
+                    mint: token_accounts.mint,
+                    from: token_accounts.source_ata,
+                    to: token_accounts.destination_ata,
+                    amount,
+                    authority: self.accounts.source,
+                    decimals: Mint::from_account_info(token_accounts.mint)?.decimals(),
+                }
+                .invoke_signed(core::slice::from_ref(&signer_seeds))?;
+
+                pinocchio_token::instructions::CloseAccount {
+                    account: token_accounts.source_ata,
+                    destination: self.accounts.signer,
+                    authority: self.accounts.source,
+                }
+                .invoke_signed(&[signer_seeds])?;
+            }
+            None => {
+                // both vaults are owned by the program, so the lamports move
+                // without a CPI
+                *self.accounts.source.try_borrow_mut_lamports()? -= amount;
+                *self.accounts.destination.try_borrow_mut_lamports()? += amount;
+            }
+        }
+
+        // close source vault account and return its rent to signer
+        {
+            let mut data = self.accounts.source.try_borrow_mut_data()?;
+            data[0] = 0xff;
+        }
+
+        *self.accounts.signer.try_borrow_mut_lamports()? +=
+            *self.accounts.source.try_borrow_lamports()?;
+        self.accounts.source.resize(1)?;
+        self.accounts.source.close()?;
+
+        VaultsMerged {
+            source: *self.accounts.source.key(),
+            destination: *self.accounts.destination.key(),
+            mint,
+            amount: amount.to_le_bytes(),
+            total: total.to_le_bytes(),
+            timestamp: current_timestamp.to_le_bytes(),
+        }
+        .emit(self.accounts.event_authority)
+    }
+}
@@ -0,0 +1,11 @@
+pub mod get_vault_status;
+pub mod get_voting_power;
+pub mod merge_vaults;
+pub mod receipt;
+pub mod split_vault;
+
+pub use get_vault_status::*;
+pub use get_voting_power::*;
+pub use merge_vaults::*;
+pub use receipt::*;
+pub use split_vault::*;
@@ -0,0 +1,209 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+use pinocchio_token::{
+    instructions::AuthorityType,
+    state::{Mint, TokenAccount},
+};
+
+use crate::{
+    errors::TimeBaseVaultError,
+    states::Vault,
+    utils::{create_pda_account, load_acc_unchecked},
+};
+
+/// The accounts appended to the initialize instructions to create the vault
+/// with a receipt token.
+pub struct CreateReceiptAccounts<'info> {
+    pub receipt_mint: &'info AccountInfo,
+    pub holder_ata: &'info AccountInfo,
+    pub token_program: &'info AccountInfo,
+    pub associated_token_program: &'info AccountInfo,
+}
+
+impl<'info> CreateReceiptAccounts<'info> {
+    /// Parses the trailing accounts of an initialize instruction, `None` when
+    /// the vault is created without a receipt.
+    pub fn from_tail(accounts: &'info [AccountInfo]) -> Result<Option<Self>, ProgramError> {
+        match accounts {
+            [] => Ok(None),
+            [receipt_mint, holder_ata, token_program, associated_token_program] => {
+                if !receipt_mint.is_writable() || !receipt_mint.data_is_empty() {
+                    return Err(ProgramError::AccountAlreadyInitialized);
+                }
+
+                Ok(Some(CreateReceiptAccounts {
+                    receipt_mint,
+                    holder_ata,
+                    token_program,
+                    associated_token_program,
+                }))
+            }
+            _ => Err(ProgramError::NotEnoughAccountKeys),
+        }
+    }
+
+    /// Creates the receipt mint of `vault` at its canonical bump, even when
+    /// its address was pre-funded, and mints its single token to `owner`. The
+    /// mint authority is removed afterwards, so the supply stays 1.
+    pub fn mint_receipt(
+        &self,
+        owner: &AccountInfo,
+        vault: &AccountInfo,
+        system_program: &AccountInfo,
+        bump: u8,
+    ) -> ProgramResult {
+        Vault::validate_receipt_pda(self.receipt_mint.key(), vault.key(), bump)?;
+
+        let bump_binding = [bump];
+        let seed = [
+            Seed::from(Vault::RECEIPT_SEED),
+            Seed::from(vault.key()),
+            Seed::from(&bump_binding),
+        ];
+        let signer_seeds = Signer::from(&seed);
+
+        create_pda_account(
+            owner,
+            self.receipt_mint,
+            Mint::LEN,
+            &pinocchio_token::ID,
+            core::slice::from_ref(&signer_seeds),
+        )?;
+
+        pinocchio_token::instructions::InitializeMint2 {
+            mint: self.receipt_mint,
+            decimals: 0,
+            mint_authority: self.receipt_mint.key(),
+            freeze_authority: None,
+        }
+        .invoke()?;
+
+        pinocchio_associated_token_account::instructions::Create {
+            account: self.holder_ata,
+            mint: self.receipt_mint,
+            funding_account: owner,
+            system_program,
+            token_program: self.token_program,
+            wallet: owner,
+        }
+        .invoke()?;
+
+        pinocchio_token::instructions::MintTo {
+            mint: self.receipt_mint,
+            account: self.holder_ata,
+            mint_authority: self.receipt_mint,
+            amount: 1,
+        }
+        .invoke_signed(core::slice::from_ref(&signer_seeds))?;
+
+        pinocchio_token::instructions::SetAuthority {
+            account: self.receipt_mint,
+            authority: self.receipt_mint,
+            authority_type: AuthorityType::MintTokens,
+            new_authority: None,
+        }
+        .invoke_signed(&[signer_seeds])
+    }
+}
+
+/// Splits the receipt mint bump off the data of an initialize instruction,
+/// which is `len` bytes followed by the bump when the vault is created with a
+/// receipt.
+pub fn split_receipt_bump(
+    data: &[u8],
+    len: usize,
+    with_receipt: bool,
+) -> Result<(&[u8], Option<u8>), ProgramError> {
+    match (data.split_at_checked(len), with_receipt) {
+        (Some((data, [])), false) => Ok((data, None)),
+        (Some((data, [bump])), true) => Ok((data, Some(*bump))),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/// The accounts appended to the withdraw instructions to withdraw a vault
+/// created with a receipt token.
+pub struct ReceiptAccounts<'info> {
+    pub receipt_mint: &'info AccountInfo,
+    pub holder_ata: &'info AccountInfo,
+    pub token_program: &'info AccountInfo,
+}
+
+impl<'info> ReceiptAccounts<'info> {
+    /// Parses the trailing accounts of a withdraw instruction, `None` when
+    /// the vault has no receipt.
+    pub fn from_tail(accounts: &'info [AccountInfo]) -> Result<Option<Self>, ProgramError> {
+        match accounts {
+            [] => Ok(None),
+            [receipt_mint, holder_ata, token_program] => Ok(Some(ReceiptAccounts {
+                receipt_mint,
+                holder_ata,
+                token_program,
+            })),
+            _ => Err(ProgramError::NotEnoughAccountKeys),
+        }
+    }
+}
+
+/// Checks that `signer` may withdraw `vault`: the holder of its receipt token
+/// if it has one, its owner otherwise. The receipt is burnt and its token
+/// account closed into `signer`.
+pub fn authorize_withdrawal(
+    vault: &AccountInfo,
+    signer: &AccountInfo,
+    receipt: Option<&ReceiptAccounts>,
+) -> ProgramResult {
+    let vault = unsafe { load_acc_unchecked::<Vault>(vault.borrow_data_unchecked()) }?;
+    let (receipt_mint, receipt) = match (&vault.receipt_mint, receipt) {
+        (None, _) => {
+            if vault.owner.ne(signer.key()) {
+                return Err(TimeBaseVaultError::Unauthorized.into());
+            }
+            return Ok(());
+        }
+        (Some(_), None) => return Err(TimeBaseVaultError::ReceiptRequired.into()),
+        (Some(receipt_mint), Some(receipt)) => (receipt_mint, receipt),
+    };
+
+    if receipt.receipt_mint.key().ne(receipt_mint) {
+        return Err(TimeBaseVaultError::ReceiptRequired.into());
+    }
+    {
+        let holder_ata = TokenAccount::from_account_info(receipt.holder_ata)?;
+        if holder_ata.mint().ne(receipt_mint)
+            || holder_ata.owner().ne(signer.key())
+            || holder_ata.amount() != 1
+        {
+            return Err(TimeBaseVaultError::Unauthorized.into());
+        }
+    }
+
+    pinocchio_token::instructions::Burn {
+        account: receipt.holder_ata,
+        mint: receipt.receipt_mint,
+        authority: signer,
+        amount: 1,
+    }
+    .invoke()?;
+
+    pinocchio_token::instructions::CloseAccount {
+        account: receipt.holder_ata,
+        destination: signer,
+        authority: signer,
+    }
+    .invoke()
+}
+
+/// Fails for vaults created with a receipt token, which only its holder can
+/// move.
+pub fn check_no_receipt(vault: &Vault) -> ProgramResult {
+    if vault.receipt_mint.is_some() {
+        return Err(TimeBaseVaultError::ReceiptRequired.into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,451 @@
+use core::mem::transmute;
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_token::state::Mint;
+
+use crate::{
+    errors::TimeBaseVaultError,
+    events::{check_event_accounts, Event, VaultCreated, VaultSplit},
+    instructions::check_no_receipt,
+    states::{Config, MintConfig, UserRegistry, Vault},
+    utils::{load_acc_mut_unchecked, load_acc_unchecked, DataLen},
+};
+
+/// The accounts only SPL vaults need.
+pub struct SplitVaultTokenAccounts<'info> {
+    pub mint: &'info AccountInfo,
+    pub vault_ata: &'info AccountInfo,
+    pub new_vault_ata: &'info AccountInfo,
+    pub token_program: &'info AccountInfo,
+    pub associated_token_program: &'info AccountInfo,
+    pub mint_config: &'info AccountInfo,
+}
+
+pub struct SplitVaultAccounts<'info> {
+    pub signer: &'info AccountInfo,
+    pub vault: &'info AccountInfo,
+    pub new_vault: &'info AccountInfo,
+    pub new_owner: &'info AccountInfo,
+    pub registry: &'info AccountInfo,
+    pub new_registry: &'info AccountInfo,
+    pub system_program: &'info AccountInfo,
+    pub config: &'info AccountInfo,
+    pub event_authority: &'info AccountInfo,
+    pub token_accounts: Option<SplitVaultTokenAccounts<'info>>,
+}
+
+impl<'info> TryFrom<&'info [AccountInfo]> for SplitVaultAccounts<'info> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'info [AccountInfo]) -> Result<Self, Self::Error> {
+        let [signer, vault, new_vault, new_owner, registry, new_registry, system_program, config, event_authority, program, token_accounts @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !signer.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // verify vault accounts
+        if !vault.is_writable() || !new_vault.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !vault.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if !new_vault.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // verify registry account, the new owner's registry is created on
+        // first use
+        if !registry.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !registry.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        // verify config account
+        Config::check(config)?;
+
+        // verify event accounts
+        check_event_accounts(event_authority, program)?;
+
+        let token_accounts = match token_accounts {
+            [] => None,
+            [mint, vault_ata, new_vault_ata, token_program, associated_token_program, mint_config] => {
+                Some(SplitVaultTokenAccounts {
+                    mint,
+                    vault_ata,
+                    new_vault_ata,
+                    token_program,
+                    associated_token_program,
+                    mint_config,
+                })
+            }
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+
+        Ok(SplitVaultAccounts {
+            signer,
+            vault,
+            new_vault,
+            new_owner,
+            registry,
+            new_registry,
+            system_program,
+            config,
+            event_authority,
+            token_accounts,
+        })
+    }
+}
+
+#[repr(C, packed)]
+pub struct SplitVaultInstructionData {
+    pub amount: u64,
+    pub unlock_timestamp: i64,
+    pub bump: u8,
+    pub new_registry_bump: u8,
+    /// The bump of the mint config PDA, ignored for SOL vaults
+    pub mint_config_bump: u8,
+}
+
+impl DataLen for SplitVaultInstructionData {
+    const LEN: usize = core::mem::size_of::<SplitVaultInstructionData>();
+}
+
+impl<'info> TryFrom<&'info [u8]> for SplitVaultInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'info [u8]) -> Result<Self, Self::Error> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(unsafe {
+            transmute::<[u8; Self::LEN], Self>(
+                TryInto::<[u8; Self::LEN]>::try_into(data)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?,
+            )
+        })
+    }
+}
+
+pub struct SplitVault<'info> {
+    pub accounts: SplitVaultAccounts<'info>,
+    pub instruction_data: SplitVaultInstructionData,
+}
+
+impl<'info> TryFrom<(&'info [u8], &'info [AccountInfo])> for SplitVault<'info> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (data, accounts): (&'info [u8], &'info [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = SplitVaultAccounts::try_from(accounts)?;
+        let instruction_data = SplitVaultInstructionData::try_from(data)?;
+
+        Ok(SplitVault {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+#[cfg(feature = "idl")]
+impl SplitVault<'_> {
+    pub const IDL: crate::idl::IdlInstruction = {
+        use crate::idl::{
+            idl_fields, IdlAccountItem, IdlInstruction, IdlType, EVENT_AUTHORITY_ACCOUNT,
+            PROGRAM_ACCOUNT,
+        };
+
+        IdlInstruction {
+            name: "SplitVault",
+            docs: "Moves part of a vault into a new vault of the same mint, unlocking at the same time or later. The new vault is bound by the pause and the mint list like any new vault and, when it unlocks later, by the lock duration bounds, but no creation fee is charged as the tokens were already charged when locked. SPL vaults append mint, vault_ata, new_vault_ata, token_program, associated_token_program and mint_config to the accounts",
+            discriminator: *Self::DISCRIMINATOR,
+            accounts: &[
+                IdlAccountItem::writable_signer("signer", "The vault owner"),
+                IdlAccountItem::writable("vault", "The vault to split"),
+                IdlAccountItem::writable("new_vault", "The vault PDA account to be created"),
+                IdlAccountItem::readonly("new_owner", "The owner of the new vault"),
+                IdlAccountItem::writable("registry", "The signer's user registry PDA"),
+                IdlAccountItem::writable(
+                    "new_registry",
+                    "The new owner's user registry PDA (created on first use)",
+                ),
+                IdlAccountItem::readonly("system_program", "The Solana System Program"),
+                IdlAccountItem::readonly("config", "The program config PDA"),
+                EVENT_AUTHORITY_ACCOUNT,
+                PROGRAM_ACCOUNT,
+            ],
+            args: idl_fields!(SplitVaultInstructionData {
+                amount: IdlType::U64,
+                unlock_timestamp: IdlType::I64,
+                bump: IdlType::U8,
+                new_registry_bump: IdlType::U8,
+                mint_config_bump: IdlType::U8,
+            }),
+        }
+    };
+}
+
+impl<'info> SplitVault<'info> {
+    pub const DISCRIMINATOR: &'info u8 = &19;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let current_timestamp = Clock::get()?.unix_timestamp;
+        let amount = self.instruction_data.amount;
+        let unlock_timestamp = self.instruction_data.unlock_timestamp;
+        if amount.eq(&0) {
+            return Err(TimeBaseVaultError::AmountMustBeGreaterThanZero.into());
+        }
+
+        let (
+            mint,
+            remaining,
+            seed_amount,
+            vault_unlock_timestamp,
+            vault_bump,
+            penalty_bps,
+            tracked,
+        ) = {
+            let mut data = self.accounts.vault.try_borrow_mut_data()?;
+            let vault = unsafe { load_acc_mut_unchecked::<Vault>(&mut data) }?;
+            if vault.owner.ne(self.accounts.signer.key()) {
+                return Err(TimeBaseVaultError::Unauthorized.into());
+            }
+            check_no_receipt(vault)?;
+            if unlock_timestamp.lt(&i64::from_le_bytes(vault.unlock_timestamp)) {
+                return Err(TimeBaseVaultError::UnlockTimestampTooEarly.into());
+            }
+            let remaining = u64::from_le_bytes(vault.amount)
+                .checked_sub(amount)
+                .filter(|remaining| *remaining > 0)
+                .ok_or(TimeBaseVaultError::InvalidSplitAmount)?;
+            vault.amount = remaining.to_le_bytes();
+            vault.withdrawn = u64::from_le_bytes(vault.withdrawn)
+                .checked_add(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?
+                .to_le_bytes();
+
+            (
+                vault.mint,
+                remaining,
+                vault.seed_amount,
+                vault.unlock_timestamp,
+                vault.bump,
+                vault.early_exit_penalty_bps,
+                vault.is_tracked(),
+            )
+        };
+
+        match (&mint, &self.accounts.token_accounts) {
+            (Some(mint), Some(token_accounts)) if mint.eq(token_accounts.mint.key()) => {}
+            (None, None) => {}
+            _ => return Err(TimeBaseVaultError::InvalidVaultMint.into()),
+        }
+
+        // the new vault is bound like any new vault, without a creation fee
+        // as the split tokens were charged when they were locked. A new vault
+        // unlocking with the vault keeps its lock, which was within the
+        // bounds when it was created, so only later unlocks are bound
+        let extended = unlock_timestamp.gt(&i64::from_le_bytes(vault_unlock_timestamp));
+        {
+            let data = self.accounts.config.try_borrow_data()?;
+            let config = unsafe { load_acc_unchecked::<Config>(&data) }?;
+            config.check_not_paused()?;
+            match &self.accounts.token_accounts {
+                Some(token_accounts) => {
+                    MintConfig::check(
+                        token_accounts.mint_config,
+                        token_accounts.mint.key(),
+                        self.instruction_data.mint_config_bump,
+                    )?;
+                    let mint_config_data = token_accounts.mint_config.try_borrow_data()?;
+                    let mint_config = if mint_config_data.is_empty() {
+                        None
+                    } else {
+                        Some(unsafe { load_acc_unchecked::<MintConfig>(&mint_config_data) }?)
+                    };
+                    config.check_mint_listed(mint_config)?;
+                    if extended {
+                        config.validate_lock_duration(
+                            mint_config,
+                            current_timestamp,
+                            unlock_timestamp,
+                        )?;
+                    }
+                }
+                None if extended => {
+                    config.validate_lock_duration(None, current_timestamp, unlock_timestamp)?
+                }
+                None => {}
+            }
+        }
+
+        Vault::validate_pda(
+            self.accounts.new_vault.key(),
+            self.accounts.new_owner.key(),
+            amount,
+            unlock_timestamp,
+            self.instruction_data.bump,
+            mint,
+        )?;
+
+        {
+            let mut data = self.accounts.registry.try_borrow_mut_data()?;
+            let registry = unsafe { load_acc_mut_unchecked::<UserRegistry>(&mut data) }?;
+            if registry.owner.ne(self.accounts.signer.key()) {
+                return Err(TimeBaseVaultError::Unauthorized.into());
+            }
+            if tracked {
+                registry.record_release(mint, amount)?;
+            }
+        }
+
+        UserRegistry::initialize_if_needed(
+            self.accounts.signer,
+            self.accounts.new_owner.key(),
+            self.accounts.new_registry,
+            self.instruction_data.new_registry_bump,
+        )?;
+        // a vault handed to someone else is left out of their mint totals,
+        // so splits can not fill the registry of another owner
+        let new_tracked = self.accounts.new_owner.key().eq(self.accounts.signer.key());
+        let id = {
+            let mut data = self.accounts.new_registry.try_borrow_mut_data()?;
+            let registry = unsafe { load_acc_mut_unchecked::<UserRegistry>(&mut data) }?;
+            if new_tracked {
+                registry.record_deposit(mint, amount)?
+            } else {
+                registry.record_untracked_deposit()?
+            }
+        };
+
+        {
+            // create and init new vault account
+            let bump_binding = [self.instruction_data.bump];
+            let amount_bytes = amount.to_le_bytes();
+            let unlock_timestamp_bytes = unlock_timestamp.to_le_bytes();
+            let create_account = pinocchio_system::instructions::CreateAccount {
+                from: self.accounts.signer,
+                to: self.accounts.new_vault,
+                space: Vault::LEN as u64,
+                lamports: Rent::get()?.minimum_balance(Vault::LEN),
+                owner: &crate::ID,
+            };
+            match &mint {
+                Some(mint) => {
+                    let seed = [
+                        Seed::from(Vault::SEED),
+                        Seed::from(self.accounts.new_owner.key()),
+                        Seed::from(mint),
+                        Seed::from(&amount_bytes),
+                        Seed::from(&unlock_timestamp_bytes),
+                        Seed::from(&bump_binding),
+                    ];
+                    create_account.invoke_signed(&[Signer::from(&seed)])?;
+                }
+                None => {
+                    let seed = [
+                        Seed::from(Vault::SEED),
+                        Seed::from(self.accounts.new_owner.key()),
+                        Seed::from(&amount_bytes),
+                        Seed::from(&unlock_timestamp_bytes),
+                        Seed::from(&bump_binding),
+                    ];
+                    create_account.invoke_signed(&[Signer::from(&seed)])?;
+                }
+            }
+
+            let mut data = self.accounts.new_vault.try_borrow_mut_data()?;
+            let vault = unsafe { load_acc_mut_unchecked::<Vault>(&mut data) }?;
+
+            vault.owner = *self.accounts.new_owner.key();
+            vault.id = id.to_le_bytes();
+            vault.mint = mint;
+            vault.unlock_timestamp = unlock_timestamp_bytes;
+            vault.amount = amount_bytes;
+            vault.seed_amount = amount_bytes;
+            vault.receipt_mint = None;
+            vault.bump = bump_binding;
+            vault.early_exit_penalty_bps = penalty_bps;
+            vault.tracked = [new_tracked as u8];
+            vault.withdrawn = 0u64.to_le_bytes();
+        }
+
+        match &self.accounts.token_accounts {
+            Some(token_accounts) => {
+                // create associated token account for new vault
+                pinocchio_associated_token_account::instructions::Create {
+                    account: token_accounts.new_vault_ata,
+                    mint: token_accounts.mint,
+                    funding_account: self.accounts.signer,
+                    system_program: self.accounts.system_program,
+                    token_program: token_accounts.token_program,
+                    wallet: self.accounts.new_vault,
+                }
+                .invoke()?;
+
+                let seed = [
+                    Seed::from(Vault::SEED),
+                    Seed::from(self.accounts.signer.key()),
+                    Seed::from(token_accounts.mint.key()),
+                    Seed::from(&seed_amount),
+                    Seed::from(&vault_unlock_timestamp),
+                    Seed::from(&vault_bump),
+                ];
+                let signer_seeds = Signer::from(&seed);
+
+                // transfer spl token to new vault
+                pinocchio_token::instructions::TransferChecked {
+                    mint: token_accounts.mint,
+                    from: token_accounts.vault_ata,
+                    to: token_accounts.new_vault_ata,
+                    amount,
+                    authority: self.accounts.vault,
+                    decimals: Mint::from_account_info(token_accounts.mint)?.decimals(),
+                }
+                .invoke_signed(&[signer_seeds])?;
+            }
+            None => {
+                // both vaults are owned by the program, so the lamports move
+                // without a CPI
+                *self.accounts.vault.try_borrow_mut_lamports()? -= amount;
+                *self.accounts.new_vault.try_borrow_mut_lamports()? += amount;
+            }
+        }
+
+        VaultCreated {
+            vault: *self.accounts.new_vault.key(),
+            owner: *self.accounts.new_owner.key(),
+            mint,
+            id: id.to_le_bytes(),
+            amount: amount.to_le_bytes(),
+            unlock_timestamp: unlock_timestamp.to_le_bytes(),
+            timestamp: current_timestamp.to_le_bytes(),
+        }
+        .emit(self.accounts.event_authority)?;
+
+        VaultSplit {
+            vault: *self.accounts.vault.key(),
+            new_vault: *self.accounts.new_vault.key(),
+            mint,
+            amount: amount.to_le_bytes(),
+            remaining: remaining.to_le_bytes(),
+            timestamp: current_timestamp.to_le_bytes(),
+        }
+        .emit(self.accounts.event_authority)
+    }
+}
@@ -1,16 +1,31 @@
 #![no_std]
 #![allow(unexpected_cfgs)]
-use pinocchio::{no_allocator, nostd_panic_handler, program_entrypoint};
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(feature = "cpi")]
+pub mod cpi;
 pub mod errors;
+pub mod events;
+#[cfg(feature = "idl")]
+pub mod idl;
 pub mod instructions;
 pub mod processor;
 pub mod states;
 pub mod utils;
 
-use processor::process_instruction;
-
 pinocchio_pubkey::declare_id!("Ac9JwB8Wc4JB7WwNkVSAY1SESxNmLw5rxuh1okLjQpX");
 
-program_entrypoint!(process_instruction);
-no_allocator!();
-nostd_panic_handler!();
+#[cfg(feature = "entrypoint")]
+mod entrypoint {
+    use pinocchio::{no_allocator, nostd_panic_handler, program_entrypoint};
+
+    use crate::processor::process_instruction;
+
+    program_entrypoint!(process_instruction);
+    no_allocator!();
+    nostd_panic_handler!();
+}
@@ -3,7 +3,11 @@ use pinocchio::{
 };
 
 use crate::instructions::{
-    InitializeSolVault, InitializeSplVault, WithdrawSolVault, WithdrawSplVault,
+    AddMintToList, BatchInitializeSplVault, BatchWithdraw, ClaimMerkleVault, DepositLockedBucket,
+    EarlyWithdrawSolVault, EarlyWithdrawSplVault, EmitEvent, GetVaultStatus, GetVotingPower,
+    InitializeConfig, InitializeMerkleVault, InitializeSolVault, InitializeSplVault, MergeVaults,
+    ReclaimMerkleVault, RedeemLockedBucket, RemoveMintFromList, SetMintConfig, SetMintListMode,
+    SetPaused, SplitVault, TransferAdmin, UpdateConfig, WithdrawSolVault, WithdrawSplVault,
 };
 
 pub fn process_instruction(
@@ -24,6 +28,68 @@ pub fn process_instruction(
         Some((WithdrawSplVault::DISCRIMINATOR, data)) => {
             WithdrawSplVault::try_from((data, accounts))?.process()
         }
+        Some((InitializeConfig::DISCRIMINATOR, data)) => {
+            InitializeConfig::try_from((data, accounts))?.process()
+        }
+        Some((UpdateConfig::DISCRIMINATOR, data)) => {
+            UpdateConfig::try_from((data, accounts))?.process()
+        }
+        Some((TransferAdmin::DISCRIMINATOR, data)) => {
+            TransferAdmin::try_from((data, accounts))?.process()
+        }
+        Some((SetPaused::DISCRIMINATOR, data)) => SetPaused::try_from((data, accounts))?.process(),
+        Some((EarlyWithdrawSolVault::DISCRIMINATOR, data)) => {
+            EarlyWithdrawSolVault::try_from((data, accounts))?.process()
+        }
+        Some((EarlyWithdrawSplVault::DISCRIMINATOR, data)) => {
+            EarlyWithdrawSplVault::try_from((data, accounts))?.process()
+        }
+        Some((SetMintConfig::DISCRIMINATOR, data)) => {
+            SetMintConfig::try_from((data, accounts))?.process()
+        }
+        Some((SetMintListMode::DISCRIMINATOR, data)) => {
+            SetMintListMode::try_from((data, accounts))?.process()
+        }
+        Some((AddMintToList::DISCRIMINATOR, data)) => {
+            AddMintToList::try_from((data, accounts))?.process()
+        }
+        Some((RemoveMintFromList::DISCRIMINATOR, data)) => {
+            RemoveMintFromList::try_from((data, accounts))?.process()
+        }
+        Some((BatchInitializeSplVault::DISCRIMINATOR, data)) => {
+            BatchInitializeSplVault::try_from((data, accounts))?.process()
+        }
+        Some((BatchWithdraw::DISCRIMINATOR, data)) => {
+            BatchWithdraw::try_from((data, accounts))?.process()
+        }
+        Some((InitializeMerkleVault::DISCRIMINATOR, data)) => {
+            InitializeMerkleVault::try_from((data, accounts))?.process()
+        }
+        Some((ClaimMerkleVault::DISCRIMINATOR, data)) => {
+            ClaimMerkleVault::try_from((data, accounts))?.process()
+        }
+        Some((SplitVault::DISCRIMINATOR, data)) => {
+            SplitVault::try_from((data, accounts))?.process()
+        }
+        Some((MergeVaults::DISCRIMINATOR, data)) => {
+            MergeVaults::try_from((data, accounts))?.process()
+        }
+        Some((DepositLockedBucket::DISCRIMINATOR, data)) => {
+            DepositLockedBucket::try_from((data, accounts))?.process()
+        }
+        Some((RedeemLockedBucket::DISCRIMINATOR, data)) => {
+            RedeemLockedBucket::try_from((data, accounts))?.process()
+        }
+        Some((GetVotingPower::DISCRIMINATOR, data)) => {
+            GetVotingPower::try_from((data, accounts))?.process()
+        }
+        Some((GetVaultStatus::DISCRIMINATOR, data)) => {
+            GetVaultStatus::try_from((data, accounts))?.process()
+        }
+        Some((ReclaimMerkleVault::DISCRIMINATOR, data)) => {
+            ReclaimMerkleVault::try_from((data, accounts))?.process()
+        }
+        Some((EmitEvent::DISCRIMINATOR, data)) => EmitEvent::try_from((data, accounts))?.process(),
         _ => Err(ProgramError::InvalidInstructionData),
     }
 }
@@ -0,0 +1,288 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+use pinocchio_token::state::TokenAccount;
+
+use crate::{
+    errors::TimeBaseVaultError,
+    states::MintConfig,
+    utils::{
+        bps_of, create_program_address, find_program_address, load_acc_unchecked, DataLen, MAX_BPS,
+    },
+};
+
+#[repr(C)]
+pub struct Config {
+    /// The admin allowed to update the config
+    pub admin: Pubkey,
+    /// The bump seed for the config
+    pub bump: [u8; 1],
+    /// Whether new vaults can be created (0 = active, 1 = paused)
+    pub paused: [u8; 1],
+    /// The minimum lock duration in seconds (0 = no minimum)
+    pub min_lock_duration: [u8; 8], // i64 as bytes
+    /// The maximum lock duration in seconds (0 = no maximum)
+    pub max_lock_duration: [u8; 8], // i64 as bytes
+    /// The fee charged on vault creation in basis points of the amount
+    pub fee_bps: [u8; 2], // u16 as bytes
+    /// The flat fee charged on vault creation in lamports
+    pub flat_fee: [u8; 8], // u64 as bytes
+    /// The account receiving the fees
+    pub treasury: Pubkey,
+    /// The account receiving the SOL penalties of early withdrawals
+    pub penalty_recipient: Pubkey,
+    /// Which mints SPL vaults accept, see [`MintListMode`]
+    pub mint_list_mode: [u8; 1],
+}
+
+/// How the `listed` flag of the mint configs restricts the mints of SPL vaults.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MintListMode {
+    /// Any mint is accepted
+    Open = 0,
+    /// Only listed mints are accepted
+    AllowList = 1,
+    /// Listed mints are rejected
+    DenyList = 2,
+}
+
+impl TryFrom<u8> for MintListMode {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(MintListMode::Open),
+            1 => Ok(MintListMode::AllowList),
+            2 => Ok(MintListMode::DenyList),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+impl DataLen for Config {
+    const LEN: usize = core::mem::size_of::<Config>();
+}
+
+#[cfg(feature = "idl")]
+impl Config {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "Config",
+            docs: "The program settings, at the PDA [\"config\"]",
+            len: Self::LEN,
+            fields: idl_fields!(Config {
+                admin: IdlType::PublicKey,
+                bump: IdlType::U8,
+                paused: IdlType::U8,
+                min_lock_duration: IdlType::I64,
+                max_lock_duration: IdlType::I64,
+                fee_bps: IdlType::U16,
+                flat_fee: IdlType::U64,
+                treasury: IdlType::PublicKey,
+                penalty_recipient: IdlType::PublicKey,
+                mint_list_mode: IdlType::U8,
+            }),
+        }
+    };
+}
+
+impl Config {
+    pub const SEED: &'static [u8] = b"config";
+
+    /// Returns the config PDA and its canonical bump.
+    pub fn find_address() -> (Pubkey, u8) {
+        find_program_address(&[Self::SEED])
+    }
+
+    /// Checks that `target` is the config PDA and `bump` its canonical bump,
+    /// so the program has a single config.
+    pub fn validate_pda(target: &Pubkey, bump: u8) -> Result<(), ProgramError> {
+        let (expected, canonical_bump) = Self::find_address();
+        if bump != canonical_bump {
+            return Err(TimeBaseVaultError::NonCanonicalBump.into());
+        }
+        if expected != *target {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    /// Verifies that `config` is the initialized config account of the program.
+    pub fn check(config: &AccountInfo) -> Result<(), ProgramError> {
+        if !config.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let data = config.try_borrow_data()?;
+        let state = unsafe { load_acc_unchecked::<Config>(&data) }?;
+        // the stored bump was checked to be canonical on initialization
+        if create_program_address(&[Self::SEED, &state.bump])? != *config.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the settings that can be changed by the admin.
+    pub fn validate_settings(
+        min_lock_duration: i64,
+        max_lock_duration: i64,
+        fee_bps: u16,
+    ) -> Result<(), ProgramError> {
+        if min_lock_duration < 0 || max_lock_duration < 0 {
+            return Err(TimeBaseVaultError::InvalidConfig.into());
+        }
+        if max_lock_duration != 0 && min_lock_duration > max_lock_duration {
+            return Err(TimeBaseVaultError::InvalidConfig.into());
+        }
+        if fee_bps > MAX_BPS {
+            return Err(TimeBaseVaultError::InvalidConfig.into());
+        }
+
+        Ok(())
+    }
+
+    /// Fails when the admin has paused the creation of new vaults.
+    pub fn check_not_paused(&self) -> Result<(), ProgramError> {
+        if self.paused[0] != 0 {
+            return Err(TimeBaseVaultError::ProgramPaused.into());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the fee charged on top of a vault of `amount`, split into the
+    /// basis-point part (in the unit of the vault) and the flat part (in lamports).
+    pub fn creation_fee(&self, amount: u64) -> Result<(u64, u64), ProgramError> {
+        let fee = bps_of(amount, u16::from_le_bytes(self.fee_bps))
+            .ok_or(TimeBaseVaultError::FeeOverflow)?;
+
+        Ok((fee, u64::from_le_bytes(self.flat_fee)))
+    }
+
+    pub fn check_treasury(&self, treasury: &AccountInfo) -> Result<(), ProgramError> {
+        if self.treasury.ne(treasury.key()) {
+            return Err(TimeBaseVaultError::InvalidTreasury.into());
+        }
+        if !treasury.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    /// Verifies that `treasury_ata` is a token account of the treasury for
+    /// `mint`, only called when a basis-point fee is charged so the account
+    /// can be any placeholder otherwise.
+    pub fn check_treasury_ata(
+        &self,
+        treasury_ata: &AccountInfo,
+        mint: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        let treasury_ata = TokenAccount::from_account_info(treasury_ata)?;
+        if treasury_ata.owner().ne(&self.treasury) || treasury_ata.mint().ne(mint) {
+            return Err(TimeBaseVaultError::InvalidTreasury.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn check_penalty_recipient(&self, recipient: &AccountInfo) -> Result<(), ProgramError> {
+        if self.penalty_recipient.ne(recipient.key()) {
+            return Err(TimeBaseVaultError::InvalidPenaltyRecipient.into());
+        }
+        if !recipient.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the minimum and maximum lock duration of the config, overridden
+    /// by the non-zero bounds of the mint config if any.
+    pub fn lock_duration_bounds(&self, mint_config: Option<&MintConfig>) -> (i64, i64) {
+        let mut min_lock_duration = i64::from_le_bytes(self.min_lock_duration);
+        let mut max_lock_duration = i64::from_le_bytes(self.max_lock_duration);
+        if let Some(mint_config) = mint_config {
+            let mint_min_lock_duration = i64::from_le_bytes(mint_config.min_lock_duration);
+            if mint_min_lock_duration != 0 {
+                min_lock_duration = mint_min_lock_duration;
+            }
+            let mint_max_lock_duration = i64::from_le_bytes(mint_config.max_lock_duration);
+            if mint_max_lock_duration != 0 {
+                max_lock_duration = mint_max_lock_duration;
+            }
+        }
+
+        (min_lock_duration, max_lock_duration)
+    }
+
+    /// Verifies the lock duration of a vault unlocking at `unlock_timestamp`
+    /// against the bounds of the config, overridden by the non-zero bounds of
+    /// the mint config if any.
+    pub fn validate_lock_duration(
+        &self,
+        mint_config: Option<&MintConfig>,
+        current_timestamp: i64,
+        unlock_timestamp: i64,
+    ) -> Result<(), ProgramError> {
+        let (min_lock_duration, max_lock_duration) = self.lock_duration_bounds(mint_config);
+
+        Self::check_lock_duration(
+            min_lock_duration,
+            max_lock_duration,
+            current_timestamp,
+            unlock_timestamp,
+        )
+    }
+
+    /// Verifies that a lock from `start_timestamp` to `unlock_timestamp` lasts
+    /// at least `min_lock_duration` and at most `max_lock_duration`, where a
+    /// maximum of 0 is not enforced.
+    pub fn check_lock_duration(
+        min_lock_duration: i64,
+        max_lock_duration: i64,
+        start_timestamp: i64,
+        unlock_timestamp: i64,
+    ) -> Result<(), ProgramError> {
+        let lock_duration = unlock_timestamp
+            .checked_sub(start_timestamp)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if lock_duration < min_lock_duration {
+            return Err(TimeBaseVaultError::LockDurationTooShort.into());
+        }
+        if max_lock_duration != 0 && lock_duration > max_lock_duration {
+            return Err(TimeBaseVaultError::LockDurationTooLong.into());
+        }
+
+        Ok(())
+    }
+
+    /// Verifies that SPL vaults can be created for the mint of `mint_config`,
+    /// where `None` is an unlisted mint.
+    pub fn check_mint_listed(&self, mint_config: Option<&MintConfig>) -> Result<(), ProgramError> {
+        let listed = mint_config.is_some_and(MintConfig::is_listed);
+        let accepted = match MintListMode::try_from(self.mint_list_mode[0])? {
+            MintListMode::Open => true,
+            MintListMode::AllowList => listed,
+            MintListMode::DenyList => !listed,
+        };
+        if !accepted {
+            return Err(TimeBaseVaultError::InvalidVaultMint.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn check_admin(&self, signer: &AccountInfo) -> Result<(), ProgramError> {
+        if !signer.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if self.admin.ne(signer.key()) {
+            return Err(TimeBaseVaultError::Unauthorized.into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    errors::TimeBaseVaultError,
+    utils::{find_program_address, DataLen, Discriminator},
+};
+
+#[repr(C)]
+pub struct LockedBucket {
+    /// The account type, [`LockedBucket::DISCRIMINATOR`]
+    pub discriminator: [u8; 1],
+    /// The mint of the pooled tokens
+    pub mint: Pubkey,
+    /// The timestamp from which receipts can be redeemed
+    pub unlock_timestamp: [u8; 8], // i64 as bytes
+    /// The mint of the receipts, minted 1:1 for deposits
+    pub receipt_mint: Pubkey,
+    /// The amount deposited and not redeemed yet
+    pub total: [u8; 8], // u64 as bytes
+    /// The bump seed for the bucket
+    pub bump: [u8; 1],
+}
+
+impl DataLen for LockedBucket {
+    const LEN: usize = core::mem::size_of::<LockedBucket>();
+}
+
+impl Discriminator for LockedBucket {
+    const DISCRIMINATOR: u8 = 1;
+}
+
+#[cfg(feature = "idl")]
+impl LockedBucket {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "LockedBucket",
+            docs: "The pooled deposits of a mint until an unlock timestamp, at the PDA [\"locked_bucket\", mint, unlock_timestamp]",
+            len: Self::LEN,
+            fields: idl_fields!(LockedBucket {
+                discriminator: IdlType::U8,
+                mint: IdlType::PublicKey,
+                unlock_timestamp: IdlType::I64,
+                receipt_mint: IdlType::PublicKey,
+                total: IdlType::U64,
+                bump: IdlType::U8,
+            }),
+        }
+    };
+}
+
+impl LockedBucket {
+    pub const SEED: &'static [u8] = b"locked_bucket";
+    pub const RECEIPT_SEED: &'static [u8] = b"locked_bucket_receipt";
+
+    /// Checks that `target` is the bucket PDA of `mint` and `unlock_timestamp`
+    /// and `bump` its canonical bump, so there is one bucket for the same
+    /// parameters.
+    pub fn validate_pda(
+        target: &Pubkey,
+        mint: &Pubkey,
+        unlock_timestamp: i64,
+        bump: u8,
+    ) -> Result<(), ProgramError> {
+        let (expected, canonical_bump) =
+            find_program_address(&[Self::SEED, mint.as_ref(), &unlock_timestamp.to_le_bytes()]);
+        if bump != canonical_bump {
+            return Err(TimeBaseVaultError::NonCanonicalBump.into());
+        }
+        if expected != *target {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that `target` is the receipt mint PDA of `bucket` and `bump` its
+    /// canonical bump.
+    pub fn validate_receipt_pda(
+        target: &Pubkey,
+        bucket: &Pubkey,
+        bump: u8,
+    ) -> Result<(), ProgramError> {
+        let (expected, canonical_bump) =
+            find_program_address(&[Self::RECEIPT_SEED, bucket.as_ref()]);
+        if bump != canonical_bump {
+            return Err(TimeBaseVaultError::NonCanonicalBump.into());
+        }
+        if expected != *target {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+use solana_sha256_hasher::hashv;
+
+use crate::{
+    errors::TimeBaseVaultError,
+    states::Config,
+    utils::{find_program_address, DataLen, Discriminator},
+};
+
+/// The prefixes of the leaf and node hashes, so a node can not be passed off
+/// as a leaf.
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+#[repr(C)]
+pub struct MerkleVault {
+    /// The account type, [`MerkleVault::DISCRIMINATOR`]
+    pub discriminator: [u8; 1],
+    /// The account that deposited the tokens
+    pub funder: Pubkey,
+    /// The mint of the deposited tokens
+    pub mint: Pubkey,
+    /// The root of the `(recipient, amount, unlock_timestamp)` leaves
+    pub root: [u8; 32],
+    /// The amount deposited
+    pub total: [u8; 8], // u64 as bytes
+    /// The amount claimed so far
+    pub claimed: [u8; 8], // u64 as bytes
+    /// The timestamp of the creation, the lock durations of the leaves start
+    /// from it
+    pub created_at: [u8; 8], // i64 as bytes
+    /// The minimum lock duration of a leaf, set from the config on creation
+    pub min_lock_duration: [u8; 8], // i64 as bytes
+    /// The maximum lock duration of a leaf (0 = no maximum)
+    pub max_lock_duration: [u8; 8], // i64 as bytes
+    /// The timestamp from which the funder can take back the unclaimed amount
+    /// (0 = never)
+    pub reclaim_timestamp: [u8; 8], // i64 as bytes
+    /// The bump seed for the merkle vault
+    pub bump: [u8; 1],
+}
+
+impl DataLen for MerkleVault {
+    const LEN: usize = core::mem::size_of::<MerkleVault>();
+}
+
+impl Discriminator for MerkleVault {
+    const DISCRIMINATOR: u8 = 2;
+}
+
+#[cfg(feature = "idl")]
+impl MerkleVault {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "MerkleVault",
+            docs: "Tokens locked for the leaves of a merkle root, at the PDA [\"merkle_vault\", funder, mint, root]",
+            len: Self::LEN,
+            fields: idl_fields!(MerkleVault {
+                discriminator: IdlType::U8,
+                funder: IdlType::PublicKey,
+                mint: IdlType::PublicKey,
+                root: IdlType::Array(&IdlType::U8, 32),
+                total: IdlType::U64,
+                claimed: IdlType::U64,
+                created_at: IdlType::I64,
+                min_lock_duration: IdlType::I64,
+                max_lock_duration: IdlType::I64,
+                reclaim_timestamp: IdlType::I64,
+                bump: IdlType::U8,
+            }),
+        }
+    };
+}
+
+impl MerkleVault {
+    pub const SEED: &'static [u8] = b"merkle_vault";
+    /// The maximum number of hashes in a proof, enough for 2^32 leaves.
+    pub const MAX_PROOF_LEN: usize = 32;
+    /// The time every recipient has to claim its leaf after the latest
+    /// unlock timestamp allowed, before the funder can reclaim it: 90 days.
+    pub const CLAIM_WINDOW: i64 = 90 * 86_400;
+
+    /// Returns the merkle vault PDA of `funder`, `mint` and `root` and its
+    /// canonical bump.
+    pub fn find_address(funder: &Pubkey, mint: &Pubkey, root: &[u8; 32]) -> (Pubkey, u8) {
+        find_program_address(&[Self::SEED, funder, mint, root])
+    }
+
+    /// Checks that `target` is the merkle vault PDA of `funder`, `mint` and
+    /// `root` and `bump` its canonical bump, so there is one merkle vault per
+    /// funder, mint and root.
+    pub fn validate_pda(
+        target: &Pubkey,
+        funder: &Pubkey,
+        mint: &Pubkey,
+        root: &[u8; 32],
+        bump: u8,
+    ) -> Result<(), ProgramError> {
+        let (expected, canonical_bump) = Self::find_address(funder, mint, root);
+        if bump != canonical_bump {
+            return Err(TimeBaseVaultError::NonCanonicalBump.into());
+        }
+        if expected != *target {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the lock duration of a leaf unlocking at `unlock_timestamp`
+    /// against the bounds recorded on creation.
+    pub fn validate_lock_duration(&self, unlock_timestamp: i64) -> Result<(), ProgramError> {
+        Config::check_lock_duration(
+            i64::from_le_bytes(self.min_lock_duration),
+            i64::from_le_bytes(self.max_lock_duration),
+            i64::from_le_bytes(self.created_at),
+            unlock_timestamp,
+        )
+    }
+
+    /// Verifies that a merkle vault created at `created_at` with a maximum
+    /// lock duration of `max_lock_duration` can be reclaimed from
+    /// `reclaim_timestamp`. Reclaim can be disabled with a zero timestamp,
+    /// otherwise it must leave [`Self::CLAIM_WINDOW`] after the latest unlock
+    /// timestamp a leaf can have, so there must be a maximum lock duration.
+    pub fn check_reclaim_timestamp(
+        created_at: i64,
+        max_lock_duration: i64,
+        reclaim_timestamp: i64,
+    ) -> Result<(), ProgramError> {
+        if reclaim_timestamp.eq(&0) {
+            return Ok(());
+        }
+        if max_lock_duration.le(&0) {
+            return Err(TimeBaseVaultError::InvalidReclaimTimestamp.into());
+        }
+
+        let earliest = created_at
+            .checked_add(max_lock_duration)
+            .and_then(|timestamp| timestamp.checked_add(Self::CLAIM_WINDOW))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if reclaim_timestamp.lt(&earliest) {
+            return Err(TimeBaseVaultError::InvalidReclaimTimestamp.into());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the amount deposited and not claimed yet.
+    pub fn unclaimed(&self) -> Result<u64, ProgramError> {
+        u64::from_le_bytes(self.total)
+            .checked_sub(u64::from_le_bytes(self.claimed))
+            .ok_or(ProgramError::ArithmeticOverflow)
+    }
+
+    /// Returns the hash of the leaf granting `amount` to `recipient` from
+    /// `unlock_timestamp`.
+    pub fn leaf_hash(recipient: &Pubkey, amount: u64, unlock_timestamp: i64) -> [u8; 32] {
+        hashv(&[
+            LEAF_PREFIX,
+            recipient.as_ref(),
+            &amount.to_le_bytes(),
+            &unlock_timestamp.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    /// Returns the hash of the parent of two nodes. The pair is sorted, so a
+    /// proof does not need to tell on which side each sibling is.
+    pub fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+        hashv(&[NODE_PREFIX, left, right]).to_bytes()
+    }
+
+    /// Verifies that `leaf` is in the tree of `root`, `proof` being the
+    /// sibling hashes from the leaf up.
+    pub fn verify_proof(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+        proof
+            .iter()
+            .fold(leaf, |node, sibling| Self::node_hash(&node, sibling))
+            .eq(root)
+    }
+}
+
+#[repr(C)]
+pub struct ClaimReceipt {
+    /// The account type, [`ClaimReceipt::DISCRIMINATOR`]
+    pub discriminator: [u8; 1],
+    /// The merkle vault the leaf was claimed from
+    pub merkle_vault: Pubkey,
+    /// The recipient of the leaf
+    pub recipient: Pubkey,
+    /// The amount claimed
+    pub amount: [u8; 8], // u64 as bytes
+    /// The timestamp of the claim
+    pub claimed_at: [u8; 8], // i64 as bytes
+    /// The bump seed for the receipt
+    pub bump: [u8; 1],
+}
+
+impl DataLen for ClaimReceipt {
+    const LEN: usize = core::mem::size_of::<ClaimReceipt>();
+}
+
+impl Discriminator for ClaimReceipt {
+    const DISCRIMINATOR: u8 = 3;
+}
+
+#[cfg(feature = "idl")]
+impl ClaimReceipt {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "ClaimReceipt",
+            docs:
+                "The claim of a merkle vault leaf, at the PDA [\"claim\", merkle_vault, leaf_hash]",
+            len: Self::LEN,
+            fields: idl_fields!(ClaimReceipt {
+                discriminator: IdlType::U8,
+                merkle_vault: IdlType::PublicKey,
+                recipient: IdlType::PublicKey,
+                amount: IdlType::U64,
+                claimed_at: IdlType::I64,
+                bump: IdlType::U8,
+            }),
+        }
+    };
+}
+
+impl ClaimReceipt {
+    pub const SEED: &'static [u8] = b"claim";
+
+    /// Returns the claim receipt PDA of the leaf hashed to `leaf` and its
+    /// canonical bump.
+    pub fn find_address(merkle_vault: &Pubkey, leaf: &[u8; 32]) -> (Pubkey, u8) {
+        find_program_address(&[Self::SEED, merkle_vault, leaf])
+    }
+
+    /// Checks that `target` is the claim receipt PDA of the leaf and `bump`
+    /// its canonical bump, as a receipt at another bump would let the leaf be
+    /// claimed again.
+    pub fn validate_pda(
+        target: &Pubkey,
+        merkle_vault: &Pubkey,
+        leaf: &[u8; 32],
+        bump: u8,
+    ) -> Result<(), ProgramError> {
+        let (expected, canonical_bump) = Self::find_address(merkle_vault, leaf);
+        if bump != canonical_bump {
+            return Err(TimeBaseVaultError::NonCanonicalBump.into());
+        }
+        if expected != *target {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    errors::TimeBaseVaultError,
+    utils::{
+        create_pda_account, find_program_address, load_acc_mut_unchecked, load_acc_unchecked,
+        DataLen,
+    },
+};
+
+#[repr(C)]
+pub struct MintConfig {
+    /// The mint the settings apply to
+    pub mint: Pubkey,
+    /// The bump seed for the mint config
+    pub bump: [u8; 1],
+    /// The minimum lock duration in seconds (0 = use the program config)
+    pub min_lock_duration: [u8; 8], // i64 as bytes
+    /// The maximum lock duration in seconds (0 = use the program config)
+    pub max_lock_duration: [u8; 8], // i64 as bytes
+    /// Whether the mint is on the config's mint list (0 = unlisted, 1 = listed)
+    pub listed: [u8; 1],
+}
+
+impl DataLen for MintConfig {
+    const LEN: usize = core::mem::size_of::<MintConfig>();
+}
+
+#[cfg(feature = "idl")]
+impl MintConfig {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "MintConfig",
+            docs: "The settings of a mint, at the PDA [\"mint_config\", mint]",
+            len: Self::LEN,
+            fields: idl_fields!(MintConfig {
+                mint: IdlType::PublicKey,
+                bump: IdlType::U8,
+                min_lock_duration: IdlType::I64,
+                max_lock_duration: IdlType::I64,
+                listed: IdlType::U8,
+            }),
+        }
+    };
+}
+
+impl MintConfig {
+    pub const SEED: &'static [u8] = b"mint_config";
+
+    /// Returns the mint config PDA of `mint` and its canonical bump.
+    pub fn find_address(mint: &Pubkey) -> (Pubkey, u8) {
+        find_program_address(&[Self::SEED, mint])
+    }
+
+    /// Checks that `target` is the mint config PDA of `mint` and `bump` its
+    /// canonical bump. An uninitialized mint config reads as an unlisted mint
+    /// without bounds, so another bump would skip the settings of the mint.
+    pub fn validate_pda(target: &Pubkey, mint: &Pubkey, bump: u8) -> Result<(), ProgramError> {
+        let (expected, canonical_bump) = Self::find_address(mint);
+        if bump != canonical_bump {
+            return Err(TimeBaseVaultError::NonCanonicalBump.into());
+        }
+        if expected != *target {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    /// Verifies that `mint_config` is the mint config PDA of `mint`, which
+    /// may not be initialized yet.
+    pub fn check(mint_config: &AccountInfo, mint: &Pubkey, bump: u8) -> Result<(), ProgramError> {
+        Self::validate_pda(mint_config.key(), mint, bump)?;
+
+        if !mint_config.data_is_empty() {
+            if !mint_config.is_owned_by(&crate::ID) {
+                return Err(ProgramError::InvalidAccountOwner);
+            }
+            let data = mint_config.try_borrow_data()?;
+            unsafe { load_acc_unchecked::<MintConfig>(&data) }?;
+        }
+
+        Ok(())
+    }
+
+    /// Creates the mint config PDA of `mint` if it does not exist yet.
+    pub fn initialize_if_needed(
+        payer: &AccountInfo,
+        mint: &Pubkey,
+        mint_config: &AccountInfo,
+        bump: u8,
+    ) -> ProgramResult {
+        Self::check(mint_config, mint, bump)?;
+        if !mint_config.data_is_empty() {
+            return Ok(());
+        }
+
+        let bump_binding = [bump];
+        let seed = [
+            Seed::from(Self::SEED),
+            Seed::from(mint),
+            Seed::from(&bump_binding),
+        ];
+        let signer_seeds = Signer::from(&seed);
+
+        create_pda_account(payer, mint_config, Self::LEN, &crate::ID, &[signer_seeds])?;
+
+        let mut data = mint_config.try_borrow_mut_data()?;
+        let mint_config = unsafe { load_acc_mut_unchecked::<MintConfig>(&mut data) }?;
+        mint_config.mint = *mint;
+        mint_config.bump = [bump];
+
+        Ok(())
+    }
+
+    pub fn is_listed(&self) -> bool {
+        self.listed[0] == 1
+    }
+}
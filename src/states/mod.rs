@@ -1,2 +1,12 @@
+pub mod config;
+pub mod locked_bucket;
+pub mod merkle_vault;
+pub mod mint_config;
+pub mod user_registry;
 pub mod vault;
+pub use config::*;
+pub use locked_bucket::*;
+pub use merkle_vault::*;
+pub use mint_config::*;
+pub use user_registry::*;
 pub use vault::*;
@@ -0,0 +1,231 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    ProgramResult,
+};
+
+use crate::{
+    errors::TimeBaseVaultError,
+    states::Vault,
+    utils::{
+        create_pda_account, find_program_address, load_acc_mut_unchecked, load_acc_unchecked,
+        DataLen,
+    },
+};
+
+#[repr(C)]
+pub struct MintTotal {
+    /// The mint address of the locked token (None for SOL)
+    pub mint: Option<Pubkey>,
+    /// The total amount currently locked for this mint
+    pub amount: [u8; 8], // u64 as bytes
+}
+
+#[cfg(feature = "idl")]
+impl MintTotal {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "MintTotal",
+            docs: "The total amount locked for a mint (None for SOL)",
+            len: core::mem::size_of::<MintTotal>(),
+            fields: idl_fields!(MintTotal {
+                mint: IdlType::Option(&IdlType::PublicKey),
+                amount: IdlType::U64,
+            }),
+        }
+    };
+}
+
+#[repr(C)]
+pub struct UserRegistry {
+    /// The owner of the registry
+    pub owner: Pubkey,
+    /// The bump seed for the registry
+    pub bump: [u8; 1],
+    /// The id assigned to the next vault created by the owner
+    pub vault_counter: [u8; 8], // u64 as bytes
+    /// The number of vaults of the owner that are not withdrawn yet
+    pub live_vaults: [u8; 8], // u64 as bytes
+    /// The total amount locked per mint, a slot with zero amount is free
+    pub mint_totals: [MintTotal; UserRegistry::MAX_MINTS],
+}
+
+impl DataLen for UserRegistry {
+    const LEN: usize = core::mem::size_of::<UserRegistry>();
+}
+
+#[cfg(feature = "idl")]
+impl UserRegistry {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "UserRegistry",
+            docs: "The vaults of an owner, at the PDA [\"registry\", owner]",
+            len: Self::LEN,
+            fields: idl_fields!(UserRegistry {
+                owner: IdlType::PublicKey,
+                bump: IdlType::U8,
+                vault_counter: IdlType::U64,
+                live_vaults: IdlType::U64,
+                mint_totals: IdlType::Array(
+                    &IdlType::Defined(&MintTotal::IDL),
+                    UserRegistry::MAX_MINTS
+                ),
+            }),
+        }
+    };
+}
+
+impl UserRegistry {
+    pub const SEED: &'static [u8] = b"registry";
+    pub const MAX_MINTS: usize = 16;
+
+    /// Returns the registry PDA of `owner` and its canonical bump.
+    pub fn find_address(owner: &Pubkey) -> (Pubkey, u8) {
+        find_program_address(&[Self::SEED, owner])
+    }
+
+    /// Checks that `target` is the registry PDA of `owner` and `bump` its
+    /// canonical bump, so an owner has a single registry.
+    pub fn validate_pda(target: &Pubkey, owner: &Pubkey, bump: u8) -> Result<(), ProgramError> {
+        let (expected, canonical_bump) = Self::find_address(owner);
+        if bump != canonical_bump {
+            return Err(TimeBaseVaultError::NonCanonicalBump.into());
+        }
+        if expected != *target {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    /// Creates the registry of `owner` if it does not exist yet, otherwise
+    /// verifies that the existing account is the registry of `owner`.
+    pub fn initialize_if_needed(
+        payer: &AccountInfo,
+        owner: &Pubkey,
+        registry: &AccountInfo,
+        bump: u8,
+    ) -> ProgramResult {
+        if !registry.is_writable() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Self::validate_pda(registry.key(), owner, bump)?;
+
+        if !registry.data_is_empty() {
+            if !registry.is_owned_by(&crate::ID) {
+                return Err(ProgramError::InvalidAccountOwner);
+            }
+            let data = registry.try_borrow_data()?;
+            let registry = unsafe { load_acc_unchecked::<UserRegistry>(&data) }?;
+            if registry.owner.ne(owner) {
+                return Err(TimeBaseVaultError::Unauthorized.into());
+            }
+            return Ok(());
+        }
+
+        let bump_binding = [bump];
+        let seed = [
+            Seed::from(Self::SEED),
+            Seed::from(owner),
+            Seed::from(&bump_binding),
+        ];
+        let signer_seeds = Signer::from(&seed);
+
+        create_pda_account(payer, registry, Self::LEN, &crate::ID, &[signer_seeds])?;
+
+        let mut data = registry.try_borrow_mut_data()?;
+        let registry = unsafe { load_acc_mut_unchecked::<UserRegistry>(&mut data) }?;
+        registry.owner = *owner;
+        registry.bump = [bump];
+
+        Ok(())
+    }
+
+    /// Records a new vault and returns the id assigned to it.
+    pub fn record_deposit(
+        &mut self,
+        mint: Option<Pubkey>,
+        amount: u64,
+    ) -> Result<u64, ProgramError> {
+        let slot = match self
+            .mint_totals
+            .iter()
+            .position(|total| total.mint == mint && u64::from_le_bytes(total.amount) > 0)
+        {
+            Some(index) => index,
+            None => self
+                .mint_totals
+                .iter()
+                .position(|total| u64::from_le_bytes(total.amount) == 0)
+                .ok_or(TimeBaseVaultError::RegistryFull)?,
+        };
+
+        let total = &mut self.mint_totals[slot];
+        let locked = u64::from_le_bytes(total.amount)
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        total.mint = mint;
+        total.amount = locked.to_le_bytes();
+
+        self.record_untracked_deposit()
+    }
+
+    /// Records a new vault whose amount is left out of the mint totals and
+    /// returns the id assigned to it. Vaults created for the owner by another
+    /// account are recorded this way, so they can not take the mint slots of
+    /// the owner.
+    pub fn record_untracked_deposit(&mut self) -> Result<u64, ProgramError> {
+        let id = u64::from_le_bytes(self.vault_counter);
+        self.vault_counter = id
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .to_le_bytes();
+        self.live_vaults = u64::from_le_bytes(self.live_vaults)
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .to_le_bytes();
+
+        Ok(id)
+    }
+
+    /// Records that `vault` has been withdrawn.
+    pub fn record_withdrawal(&mut self, vault: &Vault) -> ProgramResult {
+        if vault.is_tracked() {
+            self.record_release(vault.mint, u64::from_le_bytes(vault.amount))?;
+        }
+        self.record_close()
+    }
+
+    /// Records that a vault has been closed after its amount was recorded
+    /// elsewhere.
+    pub fn record_close(&mut self) -> ProgramResult {
+        self.live_vaults = u64::from_le_bytes(self.live_vaults)
+            .checked_sub(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .to_le_bytes();
+
+        Ok(())
+    }
+
+    /// Records that `amount` of `mint` has left a vault that stays open.
+    pub fn record_release(&mut self, mint: Option<Pubkey>, amount: u64) -> ProgramResult {
+        let total = self
+            .mint_totals
+            .iter_mut()
+            .find(|total| total.mint == mint && u64::from_le_bytes(total.amount) > 0)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        total.amount = u64::from_le_bytes(total.amount)
+            .checked_sub(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .to_le_bytes();
+
+        Ok(())
+    }
+}
@@ -1,9 +1,9 @@
-use pinocchio::{
-    program_error::ProgramError,
-    pubkey::{self, Pubkey},
-};
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
 
-use crate::utils::DataLen;
+use crate::{
+    errors::TimeBaseVaultError,
+    utils::{bps_of, create_program_address, find_program_address, DataLen, MAX_BPS},
+};
 
 #[repr(C)]
 pub struct Vault {
@@ -17,53 +17,212 @@ pub struct Vault {
     pub unlock_timestamp: [u8; 8], //i64 as bytes
     /// The mint address of the token in the vault (if applicable)
     pub mint: Option<Pubkey>,
+    /// The id of the vault in the owner's registry
+    pub id: [u8; 8], // u64 as bytes
+    /// The penalty in basis points for withdrawing before the unlock timestamp (0 = not allowed)
+    pub early_exit_penalty_bps: [u8; 2], // u16 as bytes
+    /// The amount the vault PDA is derived from, which `amount` departs from
+    /// once the vault is split
+    pub seed_amount: [u8; 8], // u64 as bytes
+    /// The mint of the receipt token whose holder withdraws the vault in place
+    /// of its owner (if applicable)
+    pub receipt_mint: Option<Pubkey>,
+    /// Whether `amount` is counted in the mint totals of the owner's registry
+    /// (0 for a vault created for the owner by another account)
+    pub tracked: [u8; 1],
+    /// The amount moved out of the vault by splits
+    pub withdrawn: [u8; 8], // u64 as bytes
 }
 
 impl DataLen for Vault {
     const LEN: usize = core::mem::size_of::<Vault>();
 }
 
+#[cfg(feature = "idl")]
+impl Vault {
+    pub const IDL: crate::idl::IdlStruct = {
+        use crate::idl::{idl_fields, IdlStruct, IdlType};
+
+        IdlStruct {
+            name: "Vault",
+            docs: "A time-locked vault, at the PDA [\"vault\", owner, mint?, seed_amount, unlock_timestamp]",
+            len: Self::LEN,
+            fields: idl_fields!(Vault {
+            owner: IdlType::PublicKey,
+            amount: IdlType::U64,
+            bump: IdlType::U8,
+            unlock_timestamp: IdlType::I64,
+            mint: IdlType::Option(&IdlType::PublicKey),
+            id: IdlType::U64,
+            early_exit_penalty_bps: IdlType::U16,
+            seed_amount: IdlType::U64,
+            receipt_mint: IdlType::Option(&IdlType::PublicKey),
+            tracked: IdlType::U8,
+            withdrawn: IdlType::U64,
+            }),
+        }
+    };
+}
+
 impl Vault {
     pub const SEED: &'static [u8] = b"vault";
+    pub const RECEIPT_SEED: &'static [u8] = b"receipt";
+
+    /// Calls `f` with the seeds of the vault PDA of `owner`, ending with
+    /// `bump`.
+    fn with_seeds<R>(
+        owner: &Pubkey,
+        mint: Option<&Pubkey>,
+        amount: u64,
+        unlock_timestamp: i64,
+        bump: u8,
+        f: impl FnOnce(&[&[u8]]) -> R,
+    ) -> R {
+        let amount_bytes = amount.to_le_bytes();
+        let unlock_timestamp_bytes = unlock_timestamp.to_le_bytes();
+        let bump = [bump];
+        match mint {
+            Some(mint) => f(&[
+                Self::SEED,
+                owner,
+                mint,
+                &amount_bytes,
+                &unlock_timestamp_bytes,
+                &bump,
+            ]),
+            None => f(&[
+                Self::SEED,
+                owner,
+                &amount_bytes,
+                &unlock_timestamp_bytes,
+                &bump,
+            ]),
+        }
+    }
+
+    /// Returns the vault PDA of `owner` and its canonical bump, `mint` is
+    /// `None` for SOL vaults.
+    pub fn find_address(
+        owner: &Pubkey,
+        mint: Option<&Pubkey>,
+        amount: u64,
+        unlock_timestamp: i64,
+    ) -> (Pubkey, u8) {
+        Self::with_seeds(owner, mint, amount, unlock_timestamp, 0, |seeds| {
+            find_program_address(&seeds[..seeds.len() - 1])
+        })
+    }
 
+    /// Returns the vault PDA of `owner` for `bump`, `mint` is `None` for SOL
+    /// vaults.
+    pub fn derive_address(
+        owner: &Pubkey,
+        mint: Option<&Pubkey>,
+        amount: u64,
+        unlock_timestamp: i64,
+        bump: u8,
+    ) -> Result<Pubkey, ProgramError> {
+        Self::with_seeds(
+            owner,
+            mint,
+            amount,
+            unlock_timestamp,
+            bump,
+            create_program_address,
+        )
+    }
+
+    /// Checks that `target` is the vault PDA of `owner` and `bump` its
+    /// canonical bump, so there is one vault address for the same parameters.
     pub fn validate_pda(
         target: &Pubkey,
-        signer: &Pubkey,
+        owner: &Pubkey,
         amount: u64,
         unlock_timestamp: i64,
         bump: u8,
         mint: Option<Pubkey>,
     ) -> Result<(), ProgramError> {
-        match mint {
-            Some(mint) => {
-                let seed_with_bump = &[
-                    Self::SEED,
-                    signer.as_ref(),
-                    mint.as_ref(),
-                    &amount.to_le_bytes(),
-                    &unlock_timestamp.to_le_bytes(),
-                    &[bump],
-                ];
-                let expected = pubkey::create_program_address(seed_with_bump, &crate::ID)?;
-                if expected != *target {
-                    return Err(ProgramError::InvalidAccountData);
-                }
-            }
-            None => {
-                let seed_with_bump = &[
-                    Self::SEED,
-                    signer.as_ref(),
-                    &amount.to_le_bytes(),
-                    &unlock_timestamp.to_le_bytes(),
-                    &[bump],
-                ];
-                let expected = pubkey::create_program_address(seed_with_bump, &crate::ID)?;
-                if expected != *target {
-                    return Err(ProgramError::InvalidAccountData);
-                }
-            }
+        let (expected, canonical_bump) =
+            Self::find_address(owner, mint.as_ref(), amount, unlock_timestamp);
+        if bump != canonical_bump {
+            return Err(TimeBaseVaultError::NonCanonicalBump.into());
+        }
+        if expected != *target {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that `key` is the PDA of the stored seeds and bump. The bump is
+    /// not required to be canonical, as vaults created before bumps had to
+    /// be canonical keep their address.
+    pub fn check_address(&self, key: &Pubkey) -> Result<(), ProgramError> {
+        let expected = Self::derive_address(
+            &self.owner,
+            self.mint.as_ref(),
+            u64::from_le_bytes(self.seed_amount),
+            i64::from_le_bytes(self.unlock_timestamp),
+            self.bump[0],
+        )?;
+        if expected.ne(key) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the receipt mint PDA of `vault` and its canonical bump.
+    pub fn find_receipt_address(vault: &Pubkey) -> (Pubkey, u8) {
+        find_program_address(&[Self::RECEIPT_SEED, vault])
+    }
+
+    /// Checks that `target` is the receipt mint PDA of `vault` and `bump` its
+    /// canonical bump, so a vault has a single receipt mint.
+    pub fn validate_receipt_pda(
+        target: &Pubkey,
+        vault: &Pubkey,
+        bump: u8,
+    ) -> Result<(), ProgramError> {
+        let (expected, canonical_bump) = Self::find_receipt_address(vault);
+        if bump != canonical_bump {
+            return Err(TimeBaseVaultError::NonCanonicalBump.into());
+        }
+        if expected != *target {
+            return Err(ProgramError::InvalidAccountData);
         }
 
         Ok(())
     }
+
+    /// Whether the amount of the vault is counted in the mint totals of the
+    /// owner's registry.
+    pub fn is_tracked(&self) -> bool {
+        self.tracked[0] != 0
+    }
+
+    /// Returns the voting power of the vault at `current_timestamp`, its
+    /// amount weighted by a multiplier growing linearly with the remaining
+    /// lock time, from 1x once unlocked up to `max_multiplier_bps` for
+    /// `max_lock_duration` or more.
+    pub fn voting_power(
+        &self,
+        current_timestamp: i64,
+        max_multiplier_bps: u16,
+        max_lock_duration: i64,
+    ) -> Result<u64, ProgramError> {
+        if max_multiplier_bps < MAX_BPS || max_lock_duration <= 0 {
+            return Err(TimeBaseVaultError::InvalidVotingPowerConfig.into());
+        }
+
+        let remaining = i64::from_le_bytes(self.unlock_timestamp)
+            .saturating_sub(current_timestamp)
+            .clamp(0, max_lock_duration);
+        // below `max_multiplier_bps - MAX_BPS`, so it fits in a u16
+        let bonus_bps = ((max_multiplier_bps - MAX_BPS) as i128 * remaining as i128
+            / max_lock_duration as i128) as u16;
+
+        bps_of(u64::from_le_bytes(self.amount), MAX_BPS + bonus_bps)
+            .ok_or(ProgramError::ArithmeticOverflow)
+    }
 }
@@ -1,8 +1,26 @@
-use pinocchio::program_error::ProgramError;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Signer,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+pub const MAX_BPS: u16 = 10_000;
+
 pub trait DataLen {
     const LEN: usize;
 }
 
+/// The first byte of the accounts that carry their type, which tells apart
+/// accounts of the same length.
+pub trait Discriminator {
+    const DISCRIMINATOR: u8;
+}
+
+/// # Safety
+///
+/// `T` must have an alignment of 1 and `bytes` must hold a valid `T`.
 #[inline(always)]
 pub unsafe fn load_acc_unchecked<T: DataLen>(bytes: &[u8]) -> Result<&T, ProgramError> {
     if bytes.len() != T::LEN {
@@ -11,6 +29,9 @@ pub unsafe fn load_acc_unchecked<T: DataLen>(bytes: &[u8]) -> Result<&T, Program
     Ok(&*(bytes.as_ptr() as *const T))
 }
 
+/// # Safety
+///
+/// `T` must have an alignment of 1 and `bytes` must hold a valid `T`.
 #[inline(always)]
 pub unsafe fn load_acc_mut_unchecked<T: DataLen>(bytes: &mut [u8]) -> Result<&mut T, ProgramError> {
     if bytes.len() != T::LEN {
@@ -19,6 +40,25 @@ pub unsafe fn load_acc_mut_unchecked<T: DataLen>(bytes: &mut [u8]) -> Result<&mu
     Ok(&mut *(bytes.as_mut_ptr() as *mut T))
 }
 
+/// Same as [`load_acc_mut_unchecked`], also failing when `bytes` do not
+/// start with the discriminator of `T`.
+///
+/// # Safety
+///
+/// `T` must have an alignment of 1 and `bytes` must hold a valid `T`.
+#[inline(always)]
+pub unsafe fn load_acc_mut<T: DataLen + Discriminator>(
+    bytes: &mut [u8],
+) -> Result<&mut T, ProgramError> {
+    if bytes.first() != Some(&T::DISCRIMINATOR) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    load_acc_mut_unchecked(bytes)
+}
+
+/// # Safety
+///
+/// `T` must have an alignment of 1 and `bytes` must hold a valid `T`.
 #[inline(always)]
 pub unsafe fn load_ix_data<T: DataLen>(bytes: &[u8]) -> Result<&T, ProgramError> {
     if bytes.len() != T::LEN {
@@ -27,10 +67,107 @@ pub unsafe fn load_ix_data<T: DataLen>(bytes: &[u8]) -> Result<&T, ProgramError>
     Ok(&*(bytes.as_ptr() as *const T))
 }
 
+/// # Safety
+///
+/// `T` must be a `#[repr(C)]` type without padding bytes.
 pub unsafe fn to_bytes<T: DataLen>(data: &T) -> &[u8] {
     core::slice::from_raw_parts(data as *const T as *const u8, T::LEN)
 }
 
+/// # Safety
+///
+/// `T` must be a `#[repr(C)]` type without padding bytes.
 pub unsafe fn to_mut_bytes<T: DataLen>(data: &mut T) -> &mut [u8] {
     core::slice::from_raw_parts_mut(data as *mut T as *mut u8, T::LEN)
 }
+
+/// Returns `bps` basis points of `amount`, rounded down.
+#[inline(always)]
+pub fn bps_of(amount: u64, bps: u16) -> Option<u64> {
+    let value = (amount as u128)
+        .checked_mul(bps as u128)?
+        .checked_div(MAX_BPS as u128)?;
+    u64::try_from(value).ok()
+}
+
+/// Returns the PDA of the program for `seeds` and its canonical bump, on
+/// chain and, with the `std` feature, off chain.
+#[inline(always)]
+pub fn find_program_address(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    #[cfg(any(target_os = "solana", not(feature = "std")))]
+    {
+        pinocchio::pubkey::find_program_address(seeds, &crate::ID)
+    }
+
+    #[cfg(all(not(target_os = "solana"), feature = "std"))]
+    {
+        let (address, bump) = solana_pubkey::Pubkey::find_program_address(
+            seeds,
+            &solana_pubkey::Pubkey::new_from_array(crate::ID),
+        );
+        (address.to_bytes(), bump)
+    }
+}
+
+/// Returns the PDA of the program for `seeds`, which end with the bump, on
+/// chain and, with the `std` feature, off chain.
+#[inline(always)]
+pub fn create_program_address(seeds: &[&[u8]]) -> Result<Pubkey, ProgramError> {
+    #[cfg(any(target_os = "solana", not(feature = "std")))]
+    {
+        pinocchio::pubkey::create_program_address(seeds, &crate::ID)
+    }
+
+    #[cfg(all(not(target_os = "solana"), feature = "std"))]
+    {
+        solana_pubkey::Pubkey::create_program_address(
+            seeds,
+            &solana_pubkey::Pubkey::new_from_array(crate::ID),
+        )
+        .map(|address| address.to_bytes())
+        .map_err(|_| ProgramError::InvalidSeeds)
+    }
+}
+
+/// Creates `account`, a PDA signed by `signers`, with `space` bytes owned by
+/// `owner` and rent paid by `payer`. `CreateAccount` fails on an address that
+/// already holds lamports, which anyone can send to a PDA ahead of time, so
+/// such an account is topped up to rent exemption, allocated and assigned.
+pub fn create_pda_account(
+    payer: &AccountInfo,
+    account: &AccountInfo,
+    space: usize,
+    owner: &Pubkey,
+    signers: &[Signer],
+) -> ProgramResult {
+    let rent = Rent::get()?.minimum_balance(space);
+    let lamports = account.lamports();
+
+    if lamports == 0 {
+        return pinocchio_system::instructions::CreateAccount {
+            from: payer,
+            to: account,
+            space: space as u64,
+            lamports: rent,
+            owner,
+        }
+        .invoke_signed(signers);
+    }
+
+    if lamports < rent {
+        pinocchio_system::instructions::Transfer {
+            from: payer,
+            to: account,
+            lamports: rent - lamports,
+        }
+        .invoke()?;
+    }
+
+    pinocchio_system::instructions::Allocate {
+        account,
+        space: space as u64,
+    }
+    .invoke_signed(signers)?;
+
+    pinocchio_system::instructions::Assign { account, owner }.invoke_signed(signers)
+}
@@ -1,30 +1,54 @@
 #[cfg(test)]
 mod tests_timebase_vault {
-    use mollusk_svm::{result::Check, Mollusk};
+    use mollusk_svm::{
+        result::{Check, InstructionResult},
+        Mollusk,
+    };
 
     use mollusk_svm_programs_token::token::{
         create_account_for_mint, create_account_for_token_account,
     };
     use pinocchio_timebase_vault::{
+        client::{self, ConfigSettings},
+        cpi,
+        events::{
+            BucketDeposited, BucketRedeemed, Event, MerkleClaimed, MerkleReclaimed,
+            MerkleVaultCreated, VaultCreated, VaultSplit, VaultWithdrawn, VaultsMerged,
+            EVENT_AUTHORITY, EVENT_AUTHORITY_BUMP, EVENT_AUTHORITY_SEED, EVENT_VERSION,
+        },
+        idl::{self, IdlField, IdlInstruction, IdlStruct},
         instructions::{
-            InitializeSolVault, InitializeSolVaultInstructionData, InitializeSplVault,
-            InitializeSplVaultInstructionData, WithdrawSolVault, WithdrawSplVault,
+            BatchInitializeSplVault, BatchVaultEntry, EmitEvent, SetMintListMode, VaultStatus,
+            VotingPower, MAX_BATCH_SIZE,
         },
-        states::Vault,
-        utils::{to_bytes, DataLen},
+        states::{
+            ClaimReceipt, Config, LockedBucket, MerkleVault, MintConfig, MintListMode, MintTotal,
+            UserRegistry, Vault,
+        },
+        utils::{to_bytes, DataLen, Discriminator, MAX_BPS},
         ID,
     };
     use solana_sdk::{
-        account::{Account, AccountSharedData},
+        account::{Account, AccountSharedData, ReadableAccount, WritableAccount},
+        address_lookup_table::AddressLookupTableAccount,
+        hash::Hash,
         instruction::{AccountMeta, Instruction},
+        message::{v0, VersionedMessage},
         native_token::LAMPORTS_PER_SOL,
+        packet::PACKET_DATA_SIZE,
         program_error::ProgramError,
         pubkey::Pubkey,
     };
     use spl_associated_token_account::get_associated_token_address;
-    use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+    use spl_token::{
+        solana_program::program_pack::Pack,
+        state::{Account as TokenAccount, AccountState, Mint},
+    };
 
     pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+    pub const TREASURY: Pubkey = Pubkey::new_from_array([0x06; 32]);
+    pub const PENALTY_RECIPIENT: Pubkey = Pubkey::new_from_array([0x07; 32]);
+    pub const EVENT_AUTHORITY_ID: Pubkey = Pubkey::new_from_array(EVENT_AUTHORITY);
 
     fn get_mollusk() -> (Mollusk, Pubkey, Account) {
         let mut mollusk = Mollusk::new(&PROGRAM_ID, "target/deploy/pinocchio_timebase_vault");
@@ -52,6 +76,102 @@ mod tests_timebase_vault {
         (mollusk, mint, mint_account)
     }
 
+    fn get_registry(
+        mollusk: &Mollusk,
+        owner: &Pubkey,
+        locked: Option<(Option<Pubkey>, u64)>,
+    ) -> (Pubkey, u8, AccountSharedData) {
+        let (registry_address, registry_bump) = client::find_registry_address(owner);
+
+        let mut registry_data = UserRegistry {
+            owner: owner.to_bytes(),
+            bump: [registry_bump],
+            vault_counter: [0; 8],
+            live_vaults: [0; 8],
+            mint_totals: core::array::from_fn(|_| MintTotal {
+                mint: None,
+                amount: [0; 8],
+            }),
+        };
+        if let Some((mint, amount)) = locked {
+            registry_data.vault_counter = 1u64.to_le_bytes();
+            registry_data.live_vaults = 1u64.to_le_bytes();
+            registry_data.mint_totals[0].mint = mint.map(|mint| mint.to_bytes());
+            registry_data.mint_totals[0].amount = amount.to_le_bytes();
+        }
+
+        let mut registry_account = AccountSharedData::new(
+            mollusk.sysvars.rent.minimum_balance(UserRegistry::LEN),
+            UserRegistry::LEN,
+            &PROGRAM_ID,
+        );
+        registry_account.set_data_from_slice(unsafe { to_bytes::<UserRegistry>(&registry_data) });
+
+        (registry_address, registry_bump, registry_account)
+    }
+
+    fn get_config_data(admin: &Pubkey) -> Config {
+        let (_, bump) = client::find_config_address();
+
+        Config {
+            admin: admin.to_bytes(),
+            bump: [bump],
+            paused: [0],
+            min_lock_duration: 0i64.to_le_bytes(),
+            max_lock_duration: 0i64.to_le_bytes(),
+            fee_bps: 0u16.to_le_bytes(),
+            flat_fee: 0u64.to_le_bytes(),
+            treasury: TREASURY.to_bytes(),
+            penalty_recipient: PENALTY_RECIPIENT.to_bytes(),
+            mint_list_mode: [MintListMode::Open as u8],
+        }
+    }
+
+    fn get_config(mollusk: &Mollusk, config_data: &Config) -> (Pubkey, AccountSharedData) {
+        let (config_address, _) = client::find_config_address();
+
+        let mut config_account = AccountSharedData::new(
+            mollusk.sysvars.rent.minimum_balance(Config::LEN),
+            Config::LEN,
+            &PROGRAM_ID,
+        );
+        config_account.set_data_from_slice(unsafe { to_bytes::<Config>(config_data) });
+
+        (config_address, config_account)
+    }
+
+    /// Pauses the config with `SetPaused` before processing `instruction`,
+    /// for instructions that must not depend on the pause.
+    fn process_after_pause(
+        mollusk: &Mollusk,
+        instruction: &Instruction,
+        accounts: &[(Pubkey, Account)],
+        checks: &[Check],
+    ) -> InstructionResult {
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+        let admin = Pubkey::new_from_array([0x04; 32]);
+        let (config_address, config_account) = get_config(mollusk, &get_config_data(&admin));
+
+        let mut accounts = accounts.to_vec();
+        accounts.extend([
+            (admin, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (config_address, config_account.into()),
+        ]);
+
+        let result = mollusk.process_and_validate_instruction_chain(
+            &[
+                (&client::set_paused(&admin, true), &[Check::success()]),
+                (instruction, checks),
+            ],
+            &accounts,
+        );
+
+        let data = result.get_account(&config_address).unwrap().data();
+        assert_eq!(unsafe { &*(data.as_ptr() as *const Config) }.paused, [1]);
+
+        result
+    }
+
     #[test]
     fn init_sol_vault() {
         let (mollusk, _, _) = get_mollusk();
@@ -62,43 +182,25 @@ mod tests_timebase_vault {
         let maker = Pubkey::new_from_array([0x02; 32]);
         let maker_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
 
-        let amount = 1 * LAMPORTS_PER_SOL;
+        let amount = LAMPORTS_PER_SOL;
         let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
 
         println!("unlock_timestamp: {}", unlock_timestamp);
 
-        let (vault_address, bump) = Pubkey::find_program_address(
-            &[
-                Vault::SEED,
-                maker.as_ref(),
-                &amount.to_le_bytes(),
-                &unlock_timestamp.to_le_bytes(),
-            ],
-            &PROGRAM_ID,
-        );
+        let (vault_address, _) = client::find_vault_address(&maker, None, amount, unlock_timestamp);
 
         let vault_account = Account::new(0, 0, &system_program);
 
-        let ix_data = InitializeSolVaultInstructionData {
-            amount,
-            unlock_timestamp,
-            bump,
-        };
+        let (registry_address, _) = client::find_registry_address(&maker);
+        let registry_account = Account::new(0, 0, &system_program);
 
-        let lamport_for_rent = mollusk.sysvars.rent.minimum_balance(Vault::LEN);
+        let (config_address, config_account) =
+            get_config(&mollusk, &get_config_data(&Pubkey::new_unique()));
+        let (_, _, expected_registry) = get_registry(&mollusk, &maker, Some((None, amount)));
 
-        let mut data = vec![InitializeSolVault::DISCRIMINATOR.clone()];
-        data.extend_from_slice(unsafe { to_bytes(&ix_data) });
+        let lamport_for_rent = mollusk.sysvars.rent.minimum_balance(Vault::LEN);
 
-        let instruction = Instruction::new_with_bytes(
-            PROGRAM_ID,
-            &data,
-            vec![
-                AccountMeta::new(maker, true),
-                AccountMeta::new(vault_address, false),
-                AccountMeta::new_readonly(system_program, false),
-            ],
-        );
+        let instruction = client::init_sol_vault(&maker, amount, unlock_timestamp, 0, &TREASURY);
 
         let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
             &instruction,
@@ -106,6 +208,14 @@ mod tests_timebase_vault {
                 (maker, maker_account),
                 (vault_address, vault_account),
                 (system_program, system_account),
+                (registry_address, registry_account),
+                (config_address, config_account.into()),
+                (TREASURY, Account::new(0, 0, &system_program)),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
             ],
             &[
                 Check::success(),
@@ -113,11 +223,16 @@ mod tests_timebase_vault {
                 Check::account(&vault_address)
                     .lamports(amount + lamport_for_rent)
                     .build(),
+                Check::account(&registry_address)
+                    .owner(&PROGRAM_ID)
+                    .data(expected_registry.data())
+                    .build(),
             ],
         );
     }
-    #[test]
-    fn withdraw_sol_vault_successfully() {
+    /// Withdraws an unlocked 2 SOL vault, after pausing the config when
+    /// `paused` is set.
+    fn process_withdraw_sol_vault(paused: bool) {
         let (mut mollusk, _, _) = get_mollusk();
 
         let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
@@ -130,15 +245,8 @@ mod tests_timebase_vault {
 
         println!("unlock_timestamp: {}", unlock_timestamp);
 
-        let (vault_address, bump) = Pubkey::find_program_address(
-            &[
-                Vault::SEED,
-                maker.as_ref(),
-                &amount.to_le_bytes(),
-                &unlock_timestamp.to_le_bytes(),
-            ],
-            &PROGRAM_ID,
-        );
+        let (vault_address, bump) =
+            client::find_vault_address(&maker, None, amount, unlock_timestamp);
 
         let lamport_for_rent = mollusk.sysvars.rent.minimum_balance(Vault::LEN);
 
@@ -148,6 +256,12 @@ mod tests_timebase_vault {
             unlock_timestamp: unlock_timestamp.to_le_bytes(),
             mint: None,
             bump: [bump],
+            id: [0; 8],
+            early_exit_penalty_bps: [0; 2],
+            seed_amount: amount.to_le_bytes(),
+            receipt_mint: None,
+            tracked: [1],
+            withdrawn: [0; 8],
         };
 
         let mut vault_account =
@@ -155,29 +269,95 @@ mod tests_timebase_vault {
 
         vault_account.set_data_from_slice(unsafe { to_bytes::<Vault>(&vault_account_data) });
 
-        let data = vec![WithdrawSolVault::DISCRIMINATOR.clone()];
+        let (registry_address, _, registry_account) =
+            get_registry(&mollusk, &maker, Some((None, amount)));
 
-        let instruction = Instruction::new_with_bytes(
-            PROGRAM_ID,
-            &data,
-            vec![
-                AccountMeta::new(maker, true),
-                AccountMeta::new(vault_address, false),
-            ],
-        );
+        let instruction = client::withdraw_sol_vault(&maker, &vault_address);
 
         mollusk.sysvars.clock.unix_timestamp = unlock_timestamp + 100;
 
-        let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
+        let accounts = [
+            (maker, maker_account),
+            (vault_address, vault_account.into()),
+            (registry_address, registry_account.into()),
+            (EVENT_AUTHORITY_ID, Account::default()),
+            (
+                PROGRAM_ID,
+                mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+            ),
+        ];
+        let checks = [
+            Check::success(),
+            Check::account(&vault_address).closed().build(),
+        ];
+
+        if paused {
+            process_after_pause(&mollusk, &instruction, &accounts, &checks);
+        } else {
+            mollusk.process_and_validate_instruction(&instruction, &accounts, &checks);
+        }
+    }
+
+    #[test]
+    fn withdraw_sol_vault_successfully() {
+        process_withdraw_sol_vault(false);
+    }
+
+    #[test]
+    fn withdraw_sol_vault_when_paused() {
+        // a pause only stops new locks, it must never trap unlocked funds
+        process_withdraw_sol_vault(true);
+    }
+
+    #[test]
+    fn withdraw_sol_vault_fail_with_vault_of_another_program() {
+        let (mollusk, _, _) = get_mollusk();
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let maker_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp - 10;
+
+        // a vault lookalike owned by another program
+        let (vault_address, mut vault_account) =
+            get_sol_vault(&mollusk, &maker, LAMPORTS_PER_SOL, unlock_timestamp, 0);
+        vault_account.set_owner(Pubkey::new_unique());
+        let (registry_address, _, registry_account) =
+            get_registry(&mollusk, &maker, Some((None, LAMPORTS_PER_SOL)));
+
+        let instruction = client::withdraw_sol_vault(&maker, &vault_address);
+        mollusk.process_and_validate_instruction(
             &instruction,
             &[
-                (maker, maker_account),
-                (vault_address, vault_account.into()),
+                (maker, maker_account.clone()),
+                (vault_address, vault_account.clone().into()),
+                (registry_address, registry_account.clone().into()),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
             ],
+            &[Check::err(ProgramError::InvalidAccountOwner)],
+        );
+
+        let (token_program, token_program_account) =
+            mollusk_svm_programs_token::token::keyed_account();
+        let instruction = client::batch_withdraw(&maker, &[(vault_address, None)], false);
+        mollusk.process_and_validate_instruction(
+            &instruction,
             &[
-                Check::success(),
-                Check::account(&vault_address).closed().build(),
+                (maker, maker_account),
+                (registry_address, registry_account.into()),
+                (token_program, token_program_account),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+                (vault_address, vault_account.into()),
             ],
+            &[Check::err(ProgramError::InvalidAccountOwner)],
         );
     }
 
@@ -195,15 +375,8 @@ mod tests_timebase_vault {
 
         println!("unlock_timestamp: {}", unlock_timestamp);
 
-        let (vault_address, bump) = Pubkey::find_program_address(
-            &[
-                Vault::SEED,
-                maker.as_ref(),
-                &amount.to_le_bytes(),
-                &unlock_timestamp.to_le_bytes(),
-            ],
-            &PROGRAM_ID,
-        );
+        let (vault_address, bump) =
+            client::find_vault_address(&maker, None, amount, unlock_timestamp);
 
         let lamport_for_rent = mollusk.sysvars.rent.minimum_balance(Vault::LEN);
 
@@ -213,6 +386,12 @@ mod tests_timebase_vault {
             unlock_timestamp: unlock_timestamp.to_le_bytes(),
             mint: None,
             bump: [bump],
+            id: [0; 8],
+            early_exit_penalty_bps: [0; 2],
+            seed_amount: amount.to_le_bytes(),
+            receipt_mint: None,
+            tracked: [1],
+            withdrawn: [0; 8],
         };
 
         let mut vault_account =
@@ -220,22 +399,22 @@ mod tests_timebase_vault {
 
         vault_account.set_data_from_slice(unsafe { to_bytes::<Vault>(&vault_account_data) });
 
-        let data = vec![WithdrawSolVault::DISCRIMINATOR.clone()];
+        let (registry_address, _, registry_account) =
+            get_registry(&mollusk, &maker, Some((None, amount)));
 
-        let instruction = Instruction::new_with_bytes(
-            PROGRAM_ID,
-            &data,
-            vec![
-                AccountMeta::new(maker, true),
-                AccountMeta::new(vault_address, false),
-            ],
-        );
+        let instruction = client::withdraw_sol_vault(&maker, &vault_address);
 
         let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
             &instruction,
             &[
                 (maker, maker_account),
                 (vault_address, vault_account.into()),
+                (registry_address, registry_account.into()),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
             ],
             &[
                 Check::err(ProgramError::Custom(3)), // VaultLocking
@@ -247,6 +426,110 @@ mod tests_timebase_vault {
         );
     }
 
+    #[test]
+    fn withdraw_sol_vault_fail_with_spl_vault() {
+        let (mollusk, mint, _) = get_mollusk();
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let maker_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+
+        let amount = 1_000_000u64;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp - 10;
+        let (vault_address, bump) =
+            client::find_vault_address(&maker, Some(&mint), amount, unlock_timestamp);
+        let lamport_for_rent = mollusk.sysvars.rent.minimum_balance(Vault::LEN);
+
+        let vault_account_data = Vault {
+            owner: maker.to_bytes(),
+            amount: amount.to_le_bytes(),
+            unlock_timestamp: unlock_timestamp.to_le_bytes(),
+            mint: Some(mint.to_bytes()),
+            bump: [bump],
+            id: [0; 8],
+            early_exit_penalty_bps: [0; 2],
+            seed_amount: amount.to_le_bytes(),
+            receipt_mint: None,
+            tracked: [1],
+            withdrawn: [0; 8],
+        };
+        let mut vault_account = AccountSharedData::new(lamport_for_rent, Vault::LEN, &PROGRAM_ID);
+        vault_account.set_data_from_slice(unsafe { to_bytes::<Vault>(&vault_account_data) });
+
+        let (registry_address, _, registry_account) =
+            get_registry(&mollusk, &maker, Some((Some(mint), amount)));
+
+        // closing the vault as a SOL vault would strand the tokens of its
+        // token account
+        let instruction = client::withdraw_sol_vault(&maker, &vault_address);
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (maker, maker_account),
+                (vault_address, vault_account.into()),
+                (registry_address, registry_account.into()),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            &[
+                Check::err(ProgramError::Custom(4)), // InvalidVaultMint
+                Check::account(&vault_address).owner(&PROGRAM_ID).build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn withdraw_spl_vault_fail_with_sol_vault() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+        let (token_program, token_program_account) =
+            mollusk_svm_programs_token::token::keyed_account();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let maker_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+
+        let amount = 2 * LAMPORTS_PER_SOL;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp - 10;
+        let (vault_address, vault_account) =
+            get_sol_vault(&mollusk, &maker, amount, unlock_timestamp, 0);
+        let (registry_address, _, registry_account) =
+            get_registry(&mollusk, &maker, Some((None, amount)));
+
+        let instruction = client::withdraw_spl_vault(&maker, &vault_address, &mint);
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (maker, maker_account),
+                (vault_address, vault_account.into()),
+                (mint, mint_account),
+                (
+                    get_associated_token_address(&maker, &mint),
+                    token_account(mint, maker, 0),
+                ),
+                (
+                    get_associated_token_address(&vault_address, &mint),
+                    token_account(mint, vault_address, 0),
+                ),
+                (token_program, token_program_account),
+                (system_program, system_account),
+                (registry_address, registry_account.into()),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            &[
+                Check::err(ProgramError::Custom(4)), // InvalidVaultMint
+                Check::account(&vault_address).owner(&PROGRAM_ID).build(),
+            ],
+        );
+    }
+
     #[test]
     fn withdraw_sol_vault_fail_with_unauthorized_user() {
         let (mollusk, _, _) = get_mollusk();
@@ -264,15 +547,8 @@ mod tests_timebase_vault {
 
         println!("unlock_timestamp: {}", unlock_timestamp);
 
-        let (vault_address, bump) = Pubkey::find_program_address(
-            &[
-                Vault::SEED,
-                maker.as_ref(),
-                &amount.to_le_bytes(),
-                &unlock_timestamp.to_le_bytes(),
-            ],
-            &PROGRAM_ID,
-        );
+        let (vault_address, bump) =
+            client::find_vault_address(&maker, None, amount, unlock_timestamp);
 
         let lamport_for_rent = mollusk.sysvars.rent.minimum_balance(Vault::LEN);
 
@@ -282,6 +558,12 @@ mod tests_timebase_vault {
             unlock_timestamp: unlock_timestamp.to_le_bytes(),
             mint: None,
             bump: [bump],
+            id: [0; 8],
+            early_exit_penalty_bps: [0; 2],
+            seed_amount: amount.to_le_bytes(),
+            receipt_mint: None,
+            tracked: [1],
+            withdrawn: [0; 8],
         };
 
         let mut vault_account =
@@ -289,22 +571,21 @@ mod tests_timebase_vault {
 
         vault_account.set_data_from_slice(unsafe { to_bytes::<Vault>(&vault_account_data) });
 
-        let data = vec![WithdrawSolVault::DISCRIMINATOR.clone()];
+        let (registry_address, _, registry_account) = get_registry(&mollusk, &attacker, None);
 
-        let instruction = Instruction::new_with_bytes(
-            PROGRAM_ID,
-            &data,
-            vec![
-                AccountMeta::new(attacker, true),
-                AccountMeta::new(vault_address, false),
-            ],
-        );
+        let instruction = client::withdraw_sol_vault(&attacker, &vault_address);
 
         let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
             &instruction,
             &[
                 (attacker, attacker_account),
                 (vault_address, vault_account.into()),
+                (registry_address, registry_account.into()),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
             ],
             &[
                 Check::err(ProgramError::Custom(2)), // Unauthorized
@@ -320,6 +601,100 @@ mod tests_timebase_vault {
     fn init_spl_vault() {
         let (mollusk, mint, mint_account) = get_mollusk();
 
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let amount = 1_000_000u64;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+
+        let (vault_address, _) =
+            client::find_vault_address(&maker, Some(&mint), amount, unlock_timestamp);
+        let (registry_address, _) = client::find_registry_address(&maker);
+
+        let result = process_init_spl_vault(
+            &mollusk,
+            (mint, mint_account),
+            &get_config_data(&Pubkey::new_unique()),
+            None,
+            &[Check::success()],
+        );
+
+        assert_eq!(
+            result.get_account(&vault_address).unwrap().owner,
+            PROGRAM_ID
+        );
+        assert_eq!(
+            result.get_account(&registry_address).unwrap().owner,
+            PROGRAM_ID
+        );
+    }
+
+    #[test]
+    fn init_spl_vault_without_treasury_token_account_when_fee_is_zero() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+
+        process_init_spl_vault_with(
+            &mollusk,
+            (mint, mint_account.clone()),
+            &get_config_data(&Pubkey::new_unique()),
+            None,
+            Account::new(0, 0, &system_program),
+            |_| {},
+            &[Check::success()],
+        );
+
+        let mut config_data = get_config_data(&Pubkey::new_unique());
+        config_data.fee_bps = 25u16.to_le_bytes();
+        process_init_spl_vault_with(
+            &mollusk,
+            (mint, mint_account),
+            &config_data,
+            None,
+            Account::new(0, 0, &system_program),
+            |_| {},
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    fn process_init_spl_vault(
+        mollusk: &Mollusk,
+        (mint, mint_account): (Pubkey, Account),
+        config_data: &Config,
+        mint_config_data: Option<&MintConfig>,
+        checks: &[Check],
+    ) -> InstructionResult {
+        let treasury_ata_account = create_account_for_token_account(TokenAccount {
+            mint,
+            owner: TREASURY,
+            amount: 0,
+            delegate: None.into(),
+            state: AccountState::Initialized,
+            is_native: None.into(),
+            delegated_amount: 0,
+            close_authority: None.into(),
+        });
+
+        process_init_spl_vault_with(
+            mollusk,
+            (mint, mint_account),
+            config_data,
+            mint_config_data,
+            treasury_ata_account,
+            |_| {},
+            checks,
+        )
+    }
+
+    /// Same as [`process_init_spl_vault`] with `treasury_ata_account` and the
+    /// instruction changed by `edit`.
+    fn process_init_spl_vault_with(
+        mollusk: &Mollusk,
+        (mint, mint_account): (Pubkey, Account),
+        config_data: &Config,
+        mint_config_data: Option<&MintConfig>,
+        treasury_ata_account: Account,
+        edit: impl FnOnce(&mut Instruction),
+        checks: &[Check],
+    ) -> InstructionResult {
         let (system_program, system_account) =
             mollusk_svm::program::keyed_account_for_system_program();
 
@@ -351,47 +726,41 @@ mod tests_timebase_vault {
         let amount = 1_000_000u64;
         let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
 
-        let (vault_address, bump) = Pubkey::find_program_address(
-            &[
-                Vault::SEED,
-                maker.as_ref(),
-                mint.as_ref(),
-                &amount.to_le_bytes(),
-                &unlock_timestamp.to_le_bytes(),
-            ],
-            &PROGRAM_ID,
-        );
+        let (vault_address, _) =
+            client::find_vault_address(&maker, Some(&mint), amount, unlock_timestamp);
 
         let vault_account = Account::new(0, 0, &system_program);
 
         let vault_ata = get_associated_token_address(&vault_address, &mint);
         let vault_ata_account = AccountSharedData::new(0, 0, &system_program);
 
-        let ix_data = InitializeSplVaultInstructionData {
-            amount,
-            unlock_timestamp,
-            bump,
+        let (registry_address, _) = client::find_registry_address(&maker);
+        let registry_account = Account::new(0, 0, &system_program);
+
+        let treasury_ata = get_associated_token_address(&TREASURY, &mint);
+
+        let mint_config_account = match mint_config_data {
+            Some(mint_config_data) => {
+                let mut mint_config_account = Account::new(
+                    mollusk.sysvars.rent.minimum_balance(MintConfig::LEN),
+                    MintConfig::LEN,
+                    &PROGRAM_ID,
+                );
+                mint_config_account.data =
+                    unsafe { to_bytes::<MintConfig>(mint_config_data) }.to_vec();
+                mint_config_account
+            }
+            None => Account::new(0, 0, &system_program),
         };
 
-        let mut data = vec![InitializeSplVault::DISCRIMINATOR.clone()];
-        data.extend_from_slice(unsafe { to_bytes(&ix_data) });
+        let (config_address, config_account) = get_config(mollusk, config_data);
 
-        let instruction = Instruction::new_with_bytes(
-            PROGRAM_ID,
-            &data,
-            vec![
-                AccountMeta::new(maker, true),
-                AccountMeta::new(vault_address, false),
-                AccountMeta::new(mint, false),
-                AccountMeta::new(user_ata, false),
-                AccountMeta::new(vault_ata, false),
-                AccountMeta::new_readonly(token_program, false),
-                AccountMeta::new_readonly(associated_token_program, false),
-                AccountMeta::new_readonly(system_program, false),
-            ],
-        );
+        let mut instruction =
+            client::init_spl_vault(&maker, &mint, amount, unlock_timestamp, 0, &TREASURY);
+        edit(&mut instruction);
+        let mint_config_address = instruction.accounts[12].pubkey;
 
-        let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
+        mollusk.process_and_validate_instruction(
             &instruction,
             &[
                 (maker, maker_account),
@@ -402,16 +771,24 @@ mod tests_timebase_vault {
                 (token_program, token_program_account),
                 (associated_token_program, associated_token_program_account),
                 (system_program, system_account),
+                (registry_address, registry_account),
+                (config_address, config_account.into()),
+                (TREASURY, Account::new(0, 0, &system_program)),
+                (treasury_ata, treasury_ata_account),
+                (mint_config_address, mint_config_account),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
             ],
-            &[
-                Check::success(),
-                Check::account(&vault_address).owner(&PROGRAM_ID).build(),
-            ],
-        );
+            checks,
+        )
     }
 
-    #[test]
-    fn withdraw_spl_vault_successfully() {
+    /// Withdraws an unlocked vault of 1,000,000 tokens, after pausing the
+    /// config when `paused` is set.
+    fn process_withdraw_spl_vault(paused: bool) {
         let (mut mollusk, mint, mint_account) = get_mollusk();
 
         let (system_program, system_account) =
@@ -441,16 +818,8 @@ mod tests_timebase_vault {
         let amount = 1_000_000u64;
         let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
 
-        let (vault_address, bump) = Pubkey::find_program_address(
-            &[
-                Vault::SEED,
-                maker.as_ref(),
-                mint.as_ref(),
-                &amount.to_le_bytes(),
-                &unlock_timestamp.to_le_bytes(),
-            ],
-            &PROGRAM_ID,
-        );
+        let (vault_address, bump) =
+            client::find_vault_address(&maker, Some(&mint), amount, unlock_timestamp);
 
         let lamport_for_rent = mollusk.sysvars.rent.minimum_balance(Vault::LEN);
 
@@ -460,6 +829,12 @@ mod tests_timebase_vault {
             unlock_timestamp: unlock_timestamp.to_le_bytes(),
             mint: Some(mint.to_bytes()),
             bump: [bump],
+            id: [0; 8],
+            early_exit_penalty_bps: [0; 2],
+            seed_amount: amount.to_le_bytes(),
+            receipt_mint: None,
+            tracked: [1],
+            withdrawn: [0; 8],
         };
 
         let mut vault_account = AccountSharedData::new(lamport_for_rent, Vault::LEN, &PROGRAM_ID);
@@ -469,7 +844,7 @@ mod tests_timebase_vault {
         let vault_token_data = TokenAccount {
             mint,
             owner: vault_address,
-            amount: amount,
+            amount,
             delegate: None.into(),
             state: AccountState::Initialized,
             is_native: None.into(),
@@ -480,39 +855,4359 @@ mod tests_timebase_vault {
         let vault_ata_account = create_account_for_token_account(vault_token_data);
         let vault_ata = get_associated_token_address(&vault_address, &mint);
 
-        let data = vec![WithdrawSplVault::DISCRIMINATOR.clone()];
+        let (registry_address, _, registry_account) =
+            get_registry(&mollusk, &maker, Some((Some(mint), amount)));
 
-        let instruction = Instruction::new_with_bytes(
-            PROGRAM_ID,
-            &data,
-            vec![
-                AccountMeta::new(maker, true),
-                AccountMeta::new(vault_address, false),
-                AccountMeta::new(mint, false),
-                AccountMeta::new(user_ata, false),
-                AccountMeta::new(vault_ata, false),
-                AccountMeta::new_readonly(token_program, false),
-                AccountMeta::new_readonly(system_program, false),
-            ],
-        );
+        let instruction = client::withdraw_spl_vault(&maker, &vault_address, &mint);
 
         mollusk.sysvars.clock.unix_timestamp = unlock_timestamp + 100;
 
+        let accounts = [
+            (maker, maker_account),
+            (vault_address, vault_account.into()),
+            (mint, mint_account),
+            (user_ata, user_ata_account),
+            (vault_ata, vault_ata_account),
+            (token_program, token_program_account),
+            (system_program, system_account),
+            (registry_address, registry_account.into()),
+            (EVENT_AUTHORITY_ID, Account::default()),
+            (
+                PROGRAM_ID,
+                mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+            ),
+        ];
+        let expected_user_ata = token_account(mint, maker, amount);
+        let checks = [
+            Check::success(),
+            Check::account(&user_ata)
+                .data(&expected_user_ata.data)
+                .build(),
+        ];
+
+        if paused {
+            process_after_pause(&mollusk, &instruction, &accounts, &checks);
+        } else {
+            mollusk.process_and_validate_instruction(&instruction, &accounts, &checks);
+        }
+    }
+
+    #[test]
+    fn withdraw_spl_vault_successfully() {
+        process_withdraw_spl_vault(false);
+    }
+
+    #[test]
+    fn withdraw_spl_vault_when_paused() {
+        process_withdraw_spl_vault(true);
+    }
+
+    #[test]
+    fn initialize_config_successfully() {
+        let (mollusk, _, _) = get_mollusk();
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let admin = Pubkey::new_from_array([0x04; 32]);
+        let admin_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+
+        let (config_address, _) = client::find_config_address();
+        let config_account = Account::new(0, 0, &system_program);
+
+        let program_account = mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID);
+        let (program_data, _) = Pubkey::find_program_address(
+            &[PROGRAM_ID.as_ref()],
+            &mollusk_svm::program::loader_keys::LOADER_V3,
+        );
+        let mut program_data_account =
+            mollusk_svm::program::create_program_data_account_loader_v3(&[]);
+        program_data_account.data[12] = 1;
+        program_data_account.data[13..45].copy_from_slice(admin.as_ref());
+
+        let treasury = Pubkey::new_unique();
+        let mut expected_config = get_config_data(&admin);
+        expected_config.min_lock_duration = 60i64.to_le_bytes();
+        expected_config.max_lock_duration = (365 * 24 * 3600i64).to_le_bytes();
+        expected_config.fee_bps = 25u16.to_le_bytes();
+        expected_config.flat_fee = 5_000u64.to_le_bytes();
+        expected_config.treasury = treasury.to_bytes();
+
+        let instruction = client::initialize_config(
+            &admin,
+            &ConfigSettings {
+                min_lock_duration: 60,
+                max_lock_duration: 365 * 24 * 3600,
+                fee_bps: 25,
+                flat_fee: 5_000,
+                treasury,
+                penalty_recipient: PENALTY_RECIPIENT,
+            },
+        );
+
         let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
             &instruction,
             &[
-                (maker, maker_account),
-                (vault_address, vault_account.into()),
-                (mint, mint_account),
-                (user_ata, user_ata_account),
-                (vault_ata, vault_ata_account.into()),
-                (token_program, token_program_account),
+                (admin, admin_account),
+                (config_address, config_account),
+                (PROGRAM_ID, program_account),
+                (program_data, program_data_account),
                 (system_program, system_account),
             ],
             &[
                 Check::success(),
-                // Check::account(&vault_address).owner(&PROGRAM_ID).build(),
+                Check::account(&config_address)
+                    .owner(&PROGRAM_ID)
+                    .data(unsafe { to_bytes::<Config>(&expected_config) })
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn initialize_config_fail_with_non_upgrade_authority() {
+        let (mollusk, _, _) = get_mollusk();
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let attacker = Pubkey::new_from_array([0x03; 32]);
+        let attacker_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+
+        let (config_address, _) = client::find_config_address();
+        let config_account = Account::new(0, 0, &system_program);
+
+        let program_account = mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID);
+        let (program_data, _) = Pubkey::find_program_address(
+            &[PROGRAM_ID.as_ref()],
+            &mollusk_svm::program::loader_keys::LOADER_V3,
+        );
+        let mut program_data_account =
+            mollusk_svm::program::create_program_data_account_loader_v3(&[]);
+        program_data_account.data[12] = 1;
+        program_data_account.data[13..45].copy_from_slice(&[0x04; 32]);
+
+        let instruction = client::initialize_config(
+            &attacker,
+            &ConfigSettings {
+                min_lock_duration: 0,
+                max_lock_duration: 0,
+                fee_bps: 0,
+                flat_fee: 0,
+                treasury: attacker,
+                penalty_recipient: attacker,
+            },
+        );
+
+        let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (attacker, attacker_account),
+                (config_address, config_account),
+                (PROGRAM_ID, program_account),
+                (program_data, program_data_account),
+                (system_program, system_account),
+            ],
+            &[
+                Check::err(ProgramError::Custom(2)), // Unauthorized
+            ],
+        );
+    }
+
+    #[test]
+    fn update_config_fail_with_unauthorized_admin() {
+        let (mollusk, _, _) = get_mollusk();
+
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+
+        let admin = Pubkey::new_from_array([0x04; 32]);
+        let attacker = Pubkey::new_from_array([0x03; 32]);
+        let attacker_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+
+        let (config_address, config_account) = get_config(&mollusk, &get_config_data(&admin));
+
+        let instruction = client::update_config(
+            &attacker,
+            &ConfigSettings {
+                min_lock_duration: 0,
+                max_lock_duration: 0,
+                fee_bps: MAX_BPS,
+                flat_fee: 0,
+                treasury: attacker,
+                penalty_recipient: attacker,
+            },
+        );
+
+        let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (attacker, attacker_account),
+                (config_address, config_account.into()),
+            ],
+            &[
+                Check::err(ProgramError::Custom(2)), // Unauthorized
+            ],
+        );
+    }
+
+    #[test]
+    fn transfer_admin_successfully() {
+        let (mollusk, _, _) = get_mollusk();
+
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+
+        let admin = Pubkey::new_from_array([0x04; 32]);
+        let admin_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+        let new_admin = Pubkey::new_from_array([0x05; 32]);
+
+        let (config_address, config_account) = get_config(&mollusk, &get_config_data(&admin));
+        let expected_config = get_config_data(&new_admin);
+
+        let instruction = client::transfer_admin(&admin, &new_admin);
+
+        let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (admin, admin_account),
+                (config_address, config_account.into()),
+            ],
+            &[
+                Check::success(),
+                Check::account(&config_address)
+                    .data(unsafe { to_bytes::<Config>(&expected_config) })
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn set_paused_successfully() {
+        let (mollusk, _, _) = get_mollusk();
+
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+
+        let admin = Pubkey::new_from_array([0x04; 32]);
+        let admin_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+
+        let (config_address, config_account) = get_config(&mollusk, &get_config_data(&admin));
+        let mut expected_config = get_config_data(&admin);
+        expected_config.paused = [1];
+
+        let instruction = client::set_paused(&admin, true);
+
+        let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (admin, admin_account),
+                (config_address, config_account.into()),
+            ],
+            &[
+                Check::success(),
+                Check::account(&config_address)
+                    .data(unsafe { to_bytes::<Config>(&expected_config) })
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn init_sol_vault_fail_when_paused() {
+        let (mollusk, _, _) = get_mollusk();
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let maker_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+
+        let amount = LAMPORTS_PER_SOL;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+
+        let (vault_address, _) = client::find_vault_address(&maker, None, amount, unlock_timestamp);
+        let vault_account = Account::new(0, 0, &system_program);
+
+        let (registry_address, _) = client::find_registry_address(&maker);
+        let registry_account = Account::new(0, 0, &system_program);
+
+        let mut config_data = get_config_data(&Pubkey::new_unique());
+        config_data.paused = [1];
+        let (config_address, config_account) = get_config(&mollusk, &config_data);
+
+        let instruction = client::init_sol_vault(&maker, amount, unlock_timestamp, 0, &TREASURY);
+
+        let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (maker, maker_account),
+                (vault_address, vault_account),
+                (system_program, system_account),
+                (registry_address, registry_account),
+                (config_address, config_account.into()),
+                (TREASURY, Account::new(0, 0, &system_program)),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            &[
+                Check::err(ProgramError::Custom(7)), // ProgramPaused
+            ],
+        );
+    }
+
+    #[test]
+    fn init_sol_vault_with_fee() {
+        let (mollusk, _, _) = get_mollusk();
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let maker_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+
+        let amount = LAMPORTS_PER_SOL;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+
+        let (vault_address, _) = client::find_vault_address(&maker, None, amount, unlock_timestamp);
+
+        let vault_account = Account::new(0, 0, &system_program);
+
+        let (registry_address, _) = client::find_registry_address(&maker);
+        let registry_account = Account::new(0, 0, &system_program);
+
+        let mut config_data = get_config_data(&Pubkey::new_unique());
+        config_data.fee_bps = 100u16.to_le_bytes();
+        config_data.flat_fee = 5_000u64.to_le_bytes();
+        let (config_address, config_account) = get_config(&mollusk, &config_data);
+        let (_, _, expected_registry) = get_registry(&mollusk, &maker, Some((None, amount)));
+
+        let lamport_for_rent = mollusk.sysvars.rent.minimum_balance(Vault::LEN);
+
+        let instruction = client::init_sol_vault(&maker, amount, unlock_timestamp, 0, &TREASURY);
+
+        let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (maker, maker_account),
+                (vault_address, vault_account),
+                (system_program, system_account),
+                (registry_address, registry_account),
+                (config_address, config_account.into()),
+                (TREASURY, Account::new(0, 0, &system_program)),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            &[
+                Check::success(),
+                Check::account(&vault_address).owner(&PROGRAM_ID).build(),
+                Check::account(&vault_address)
+                    .lamports(amount + lamport_for_rent)
+                    .build(),
+                Check::account(&registry_address)
+                    .owner(&PROGRAM_ID)
+                    .data(expected_registry.data())
+                    .build(),
+                Check::account(&TREASURY)
+                    .lamports(amount / 100 + 5_000)
+                    .build(),
+            ],
+        );
+    }
+
+    fn get_sol_vault(
+        mollusk: &Mollusk,
+        maker: &Pubkey,
+        amount: u64,
+        unlock_timestamp: i64,
+        early_exit_penalty_bps: u16,
+    ) -> (Pubkey, AccountSharedData) {
+        let (vault_address, bump) =
+            client::find_vault_address(maker, None, amount, unlock_timestamp);
+
+        let lamport_for_rent = mollusk.sysvars.rent.minimum_balance(Vault::LEN);
+
+        let vault_account_data = Vault {
+            owner: maker.to_bytes(),
+            amount: amount.to_le_bytes(),
+            unlock_timestamp: unlock_timestamp.to_le_bytes(),
+            mint: None,
+            bump: [bump],
+            id: [0; 8],
+            early_exit_penalty_bps: early_exit_penalty_bps.to_le_bytes(),
+            seed_amount: amount.to_le_bytes(),
+            receipt_mint: None,
+            tracked: [1],
+            withdrawn: [0; 8],
+        };
+
+        let mut vault_account =
+            AccountSharedData::new(lamport_for_rent + amount, Vault::LEN, &PROGRAM_ID);
+        vault_account.set_data_from_slice(unsafe { to_bytes::<Vault>(&vault_account_data) });
+
+        (vault_address, vault_account)
+    }
+
+    #[test]
+    fn early_withdraw_sol_vault_with_penalty() {
+        let (mollusk, _, _) = get_mollusk();
+
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let maker_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+
+        let amount = 2 * LAMPORTS_PER_SOL;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let lamport_for_rent = mollusk.sysvars.rent.minimum_balance(Vault::LEN);
+
+        let (vault_address, vault_account) =
+            get_sol_vault(&mollusk, &maker, amount, unlock_timestamp, 1_000);
+        let (registry_address, _, registry_account) =
+            get_registry(&mollusk, &maker, Some((None, amount)));
+        let (config_address, config_account) =
+            get_config(&mollusk, &get_config_data(&Pubkey::new_unique()));
+
+        let instruction =
+            client::early_withdraw_sol_vault(&maker, &vault_address, &PENALTY_RECIPIENT);
+
+        let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (maker, maker_account),
+                (vault_address, vault_account.into()),
+                (registry_address, registry_account.into()),
+                (config_address, config_account.into()),
+                (PENALTY_RECIPIENT, Account::new(0, 0, &system_program)),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            &[
+                Check::success(),
+                Check::account(&vault_address).closed().build(),
+                Check::account(&PENALTY_RECIPIENT)
+                    .lamports(amount / 10)
+                    .build(),
+                Check::account(&maker)
+                    .lamports(10 * LAMPORTS_PER_SOL + amount - amount / 10 + lamport_for_rent)
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn early_withdraw_unlocked_sol_vault_without_config() {
+        let (mut mollusk, _, _) = get_mollusk();
+
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let maker_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+
+        let amount = 2 * LAMPORTS_PER_SOL;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let lamport_for_rent = mollusk.sysvars.rent.minimum_balance(Vault::LEN);
+
+        let (vault_address, vault_account) =
+            get_sol_vault(&mollusk, &maker, amount, unlock_timestamp, 1_000);
+        let (registry_address, _, registry_account) =
+            get_registry(&mollusk, &maker, Some((None, amount)));
+        mollusk.sysvars.clock.unix_timestamp = unlock_timestamp;
+
+        // no penalty is charged, so the config and recipient are placeholders
+        let placeholder = Pubkey::new_unique();
+        let mut instruction =
+            client::early_withdraw_sol_vault(&maker, &vault_address, &placeholder);
+        instruction.accounts[3].pubkey = placeholder;
+
+        let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (maker, maker_account),
+                (vault_address, vault_account.into()),
+                (registry_address, registry_account.into()),
+                (placeholder, Account::new(0, 0, &system_program)),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            &[
+                Check::success(),
+                Check::account(&vault_address).closed().build(),
+                Check::account(&placeholder).lamports(0).build(),
+                Check::account(&maker)
+                    .lamports(10 * LAMPORTS_PER_SOL + amount + lamport_for_rent)
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn early_withdraw_sol_vault_fail_when_not_allowed() {
+        let (mollusk, _, _) = get_mollusk();
+
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let maker_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+
+        let amount = 2 * LAMPORTS_PER_SOL;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+
+        let (vault_address, vault_account) =
+            get_sol_vault(&mollusk, &maker, amount, unlock_timestamp, 0);
+        let (registry_address, _, registry_account) =
+            get_registry(&mollusk, &maker, Some((None, amount)));
+        let (config_address, config_account) =
+            get_config(&mollusk, &get_config_data(&Pubkey::new_unique()));
+
+        let instruction =
+            client::early_withdraw_sol_vault(&maker, &vault_address, &PENALTY_RECIPIENT);
+
+        let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (maker, maker_account),
+                (vault_address, vault_account.into()),
+                (registry_address, registry_account.into()),
+                (config_address, config_account.into()),
+                (PENALTY_RECIPIENT, Account::new(0, 0, &system_program)),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            &[
+                Check::err(ProgramError::Custom(3)), // VaultLocking
+            ],
+        );
+    }
+
+    fn process_init_sol_vault(
+        mollusk: &Mollusk,
+        config_data: &Config,
+        unlock_timestamp: i64,
+        checks: &[Check],
+    ) {
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let maker_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+
+        let amount = LAMPORTS_PER_SOL;
+
+        let (vault_address, _) = client::find_vault_address(&maker, None, amount, unlock_timestamp);
+        let (registry_address, _) = client::find_registry_address(&maker);
+        let (config_address, config_account) = get_config(mollusk, config_data);
+
+        let instruction = client::init_sol_vault(&maker, amount, unlock_timestamp, 0, &TREASURY);
+
+        let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (maker, maker_account),
+                (vault_address, Account::new(0, 0, &system_program)),
+                (system_program, system_account),
+                (registry_address, Account::new(0, 0, &system_program)),
+                (config_address, config_account.into()),
+                (TREASURY, Account::new(0, 0, &system_program)),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            checks,
+        );
+    }
+
+    #[test]
+    fn init_sol_vault_fail_with_lock_duration_too_short() {
+        let (mollusk, _, _) = get_mollusk();
+
+        let mut config_data = get_config_data(&Pubkey::new_unique());
+        config_data.min_lock_duration = 7200i64.to_le_bytes();
+
+        process_init_sol_vault(
+            &mollusk,
+            &config_data,
+            mollusk.sysvars.clock.unix_timestamp + 3600,
+            &[Check::err(ProgramError::Custom(12))], // LockDurationTooShort
+        );
+    }
+
+    #[test]
+    fn init_sol_vault_fail_with_lock_duration_too_long() {
+        let (mollusk, _, _) = get_mollusk();
+
+        let mut config_data = get_config_data(&Pubkey::new_unique());
+        config_data.max_lock_duration = 1800i64.to_le_bytes();
+
+        process_init_sol_vault(
+            &mollusk,
+            &config_data,
+            mollusk.sysvars.clock.unix_timestamp + 3600,
+            &[Check::err(ProgramError::Custom(13))], // LockDurationTooLong
+        );
+    }
+
+    #[test]
+    fn set_mint_config_successfully() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let admin = Pubkey::new_from_array([0x04; 32]);
+        let admin_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+
+        let (config_address, config_account) = get_config(&mollusk, &get_config_data(&admin));
+        let (mint_config_address, bump) = client::find_mint_config_address(&mint);
+
+        let expected_mint_config = MintConfig {
+            mint: mint.to_bytes(),
+            bump: [bump],
+            min_lock_duration: 3600i64.to_le_bytes(),
+            max_lock_duration: 0i64.to_le_bytes(),
+            listed: [0],
+        };
+
+        let instruction = client::set_mint_config(&admin, &mint, 3600, 0);
+
+        let _: mollusk_svm::result::InstructionResult = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (admin, admin_account),
+                (config_address, config_account.into()),
+                (mint_config_address, Account::new(0, 0, &system_program)),
+                (mint, mint_account),
+                (system_program, system_account),
+            ],
+            &[
+                Check::success(),
+                Check::account(&mint_config_address)
+                    .owner(&PROGRAM_ID)
+                    .data(unsafe { to_bytes::<MintConfig>(&expected_mint_config) })
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn init_spl_vault_with_allow_listed_mint() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+
+        let mut config_data = get_config_data(&Pubkey::new_unique());
+        config_data.mint_list_mode = [MintListMode::AllowList as u8];
+
+        let (_, bump) = client::find_mint_config_address(&mint);
+        let mint_config_data = MintConfig {
+            mint: mint.to_bytes(),
+            bump: [bump],
+            min_lock_duration: 0i64.to_le_bytes(),
+            max_lock_duration: 0i64.to_le_bytes(),
+            listed: [1],
+        };
+
+        process_init_spl_vault(
+            &mollusk,
+            (mint, mint_account),
+            &config_data,
+            Some(&mint_config_data),
+            &[Check::success()],
+        );
+    }
+
+    #[test]
+    fn init_spl_vault_fail_with_unlisted_mint() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+
+        let mut config_data = get_config_data(&Pubkey::new_unique());
+        config_data.mint_list_mode = [MintListMode::AllowList as u8];
+
+        process_init_spl_vault(
+            &mollusk,
+            (mint, mint_account),
+            &config_data,
+            None,
+            &[Check::err(ProgramError::Custom(4))], // InvalidVaultMint
+        );
+    }
+
+    #[test]
+    fn init_spl_vault_fail_with_deny_listed_mint() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+
+        let mut config_data = get_config_data(&Pubkey::new_unique());
+        config_data.mint_list_mode = [MintListMode::DenyList as u8];
+
+        let (_, bump) = client::find_mint_config_address(&mint);
+        let mint_config_data = MintConfig {
+            mint: mint.to_bytes(),
+            bump: [bump],
+            min_lock_duration: 0i64.to_le_bytes(),
+            max_lock_duration: 0i64.to_le_bytes(),
+            listed: [1],
+        };
+
+        process_init_spl_vault(
+            &mollusk,
+            (mint, mint_account),
+            &config_data,
+            Some(&mint_config_data),
+            &[Check::err(ProgramError::Custom(4))], // InvalidVaultMint
+        );
+    }
+
+    #[test]
+    fn init_spl_vault_fail_with_deny_listed_mint_at_non_canonical_bump() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+
+        let mut config_data = get_config_data(&Pubkey::new_unique());
+        config_data.mint_list_mode = [MintListMode::DenyList as u8];
+        let treasury_ata_account = create_account_for_token_account(TokenAccount {
+            mint,
+            owner: TREASURY,
+            amount: 0,
+            delegate: None.into(),
+            state: AccountState::Initialized,
+            is_native: None.into(),
+            delegated_amount: 0,
+            close_authority: None.into(),
+        });
+
+        // the listed mint config is left out for an empty one at another bump
+        let (mint_config, bump) = non_canonical_address(&[MintConfig::SEED, mint.as_ref()]);
+        process_init_spl_vault_with(
+            &mollusk,
+            (mint, mint_account),
+            &config_data,
+            None,
+            treasury_ata_account,
+            |instruction| {
+                instruction.accounts[12].pubkey = mint_config;
+                instruction.data[21] = bump;
+            },
+            &[Check::err(ProgramError::Custom(22))], // NonCanonicalBump
+        );
+    }
+
+    #[test]
+    fn add_mint_to_list_successfully() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let admin = Pubkey::new_from_array([0x04; 32]);
+        let admin_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+
+        let (config_address, config_account) = get_config(&mollusk, &get_config_data(&admin));
+        let (mint_config_address, bump) = client::find_mint_config_address(&mint);
+
+        let expected_mint_config = MintConfig {
+            mint: mint.to_bytes(),
+            bump: [bump],
+            min_lock_duration: 0i64.to_le_bytes(),
+            max_lock_duration: 0i64.to_le_bytes(),
+            listed: [1],
+        };
+
+        let instruction = client::add_mint_to_list(&admin, &mint);
+
+        let _: InstructionResult = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (admin, admin_account),
+                (config_address, config_account.into()),
+                (mint_config_address, Account::new(0, 0, &system_program)),
+                (mint, mint_account),
+                (system_program, system_account),
+            ],
+            &[
+                Check::success(),
+                Check::account(&mint_config_address)
+                    .owner(&PROGRAM_ID)
+                    .data(unsafe { to_bytes::<MintConfig>(&expected_mint_config) })
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn set_mint_list_mode_fail_with_invalid_mode() {
+        let (mollusk, _, _) = get_mollusk();
+
+        let admin = Pubkey::new_from_array([0x04; 32]);
+        let admin_account = Account::new(LAMPORTS_PER_SOL, 0, &Pubkey::default());
+
+        let (config_address, config_account) = get_config(&mollusk, &get_config_data(&admin));
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[*SetMintListMode::DISCRIMINATOR, 3],
+            vec![
+                AccountMeta::new(admin, true),
+                AccountMeta::new(config_address, false),
+            ],
+        );
+
+        let _: InstructionResult = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (admin, admin_account),
+                (config_address, config_account.into()),
+            ],
+            &[Check::err(ProgramError::InvalidInstructionData)],
+        );
+    }
+
+    fn process_batch_init_spl_vault(
+        mollusk: &Mollusk,
+        (mint, mint_account): (Pubkey, Account),
+        vaults: &[client::BatchVault],
+        checks: &[Check],
+    ) -> InstructionResult {
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+        let (token_program, token_program_account) =
+            mollusk_svm_programs_token::token::keyed_account();
+        let (associated_token_program, associated_token_program_account) =
+            mollusk_svm_programs_token::associated_token::keyed_account();
+
+        let funder = Pubkey::new_from_array([0x02; 32]);
+        let funder_ata_account = create_account_for_token_account(TokenAccount {
+            mint,
+            owner: funder,
+            amount: 10_000_000_000,
+            delegate: None.into(),
+            state: AccountState::Initialized,
+            is_native: None.into(),
+            delegated_amount: 0,
+            close_authority: None.into(),
+        });
+        let treasury_ata_account = create_account_for_token_account(TokenAccount {
+            mint,
+            owner: TREASURY,
+            amount: 0,
+            delegate: None.into(),
+            state: AccountState::Initialized,
+            is_native: None.into(),
+            delegated_amount: 0,
+            close_authority: None.into(),
+        });
+        let (config_address, config_account) =
+            get_config(mollusk, &get_config_data(&Pubkey::new_unique()));
+
+        let instruction = client::batch_init_spl_vault(&funder, &mint, vaults, 0, &TREASURY);
+
+        // the vault, vault ATA and registry accounts do not exist yet
+        let mut accounts: Vec<(Pubkey, Account)> = vec![
+            (
+                funder,
+                Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program),
+            ),
+            (mint, mint_account),
+            (
+                get_associated_token_address(&funder, &mint),
+                funder_ata_account,
+            ),
+            (token_program, token_program_account),
+            (associated_token_program, associated_token_program_account),
+            (system_program, system_account),
+            (config_address, config_account.into()),
+            (TREASURY, Account::new(0, 0, &system_program)),
+            (
+                get_associated_token_address(&TREASURY, &mint),
+                treasury_ata_account,
+            ),
+            (
+                client::find_mint_config_address(&mint).0,
+                Account::new(0, 0, &system_program),
+            ),
+            (EVENT_AUTHORITY_ID, Account::default()),
+            (
+                PROGRAM_ID,
+                mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+            ),
+        ];
+        for meta in &instruction.accounts[accounts.len()..] {
+            accounts.push((meta.pubkey, Account::new(0, 0, &system_program)));
+        }
+
+        mollusk.process_and_validate_instruction(&instruction, &accounts, checks)
+    }
+
+    fn batch_vaults(mollusk: &Mollusk, count: usize) -> Vec<client::BatchVault> {
+        (0..count)
+            .map(|i| client::BatchVault {
+                beneficiary: Pubkey::new_unique(),
+                amount: 1_000_000 * (i as u64 + 1),
+                unlock_timestamp: mollusk.sysvars.clock.unix_timestamp + 3600,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn batch_init_spl_vault_successfully() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        let vaults = batch_vaults(&mollusk, 3);
+
+        let expected_vaults: Vec<(Pubkey, Pubkey, u64)> = vaults
+            .iter()
+            .map(|entry| {
+                let (vault, _) = client::find_vault_address(
+                    &entry.beneficiary,
+                    Some(&mint),
+                    entry.amount,
+                    entry.unlock_timestamp,
+                );
+                (vault, entry.beneficiary, entry.amount)
+            })
+            .collect();
+        let mut checks = vec![Check::success()];
+        for (vault, _, _) in &expected_vaults {
+            checks.push(Check::account(vault).owner(&PROGRAM_ID).build());
+        }
+
+        let result = process_batch_init_spl_vault(&mollusk, (mint, mint_account), &vaults, &checks);
+
+        for (vault, beneficiary, amount) in expected_vaults {
+            let data = result.get_account(&vault).unwrap().data();
+            let decoded = client::VaultAccount::decode(data).unwrap();
+            assert_eq!(decoded.owner, beneficiary);
+            assert_eq!(decoded.amount, amount);
+            assert_eq!(decoded.mint, Some(mint));
+            assert!(!decoded.tracked);
+
+            // the beneficiary did not sign, so the vault is not counted
+            let registry = client::find_registry_address(&beneficiary).0;
+            let data = result.get_account(&registry).unwrap().data();
+            let registry = unsafe { &*(data.as_ptr() as *const UserRegistry) };
+            assert_eq!(u64::from_le_bytes(registry.live_vaults), 1);
+            assert!(registry
+                .mint_totals
+                .iter()
+                .all(|total| u64::from_le_bytes(total.amount) == 0));
+
+            let vault_ata = result
+                .get_account(&get_associated_token_address(&vault, &mint))
+                .unwrap();
+            let vault_ata = TokenAccount::unpack(vault_ata.data()).unwrap();
+            assert_eq!(vault_ata.amount, amount);
+        }
+    }
+
+    #[test]
+    fn batch_init_spl_vault_fits_a_versioned_transaction() {
+        // the account locks a transaction may take on mainnet
+        const ACCOUNT_LOCK_LIMIT: usize = 64;
+        let funder = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let compute_budget = Pubkey::from_str_const("ComputeBudget111111111111111111111111111111");
+        // SetComputeUnitLimit, as a full batch needs more than the default,
+        // and SetComputeUnitPrice, to land the batch under load
+        let compute_unit_limit = Instruction::new_with_bytes(
+            compute_budget,
+            &[&[2], &1_400_000u32.to_le_bytes()[..]].concat(),
+            vec![],
+        );
+        let compute_unit_price = Instruction::new_with_bytes(
+            compute_budget,
+            &[&[3], &10_000u64.to_le_bytes()[..]].concat(),
+            vec![],
+        );
+        // (transaction bytes, account locks) of a batch with every account
+        // but the signer and the invoked programs in one lookup table
+        let transaction = |count: usize| {
+            let vaults: Vec<_> = (0..count)
+                .map(|_| client::BatchVault {
+                    beneficiary: Pubkey::new_unique(),
+                    amount: 1_000_000,
+                    unlock_timestamp: 1_757_636_943,
+                })
+                .collect();
+            let instruction = client::batch_init_spl_vault(&funder, &mint, &vaults, 0, &TREASURY);
+            let lookup_table = AddressLookupTableAccount {
+                key: Pubkey::new_unique(),
+                addresses: instruction
+                    .accounts
+                    .iter()
+                    .map(|meta| meta.pubkey)
+                    .filter(|key| *key != funder && *key != PROGRAM_ID)
+                    .collect(),
+            };
+            let message = v0::Message::try_compile(
+                &funder,
+                &[
+                    compute_unit_limit.clone(),
+                    compute_unit_price.clone(),
+                    instruction,
+                ],
+                &[lookup_table],
+                Hash::default(),
+            )
+            .unwrap();
+            let locks = message.account_keys.len()
+                + message
+                    .address_table_lookups
+                    .iter()
+                    .map(|lookup| lookup.writable_indexes.len() + lookup.readonly_indexes.len())
+                    .sum::<usize>();
+            // one signature and its length prefix
+            let len = 1 + 64 + VersionedMessage::V0(message).serialize().len();
+            (len, locks)
+        };
+
+        assert_eq!(transaction(1), (339, 16));
+        // a full batch fits the packet with both compute budget instructions
+        assert_eq!(transaction(MAX_BATCH_SIZE), (1_180, 61));
+        // another vault would still fit the account locks, not the packet
+        let (len, locks) = transaction(MAX_BATCH_SIZE + 1);
+        assert_eq!(locks, ACCOUNT_LOCK_LIMIT);
+        assert!(len > PACKET_DATA_SIZE);
+    }
+
+    #[test]
+    fn batch_init_spl_vault_compute_units() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        let vaults = batch_vaults(&mollusk, MAX_BATCH_SIZE);
+
+        let result = process_batch_init_spl_vault(
+            &mollusk,
+            (mint, mint_account),
+            &vaults,
+            &[Check::success()],
+        );
+
+        // the compute unit limit the batch is sent with, the most a
+        // transaction can request
+        const COMPUTE_UNIT_BUDGET: u64 = 1_400_000;
+        assert!(
+            result.compute_units_consumed <= COMPUTE_UNIT_BUDGET,
+            "a batch of {MAX_BATCH_SIZE} vaults takes {} compute units",
+            result.compute_units_consumed
+        );
+    }
+
+    #[test]
+    fn batch_init_spl_vault_fail_with_invalid_batch_size() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+
+        process_batch_init_spl_vault(
+            &mollusk,
+            (mint, mint_account.clone()),
+            &[],
+            &[Check::err(ProgramError::Custom(14))], // InvalidBatchSize
+        );
+        process_batch_init_spl_vault(
+            &mollusk,
+            (mint, mint_account),
+            &batch_vaults(&mollusk, MAX_BATCH_SIZE + 1),
+            &[Check::err(ProgramError::Custom(14))], // InvalidBatchSize
+        );
+    }
+
+    fn process_batch_withdraw(skip_locked: bool, paused: bool) {
+        let (mut mollusk, mint, mint_account) = get_mollusk();
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+        let (token_program, token_program_account) =
+            mollusk_svm_programs_token::token::keyed_account();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let now = mollusk.sysvars.clock.unix_timestamp;
+        let rent = mollusk.sysvars.rent.minimum_balance(Vault::LEN);
+        let amount = LAMPORTS_PER_SOL;
+
+        let vault_account = |mint: Option<&Pubkey>, unlock_timestamp: i64| {
+            let (address, _) = client::find_vault_address(&maker, mint, amount, unlock_timestamp);
+            let lamports = if mint.is_some() { rent } else { rent + amount };
+            let mut account = Account::new(lamports, Vault::LEN, &PROGRAM_ID);
+            account.data = vault_bytes(&maker, mint, unlock_timestamp);
+            (address, account)
+        };
+        let token_account = |owner: Pubkey, amount: u64| {
+            create_account_for_token_account(TokenAccount {
+                mint,
+                owner,
+                amount,
+                delegate: None.into(),
+                state: AccountState::Initialized,
+                is_native: None.into(),
+                delegated_amount: 0,
+                close_authority: None.into(),
+            })
+        };
+
+        let (sol_vault, sol_vault_account) = vault_account(None, now - 10);
+        let (locked_vault, locked_vault_account) = vault_account(None, now + 3600);
+        let (spl_vault, spl_vault_account) = vault_account(Some(&mint), now - 10);
+
+        let (registry_address, _, mut registry_account) =
+            get_registry(&mollusk, &maker, Some((None, 2 * amount)));
+        {
+            let mut data = registry_account.data().to_vec();
+            let registry = unsafe { &mut *(data.as_mut_ptr() as *mut UserRegistry) };
+            registry.live_vaults = 3u64.to_le_bytes();
+            registry.mint_totals[1] = MintTotal {
+                mint: Some(mint.to_bytes()),
+                amount: amount.to_le_bytes(),
+            };
+            registry_account.set_data_from_slice(&data);
+        }
+
+        let instruction = client::batch_withdraw(
+            &maker,
+            &[
+                (sol_vault, None),
+                (locked_vault, None),
+                (spl_vault, Some(mint)),
+            ],
+            skip_locked,
+        );
+        mollusk.sysvars.clock.unix_timestamp = now;
+
+        let accounts = [
+            (maker, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (registry_address, registry_account.into()),
+            (token_program, token_program_account),
+            (EVENT_AUTHORITY_ID, Account::default()),
+            (
+                PROGRAM_ID,
+                mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+            ),
+            (sol_vault, sol_vault_account),
+            (locked_vault, locked_vault_account),
+            (spl_vault, spl_vault_account),
+            (mint, mint_account),
+            (
+                get_associated_token_address(&maker, &mint),
+                token_account(maker, 0),
+            ),
+            (
+                get_associated_token_address(&spl_vault, &mint),
+                token_account(spl_vault, amount),
+            ),
+        ];
+        let checks = if skip_locked {
+            vec![
+                Check::success(),
+                Check::account(&sol_vault).lamports(0).build(),
+                Check::account(&spl_vault).lamports(0).build(),
+                Check::account(&locked_vault)
+                    .lamports(rent + amount)
+                    .owner(&PROGRAM_ID)
+                    .build(),
+            ]
+        } else {
+            vec![Check::err(ProgramError::Custom(3))] // VaultLocking
+        };
+
+        if paused {
+            process_after_pause(&mollusk, &instruction, &accounts, &checks);
+        } else {
+            mollusk.process_and_validate_instruction(&instruction, &accounts, &checks);
+        }
+    }
+
+    #[test]
+    fn batch_withdraw_skips_locked_vaults() {
+        process_batch_withdraw(true, false);
+    }
+
+    #[test]
+    fn batch_withdraw_fail_with_locked_vault() {
+        process_batch_withdraw(false, false);
+    }
+
+    #[test]
+    fn batch_withdraw_when_paused() {
+        process_batch_withdraw(true, true);
+    }
+
+    fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Account {
+        create_account_for_token_account(TokenAccount {
+            mint,
+            owner,
+            amount,
+            delegate: None.into(),
+            state: AccountState::Initialized,
+            is_native: None.into(),
+            delegated_amount: 0,
+            close_authority: None.into(),
+        })
+    }
+
+    fn merkle_leaves(mollusk: &Mollusk) -> Vec<client::MerkleLeaf> {
+        let now = mollusk.sysvars.clock.unix_timestamp;
+        vec![
+            client::MerkleLeaf {
+                recipient: Pubkey::new_from_array([0x03; 32]),
+                amount: 100_000,
+                unlock_timestamp: now - 10,
+            },
+            client::MerkleLeaf {
+                recipient: Pubkey::new_from_array([0x04; 32]),
+                amount: 200_000,
+                unlock_timestamp: now + 3600,
+            },
+            client::MerkleLeaf {
+                recipient: Pubkey::new_from_array([0x05; 32]),
+                amount: 300_000,
+                unlock_timestamp: now - 10,
+            },
+        ]
+    }
+
+    /// Creates the merkle vault of [`merkle_leaves`] under a config with a
+    /// max lock duration of `max_lock_duration`, reclaimable `reclaim_delay`
+    /// seconds after its creation or never if `None`.
+    fn process_init_merkle_vault(
+        max_lock_duration: i64,
+        reclaim_delay: Option<i64>,
+        checks: &[Check],
+    ) -> InstructionResult {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+        let (token_program, token_program_account) =
+            mollusk_svm_programs_token::token::keyed_account();
+        let (associated_token_program, associated_token_program_account) =
+            mollusk_svm_programs_token::associated_token::keyed_account();
+
+        let funder = Pubkey::new_from_array([0x02; 32]);
+        let root = client::MerkleTree::new(&merkle_leaves(&mollusk)).root();
+        let total = 600_000;
+        let (merkle_vault, _) = client::find_merkle_vault_address(&funder, &mint, &root);
+        let vault_ata = get_associated_token_address(&merkle_vault, &mint);
+
+        let now = mollusk.sysvars.clock.unix_timestamp;
+        let mut config_data = get_config_data(&Pubkey::new_unique());
+        config_data.max_lock_duration = max_lock_duration.to_le_bytes();
+        let (config_address, config_account) = get_config(&mollusk, &config_data);
+        let reclaim_timestamp = reclaim_delay.map_or(0, |delay| now + delay);
+
+        let instruction =
+            client::init_merkle_vault(&funder, &mint, &root, total, reclaim_timestamp, &TREASURY);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (
+                    funder,
+                    Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program),
+                ),
+                (merkle_vault, Account::new(0, 0, &system_program)),
+                (mint, mint_account),
+                (
+                    get_associated_token_address(&funder, &mint),
+                    token_account(mint, funder, total),
+                ),
+                (vault_ata, Account::new(0, 0, &system_program)),
+                (token_program, token_program_account),
+                (associated_token_program, associated_token_program_account),
+                (system_program, system_account),
+                (config_address, config_account.into()),
+                (TREASURY, Account::new(0, 0, &system_program)),
+                (
+                    get_associated_token_address(&TREASURY, &mint),
+                    token_account(mint, TREASURY, 0),
+                ),
+                (
+                    client::find_mint_config_address(&mint).0,
+                    Account::new(0, 0, &system_program),
+                ),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            checks,
+        )
+    }
+
+    #[test]
+    fn init_merkle_vault_successfully() {
+        let (mollusk, mint, _) = get_mollusk();
+        let funder = Pubkey::new_from_array([0x02; 32]);
+        let root = client::MerkleTree::new(&merkle_leaves(&mollusk)).root();
+        let total = 600_000;
+        let (merkle_vault, _) = client::find_merkle_vault_address(&funder, &mint, &root);
+        let vault_ata = get_associated_token_address(&merkle_vault, &mint);
+        let now = mollusk.sysvars.clock.unix_timestamp;
+        let reclaim_delay = 86_400 + MerkleVault::CLAIM_WINDOW;
+
+        let result = process_init_merkle_vault(
+            86_400,
+            Some(reclaim_delay),
+            &[
+                Check::success(),
+                Check::account(&merkle_vault).owner(&PROGRAM_ID).build(),
+            ],
+        );
+
+        let data = result.get_account(&merkle_vault).unwrap().data();
+        let state = unsafe { &*(data.as_ptr() as *const MerkleVault) };
+        assert_eq!(state.discriminator, [MerkleVault::DISCRIMINATOR]);
+        assert_eq!(state.funder, funder.to_bytes());
+        assert_eq!(state.root, root);
+        assert_eq!(u64::from_le_bytes(state.total), total);
+        assert_eq!(u64::from_le_bytes(state.claimed), 0);
+        assert_eq!(i64::from_le_bytes(state.created_at), now);
+        assert_eq!(i64::from_le_bytes(state.min_lock_duration), 0);
+        assert_eq!(i64::from_le_bytes(state.max_lock_duration), 86_400);
+        assert_eq!(
+            i64::from_le_bytes(state.reclaim_timestamp),
+            now + reclaim_delay
+        );
+
+        let vault_ata = result.get_account(&vault_ata).unwrap();
+        assert_eq!(
+            TokenAccount::unpack(vault_ata.data()).unwrap().amount,
+            total
+        );
+    }
+
+    #[test]
+    fn init_merkle_vault_without_reclaim() {
+        process_init_merkle_vault(0, None, &[Check::success()]);
+    }
+
+    #[test]
+    fn init_merkle_vault_fail_with_reclaim_within_claim_window() {
+        process_init_merkle_vault(
+            86_400,
+            Some(86_400 + MerkleVault::CLAIM_WINDOW - 1),
+            &[Check::err(ProgramError::Custom(23))], // InvalidReclaimTimestamp
+        );
+    }
+
+    #[test]
+    fn init_merkle_vault_fail_with_reclaim_without_max_lock_duration() {
+        process_init_merkle_vault(
+            0,
+            Some(MerkleVault::CLAIM_WINDOW),
+            &[Check::err(ProgramError::Custom(23))], // InvalidReclaimTimestamp
+        );
+    }
+
+    #[test]
+    fn merkle_vault_reclaim_timestamp_leaves_claim_window() {
+        let window = MerkleVault::CLAIM_WINDOW;
+        assert!(MerkleVault::check_reclaim_timestamp(1_000, 3600, 0).is_ok());
+        assert!(MerkleVault::check_reclaim_timestamp(1_000, 3600, 4_600 + window).is_ok());
+        assert_eq!(
+            MerkleVault::check_reclaim_timestamp(1_000, 3600, 4_599 + window).map_err(u64::from),
+            Err(23)
+        );
+        assert_eq!(
+            MerkleVault::check_reclaim_timestamp(1_000, 0, i64::MAX).map_err(u64::from),
+            Err(23)
+        );
+    }
+
+    /// Claims the leaf at `index` of [`merkle_leaves`] with `amount` instead
+    /// of the leaf amount if given, with the receipt already created if
+    /// `claimed`.
+    fn process_claim_merkle_vault(
+        mollusk: &Mollusk,
+        (mint, mint_account): (Pubkey, Account),
+        index: usize,
+        amount: Option<u64>,
+        claimed: bool,
+        checks: &[Check],
+    ) -> InstructionResult {
+        process_claim_merkle_vault_with(
+            mollusk,
+            (mint, mint_account),
+            index,
+            amount,
+            claimed,
+            |_| {},
+            |_, _| {},
+            checks,
+        )
+    }
+
+    /// Returns the state of the merkle vault of [`merkle_leaves`] created an
+    /// hour ago by `funder`, with its address.
+    fn merkle_vault_state(
+        mollusk: &Mollusk,
+        funder: &Pubkey,
+        mint: &Pubkey,
+    ) -> (Pubkey, MerkleVault) {
+        let leaves = merkle_leaves(mollusk);
+        let root = client::MerkleTree::new(&leaves).root();
+        let total: u64 = leaves.iter().map(|leaf| leaf.amount).sum();
+        let (merkle_vault, bump) = client::find_merkle_vault_address(funder, mint, &root);
+        let now = mollusk.sysvars.clock.unix_timestamp;
+
+        let state = MerkleVault {
+            discriminator: [MerkleVault::DISCRIMINATOR],
+            funder: funder.to_bytes(),
+            mint: mint.to_bytes(),
+            root,
+            total: total.to_le_bytes(),
+            claimed: 0u64.to_le_bytes(),
+            created_at: (now - 3600).to_le_bytes(),
+            min_lock_duration: 0i64.to_le_bytes(),
+            max_lock_duration: 86_400i64.to_le_bytes(),
+            reclaim_timestamp: (now - 3600 + 86_400 + MerkleVault::CLAIM_WINDOW).to_le_bytes(),
+            bump: [bump],
+        };
+
+        (merkle_vault, state)
+    }
+
+    fn merkle_vault_account(mollusk: &Mollusk, state: &MerkleVault) -> Account {
+        let mut account = Account::new(
+            mollusk.sysvars.rent.minimum_balance(MerkleVault::LEN),
+            MerkleVault::LEN,
+            &PROGRAM_ID,
+        );
+        account.data = unsafe { to_bytes(state) }.to_vec();
+        account
+    }
+
+    /// Same as [`process_claim_merkle_vault`] with the instruction changed by
+    /// `edit` and the merkle vault state and receipt account by `setup`,
+    /// where `claimed` marks the receipt at the canonical bump.
+    #[allow(clippy::too_many_arguments)]
+    fn process_claim_merkle_vault_with(
+        mollusk: &Mollusk,
+        (mint, mint_account): (Pubkey, Account),
+        index: usize,
+        amount: Option<u64>,
+        claimed: bool,
+        edit: impl FnOnce(&mut Instruction),
+        setup: impl FnOnce(&mut MerkleVault, &mut Account),
+        checks: &[Check],
+    ) -> InstructionResult {
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+        let (token_program, token_program_account) =
+            mollusk_svm_programs_token::token::keyed_account();
+
+        let funder = Pubkey::new_from_array([0x02; 32]);
+        let leaves = merkle_leaves(mollusk);
+        let tree = client::MerkleTree::new(&leaves);
+        let (merkle_vault, mut state) = merkle_vault_state(mollusk, &funder, &mint);
+        let total = u64::from_le_bytes(state.total);
+
+        let leaf = &leaves[index];
+        let mut instruction = client::claim_merkle_vault(
+            &leaf.recipient,
+            &merkle_vault,
+            &mint,
+            amount.unwrap_or(leaf.amount),
+            leaf.unlock_timestamp,
+            &tree.proof(index),
+        );
+        let (canonical_receipt, _) =
+            client::find_claim_receipt_address(&merkle_vault, &leaf.hash());
+        edit(&mut instruction);
+        let claim_receipt = instruction.accounts[5].pubkey;
+        let mut receipt_account = if claimed && claim_receipt == canonical_receipt {
+            Account::new(
+                mollusk.sysvars.rent.minimum_balance(ClaimReceipt::LEN),
+                ClaimReceipt::LEN,
+                &PROGRAM_ID,
+            )
+        } else {
+            Account::new(0, 0, &system_program)
+        };
+        setup(&mut state, &mut receipt_account);
+        let merkle_vault_account = merkle_vault_account(mollusk, &state);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (
+                    leaf.recipient,
+                    Account::new(LAMPORTS_PER_SOL, 0, &system_program),
+                ),
+                (merkle_vault, merkle_vault_account),
+                (mint, mint_account),
+                (
+                    get_associated_token_address(&leaf.recipient, &mint),
+                    token_account(mint, leaf.recipient, 0),
+                ),
+                (
+                    get_associated_token_address(&merkle_vault, &mint),
+                    token_account(mint, merkle_vault, total),
+                ),
+                (claim_receipt, receipt_account),
+                (token_program, token_program_account),
+                (system_program, system_account),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            checks,
+        )
+    }
+
+    #[test]
+    fn claim_merkle_vault_successfully() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        let leaves = merkle_leaves(&mollusk);
+        let leaf = &leaves[2];
+        let root = client::MerkleTree::new(&leaves).root();
+        let funder = Pubkey::new_from_array([0x02; 32]);
+        let (merkle_vault, _) = client::find_merkle_vault_address(&funder, &mint, &root);
+        let (claim_receipt, _) = client::find_claim_receipt_address(&merkle_vault, &leaf.hash());
+
+        let result = process_claim_merkle_vault(
+            &mollusk,
+            (mint, mint_account),
+            2,
+            None,
+            false,
+            &[
+                Check::success(),
+                Check::account(&claim_receipt).owner(&PROGRAM_ID).build(),
+            ],
+        );
+
+        let recipient_ata = result
+            .get_account(&get_associated_token_address(&leaf.recipient, &mint))
+            .unwrap();
+        assert_eq!(
+            TokenAccount::unpack(recipient_ata.data()).unwrap().amount,
+            leaf.amount
+        );
+
+        let data = result.get_account(&merkle_vault).unwrap().data();
+        let state = unsafe { &*(data.as_ptr() as *const MerkleVault) };
+        assert_eq!(u64::from_le_bytes(state.claimed), leaf.amount);
+
+        let data = result.get_account(&claim_receipt).unwrap().data();
+        let receipt = unsafe { &*(data.as_ptr() as *const ClaimReceipt) };
+        assert_eq!(receipt.discriminator, [ClaimReceipt::DISCRIMINATOR]);
+    }
+
+    #[test]
+    fn claim_merkle_vault_fail_with_locked_leaf() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        process_claim_merkle_vault(
+            &mollusk,
+            (mint, mint_account),
+            1,
+            None,
+            false,
+            &[Check::err(ProgramError::Custom(3))], // VaultLocking
+        );
+    }
+
+    #[test]
+    fn claim_merkle_vault_fail_with_invalid_proof() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        process_claim_merkle_vault(
+            &mollusk,
+            (mint, mint_account),
+            0,
+            Some(600_000),
+            false,
+            &[Check::err(ProgramError::Custom(15))], // InvalidMerkleProof
+        );
+    }
+
+    #[test]
+    fn claim_merkle_vault_fail_when_already_claimed() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        process_claim_merkle_vault(
+            &mollusk,
+            (mint, mint_account),
+            0,
+            None,
+            true,
+            &[Check::err(ProgramError::Custom(16))], // AlreadyClaimed
+        );
+    }
+
+    #[test]
+    fn claim_merkle_vault_fail_when_claimed_again_at_another_bump() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        let leaves = merkle_leaves(&mollusk);
+        let root = client::MerkleTree::new(&leaves).root();
+        let funder = Pubkey::new_from_array([0x02; 32]);
+        let (merkle_vault, _) = client::find_merkle_vault_address(&funder, &mint, &root);
+        let (claim_receipt, bump) =
+            non_canonical_address(&[ClaimReceipt::SEED, merkle_vault.as_ref(), &leaves[0].hash()]);
+
+        // the receipt of the first claim is left out for an empty one at
+        // another bump
+        process_claim_merkle_vault_with(
+            &mollusk,
+            (mint, mint_account),
+            0,
+            None,
+            true,
+            |instruction| {
+                instruction.accounts[5].pubkey = claim_receipt;
+                instruction.data[17] = bump;
+            },
+            |_, _| {},
+            &[Check::err(ProgramError::Custom(22))], // NonCanonicalBump
+        );
+    }
+
+    #[test]
+    fn claim_merkle_vault_fail_without_merkle_vault_discriminator() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        process_claim_merkle_vault_with(
+            &mollusk,
+            (mint, mint_account),
+            0,
+            None,
+            false,
+            |_| {},
+            |state, _| state.discriminator = [0],
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    #[test]
+    fn claim_merkle_vault_with_prefunded_receipt() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        let leaves = merkle_leaves(&mollusk);
+        let funder = Pubkey::new_from_array([0x02; 32]);
+        let (merkle_vault, _) = merkle_vault_state(&mollusk, &funder, &mint);
+        let (claim_receipt, _) =
+            client::find_claim_receipt_address(&merkle_vault, &leaves[0].hash());
+
+        // anyone can send lamports to the receipt address of a public leaf
+        process_claim_merkle_vault_with(
+            &mollusk,
+            (mint, mint_account),
+            0,
+            None,
+            false,
+            |_| {},
+            |_, receipt| receipt.lamports = 1_000,
+            &[
+                Check::success(),
+                Check::account(&claim_receipt)
+                    .owner(&PROGRAM_ID)
+                    .lamports(mollusk.sysvars.rent.minimum_balance(ClaimReceipt::LEN))
+                    .build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn claim_merkle_vault_fail_with_lock_too_long() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+
+        // the leaf unlocks 3590 seconds after the vault was created
+        process_claim_merkle_vault_with(
+            &mollusk,
+            (mint, mint_account),
+            0,
+            None,
+            false,
+            |_| {},
+            |state, _| state.max_lock_duration = 1800i64.to_le_bytes(),
+            &[Check::err(ProgramError::Custom(13))], // LockDurationTooLong
+        );
+    }
+
+    #[test]
+    fn claim_merkle_vault_fail_with_lock_too_short() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        process_claim_merkle_vault_with(
+            &mollusk,
+            (mint, mint_account),
+            0,
+            None,
+            false,
+            |_| {},
+            |state, _| state.min_lock_duration = 7200i64.to_le_bytes(),
+            &[Check::err(ProgramError::Custom(12))], // LockDurationTooShort
+        );
+    }
+
+    /// Reclaims the merkle vault of [`merkle_leaves`] with `claimed` tokens
+    /// already claimed, `delay` seconds after its reclaim timestamp.
+    fn process_reclaim_merkle_vault(
+        claimed: u64,
+        delay: i64,
+        checks: &[Check],
+    ) -> InstructionResult {
+        process_reclaim_merkle_vault_with(claimed, delay, |_| {}, checks)
+    }
+
+    /// Same as [`process_reclaim_merkle_vault`] with the merkle vault state
+    /// changed by `setup` once the clock is set.
+    fn process_reclaim_merkle_vault_with(
+        claimed: u64,
+        delay: i64,
+        setup: impl FnOnce(&mut MerkleVault),
+        checks: &[Check],
+    ) -> InstructionResult {
+        let (mut mollusk, mint, mint_account) = get_mollusk();
+        let (token_program, token_program_account) =
+            mollusk_svm_programs_token::token::keyed_account();
+
+        let funder = Pubkey::new_from_array([0x02; 32]);
+        let (merkle_vault, mut state) = merkle_vault_state(&mollusk, &funder, &mint);
+        state.claimed = claimed.to_le_bytes();
+        let total = u64::from_le_bytes(state.total);
+        mollusk.sysvars.clock.unix_timestamp = i64::from_le_bytes(state.reclaim_timestamp) + delay;
+        setup(&mut state);
+
+        let instruction = client::reclaim_merkle_vault(&funder, &merkle_vault, &mint);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (
+                    funder,
+                    Account::new(LAMPORTS_PER_SOL, 0, &Pubkey::default()),
+                ),
+                (merkle_vault, merkle_vault_account(&mollusk, &state)),
+                (mint, mint_account),
+                (
+                    get_associated_token_address(&funder, &mint),
+                    token_account(mint, funder, 0),
+                ),
+                (
+                    get_associated_token_address(&merkle_vault, &mint),
+                    token_account(mint, merkle_vault, total - claimed),
+                ),
+                (token_program, token_program_account),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            checks,
+        )
+    }
+
+    #[test]
+    fn reclaim_merkle_vault_successfully() {
+        let (mollusk, mint, _) = get_mollusk();
+        let funder = Pubkey::new_from_array([0x02; 32]);
+        let (merkle_vault, state) = merkle_vault_state(&mollusk, &funder, &mint);
+        let total = u64::from_le_bytes(state.total);
+
+        let result = process_reclaim_merkle_vault(100_000, 0, &[Check::success()]);
+
+        let funder_ata = result
+            .get_account(&get_associated_token_address(&funder, &mint))
+            .unwrap();
+        assert_eq!(
+            TokenAccount::unpack(funder_ata.data()).unwrap().amount,
+            total - 100_000
+        );
+
+        // the leaves left are no longer claimable
+        let data = result.get_account(&merkle_vault).unwrap().data();
+        let state = unsafe { &*(data.as_ptr() as *const MerkleVault) };
+        assert_eq!(u64::from_le_bytes(state.claimed), total);
+    }
+
+    #[test]
+    fn reclaim_merkle_vault_fail_before_reclaim_timestamp() {
+        process_reclaim_merkle_vault(
+            0,
+            -1,
+            &[Check::err(ProgramError::Custom(3))], // VaultLocking
+        );
+    }
+
+    #[test]
+    fn reclaim_merkle_vault_fail_without_reclaim_timestamp() {
+        process_reclaim_merkle_vault_with(
+            0,
+            0,
+            |state| state.reclaim_timestamp = 0i64.to_le_bytes(),
+            &[Check::err(ProgramError::Custom(23))], // InvalidReclaimTimestamp
+        );
+    }
+
+    #[test]
+    fn merkle_vault_address_is_canonical() {
+        let funder = Pubkey::new_from_array([0x02; 32]);
+        let mint = Pubkey::new_from_array([0x03; 32]);
+        let root = [0x08; 32];
+        let (merkle_vault, bump) = client::find_merkle_vault_address(&funder, &mint, &root);
+        assert!(MerkleVault::validate_pda(
+            merkle_vault.as_array(),
+            funder.as_array(),
+            mint.as_array(),
+            &root,
+            bump
+        )
+        .is_ok());
+
+        let (merkle_vault, bump) =
+            non_canonical_address(&[MerkleVault::SEED, funder.as_ref(), mint.as_ref(), &root]);
+        assert_eq!(
+            MerkleVault::validate_pda(
+                merkle_vault.as_array(),
+                funder.as_array(),
+                mint.as_array(),
+                &root,
+                bump
+            )
+            .map_err(u64::from),
+            Err(22) // NonCanonicalBump
+        );
+    }
+
+    #[test]
+    fn claim_receipt_address_is_canonical() {
+        let merkle_vault = Pubkey::new_from_array([0x05; 32]);
+        let leaf = [0x08; 32];
+        let (claim_receipt, bump) = client::find_claim_receipt_address(&merkle_vault, &leaf);
+        assert!(ClaimReceipt::validate_pda(
+            claim_receipt.as_array(),
+            merkle_vault.as_array(),
+            &leaf,
+            bump
+        )
+        .is_ok());
+
+        let (claim_receipt, bump) =
+            non_canonical_address(&[ClaimReceipt::SEED, merkle_vault.as_ref(), &leaf]);
+        assert_eq!(
+            ClaimReceipt::validate_pda(
+                claim_receipt.as_array(),
+                merkle_vault.as_array(),
+                &leaf,
+                bump
+            )
+            .map_err(u64::from),
+            Err(22) // NonCanonicalBump
+        );
+    }
+
+    #[test]
+    fn merkle_tree_proofs_verify() {
+        for count in 1..=9 {
+            let leaves: Vec<client::MerkleLeaf> = (0..count)
+                .map(|i| client::MerkleLeaf {
+                    recipient: Pubkey::new_unique(),
+                    amount: 1_000 * (i as u64 + 1),
+                    unlock_timestamp: i as i64,
+                })
+                .collect();
+            let tree = client::MerkleTree::new(&leaves);
+            let root = tree.root();
+
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = tree.proof(index);
+                assert!(MerkleVault::verify_proof(&root, leaf.hash(), &proof));
+
+                let forged = client::MerkleLeaf {
+                    amount: leaf.amount + 1,
+                    ..leaf.clone()
+                };
+                assert!(!MerkleVault::verify_proof(&root, forged.hash(), &proof));
+            }
+        }
+    }
+
+    /// Splits `split` lamports of a 1 SOL vault unlocking `lock` seconds
+    /// from now into a vault of a new owner unlocking `delay` seconds after it.
+    fn process_split_sol_vault(
+        lock: i64,
+        split: u64,
+        delay: i64,
+        config_data: &Config,
+        checks: &[Check],
+    ) -> InstructionResult {
+        let (mollusk, _, _) = get_mollusk();
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+        let (config, config_account) = get_config(&mollusk, config_data);
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let new_owner = Pubkey::new_from_array([0x03; 32]);
+        let amount = LAMPORTS_PER_SOL;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + lock;
+
+        let (vault_address, vault_account) =
+            get_sol_vault(&mollusk, &maker, amount, unlock_timestamp, 0);
+        let (registry_address, _, registry_account) =
+            get_registry(&mollusk, &maker, Some((None, amount)));
+
+        let instruction = client::split_vault(
+            &maker,
+            &vault_address,
+            None,
+            &new_owner,
+            split,
+            unlock_timestamp + delay,
+        );
+        let new_vault = instruction.accounts[2].pubkey;
+        let new_registry = instruction.accounts[5].pubkey;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (maker, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+                (vault_address, vault_account.into()),
+                (new_vault, Account::new(0, 0, &system_program)),
+                (new_owner, Account::new(0, 0, &system_program)),
+                (registry_address, registry_account.into()),
+                (new_registry, Account::new(0, 0, &system_program)),
+                (system_program, system_account),
+                (config, config_account.into()),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            checks,
+        )
+    }
+
+    #[test]
+    fn split_sol_vault_successfully() {
+        let (mollusk, _, _) = get_mollusk();
+        let rent = mollusk.sysvars.rent.minimum_balance(Vault::LEN);
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let new_owner = Pubkey::new_from_array([0x03; 32]);
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let split = LAMPORTS_PER_SOL / 4;
+
+        let (vault_address, _) =
+            client::find_vault_address(&maker, None, LAMPORTS_PER_SOL, unlock_timestamp);
+        let (new_vault, _) =
+            client::find_vault_address(&new_owner, None, split, unlock_timestamp + 60);
+        let (new_registry, _) = client::find_registry_address(&new_owner);
+
+        let result = process_split_sol_vault(
+            3600,
+            split,
+            60,
+            &get_config_data(&Pubkey::new_unique()),
+            &[
+                Check::success(),
+                Check::account(&vault_address)
+                    .lamports(rent + LAMPORTS_PER_SOL - split)
+                    .build(),
+                Check::account(&new_vault).lamports(rent + split).build(),
+                Check::account(&new_registry).owner(&PROGRAM_ID).build(),
+            ],
+        );
+
+        let data = result.get_account(&vault_address).unwrap().data();
+        let vault = client::VaultAccount::decode_at(&vault_address, data).unwrap();
+        assert_eq!(vault.amount, LAMPORTS_PER_SOL - split);
+        assert_eq!(vault.seed_amount, LAMPORTS_PER_SOL);
+        assert_eq!(vault.withdrawn, split);
+
+        let data = result.get_account(&new_vault).unwrap().data();
+        let vault = client::VaultAccount::decode_at(&new_vault, data).unwrap();
+        assert_eq!(vault.owner, new_owner);
+        assert_eq!(vault.amount, split);
+        assert_eq!(vault.unlock_timestamp, unlock_timestamp + 60);
+        assert!(!vault.tracked);
+        assert_eq!(vault.withdrawn, 0);
+
+        // the vault handed to the new owner stays out of their mint totals
+        let data = result.get_account(&new_registry).unwrap().data();
+        let registry = unsafe { &*(data.as_ptr() as *const UserRegistry) };
+        assert_eq!(u64::from_le_bytes(registry.live_vaults), 1);
+        assert!(registry
+            .mint_totals
+            .iter()
+            .all(|total| u64::from_le_bytes(total.amount) == 0));
+    }
+
+    #[test]
+    fn split_vault_fail_with_earlier_unlock() {
+        process_split_sol_vault(
+            3600,
+            LAMPORTS_PER_SOL / 4,
+            -1,
+            &get_config_data(&Pubkey::new_unique()),
+            &[Check::err(ProgramError::Custom(18))], // UnlockTimestampTooEarly
+        );
+    }
+
+    #[test]
+    fn split_vault_fail_when_paused() {
+        let mut config_data = get_config_data(&Pubkey::new_unique());
+        config_data.paused = [1];
+
+        process_split_sol_vault(
+            3600,
+            LAMPORTS_PER_SOL / 4,
+            0,
+            &config_data,
+            &[Check::err(ProgramError::Custom(7))], // ProgramPaused
+        );
+    }
+
+    #[test]
+    fn split_vault_fail_with_lock_too_long() {
+        // the vault unlocks in an hour, the new vault a minute after the max
+        let mut config_data = get_config_data(&Pubkey::new_unique());
+        config_data.max_lock_duration = 3600i64.to_le_bytes();
+
+        process_split_sol_vault(
+            3600,
+            LAMPORTS_PER_SOL / 4,
+            60,
+            &config_data,
+            &[Check::err(ProgramError::Custom(13))], // LockDurationTooLong
+        );
+    }
+
+    #[test]
+    fn split_vault_at_the_same_unlock_below_the_min_lock() {
+        // the vault unlocks in an hour, below a min lock raised since
+        let mut config_data = get_config_data(&Pubkey::new_unique());
+        config_data.min_lock_duration = 7200i64.to_le_bytes();
+
+        process_split_sol_vault(
+            3600,
+            LAMPORTS_PER_SOL / 4,
+            0,
+            &config_data,
+            &[Check::success()],
+        );
+        process_split_sol_vault(
+            3600,
+            LAMPORTS_PER_SOL / 4,
+            60,
+            &config_data,
+            &[Check::err(ProgramError::Custom(12))], // LockDurationTooShort
+        );
+    }
+
+    #[test]
+    fn split_unlocked_vault() {
+        // the vault unlocked an hour ago
+        process_split_sol_vault(
+            -3600,
+            LAMPORTS_PER_SOL / 4,
+            0,
+            &get_config_data(&Pubkey::new_unique()),
+            &[Check::success()],
+        );
+    }
+
+    #[test]
+    fn split_vault_fail_with_whole_amount() {
+        process_split_sol_vault(
+            3600,
+            LAMPORTS_PER_SOL,
+            0,
+            &get_config_data(&Pubkey::new_unique()),
+            &[Check::err(ProgramError::Custom(17))], // InvalidSplitAmount
+        );
+    }
+
+    /// Splits 400,000 tokens off an SPL vault of 1,000,000 into a vault of
+    /// the same owner, under `config_data` and the mint config
+    /// `mint_config_data` if the mint has one.
+    fn process_split_spl_vault(
+        config_data: &Config,
+        mint_config_data: Option<&MintConfig>,
+        checks: &[Check],
+    ) -> InstructionResult {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+        let (token_program, token_program_account) =
+            mollusk_svm_programs_token::token::keyed_account();
+        let (associated_token_program, associated_token_program_account) =
+            mollusk_svm_programs_token::associated_token::keyed_account();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let amount = 1_000_000u64;
+        let split = 400_000u64;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let (vault_address, bump) =
+            client::find_vault_address(&maker, Some(&mint), amount, unlock_timestamp);
+
+        let vault = Vault {
+            owner: maker.to_bytes(),
+            amount: amount.to_le_bytes(),
+            unlock_timestamp: unlock_timestamp.to_le_bytes(),
+            mint: Some(mint.to_bytes()),
+            bump: [bump],
+            id: [0; 8],
+            early_exit_penalty_bps: [0; 2],
+            seed_amount: amount.to_le_bytes(),
+            receipt_mint: None,
+            tracked: [1],
+            withdrawn: [0; 8],
+        };
+        let mut vault_account = Account::new(
+            mollusk.sysvars.rent.minimum_balance(Vault::LEN),
+            Vault::LEN,
+            &PROGRAM_ID,
+        );
+        vault_account.data = unsafe { to_bytes(&vault) }.to_vec();
+        let (registry_address, _, registry_account) =
+            get_registry(&mollusk, &maker, Some((Some(mint), amount)));
+
+        // split into a vault of the same owner, which shares the registry
+        let instruction = client::split_vault(
+            &maker,
+            &vault_address,
+            Some(&mint),
+            &maker,
+            split,
+            unlock_timestamp,
+        );
+        let new_vault = instruction.accounts[2].pubkey;
+        let vault_ata = get_associated_token_address(&vault_address, &mint);
+        let new_vault_ata = get_associated_token_address(&new_vault, &mint);
+        let (config, config_account) = get_config(&mollusk, config_data);
+        let (mint_config, _) = client::find_mint_config_address(&mint);
+        let mint_config_account = match mint_config_data {
+            Some(mint_config_data) => {
+                let mut mint_config_account = Account::new(
+                    mollusk.sysvars.rent.minimum_balance(MintConfig::LEN),
+                    MintConfig::LEN,
+                    &PROGRAM_ID,
+                );
+                mint_config_account.data =
+                    unsafe { to_bytes::<MintConfig>(mint_config_data) }.to_vec();
+                mint_config_account
+            }
+            None => Account::new(0, 0, &system_program),
+        };
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (maker, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+                (vault_address, vault_account),
+                (new_vault, Account::new(0, 0, &system_program)),
+                (registry_address, registry_account.into()),
+                (system_program, system_account),
+                (config, config_account.into()),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+                (mint, mint_account),
+                (vault_ata, token_account(mint, vault_address, amount)),
+                (new_vault_ata, Account::new(0, 0, &system_program)),
+                (token_program, token_program_account),
+                (associated_token_program, associated_token_program_account),
+                (mint_config, mint_config_account),
+            ],
+            checks,
+        )
+    }
+
+    #[test]
+    fn split_spl_vault_successfully() {
+        let (_, mint, _) = get_mollusk();
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let amount = 1_000_000u64;
+        let split = 400_000u64;
+        let unlock_timestamp = get_mollusk().0.sysvars.clock.unix_timestamp + 3600;
+        let (vault_address, _) =
+            client::find_vault_address(&maker, Some(&mint), amount, unlock_timestamp);
+        let (new_vault, _) =
+            client::find_vault_address(&maker, Some(&mint), split, unlock_timestamp);
+        let vault_ata = get_associated_token_address(&vault_address, &mint);
+        let new_vault_ata = get_associated_token_address(&new_vault, &mint);
+        let (registry_address, _) = client::find_registry_address(&maker);
+
+        let result = process_split_spl_vault(
+            &get_config_data(&Pubkey::new_unique()),
+            None,
+            &[Check::success()],
+        );
+
+        let token_amount = |address: &Pubkey| {
+            TokenAccount::unpack(result.get_account(address).unwrap().data())
+                .unwrap()
+                .amount
+        };
+        assert_eq!(token_amount(&vault_ata), amount - split);
+        assert_eq!(token_amount(&new_vault_ata), split);
+
+        let data = result.get_account(&registry_address).unwrap().data();
+        let registry = unsafe { &*(data.as_ptr() as *const UserRegistry) };
+        assert_eq!(u64::from_le_bytes(registry.live_vaults), 2);
+        assert_eq!(u64::from_le_bytes(registry.mint_totals[0].amount), amount);
+    }
+
+    #[test]
+    fn split_spl_vault_fail_with_deny_listed_mint() {
+        let (_, mint, _) = get_mollusk();
+
+        let mut config_data = get_config_data(&Pubkey::new_unique());
+        config_data.mint_list_mode = [MintListMode::DenyList as u8];
+        let (_, bump) = client::find_mint_config_address(&mint);
+        let mint_config_data = MintConfig {
+            mint: mint.to_bytes(),
+            bump: [bump],
+            min_lock_duration: 0i64.to_le_bytes(),
+            max_lock_duration: 0i64.to_le_bytes(),
+            listed: [1],
+        };
+
+        // a split creates a vault, so it can not move tokens of a denied mint
+        // into new vaults
+        process_split_spl_vault(
+            &config_data,
+            Some(&mint_config_data),
+            &[Check::err(ProgramError::Custom(4))], // InvalidVaultMint
+        );
+    }
+
+    /// Merges a 1 SOL vault unlocking in an hour into a 2 SOL vault
+    /// unlocking `delay` seconds after it, under `config_data`.
+    fn process_merge_sol_vaults(
+        delay: i64,
+        (source_penalty, destination_penalty): (u16, u16),
+        config_data: &Config,
+        checks: &[Check],
+    ) -> InstructionResult {
+        let (mollusk, _, _) = get_mollusk();
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+
+        let (source, source_account) = get_sol_vault(
+            &mollusk,
+            &maker,
+            LAMPORTS_PER_SOL,
+            unlock_timestamp,
+            source_penalty,
+        );
+        let (destination, destination_account) = get_sol_vault(
+            &mollusk,
+            &maker,
+            2 * LAMPORTS_PER_SOL,
+            unlock_timestamp + delay,
+            destination_penalty,
+        );
+        let (registry_address, _, registry_account) =
+            get_registry(&mollusk, &maker, Some((None, 3 * LAMPORTS_PER_SOL)));
+        let (config, config_account) = get_config(&mollusk, config_data);
+
+        mollusk.process_and_validate_instruction(
+            &client::merge_vaults(&maker, &source, &destination, None),
+            &[
+                (maker, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+                (source, source_account.into()),
+                (destination, destination_account.into()),
+                (registry_address, registry_account.into()),
+                (config, config_account.into()),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            checks,
+        )
+    }
+
+    #[test]
+    fn merge_sol_vaults_successfully() {
+        let (mollusk, _, _) = get_mollusk();
+        let rent = mollusk.sysvars.rent.minimum_balance(Vault::LEN);
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+
+        let (source, _) =
+            client::find_vault_address(&maker, None, LAMPORTS_PER_SOL, unlock_timestamp);
+        let (destination, _) =
+            client::find_vault_address(&maker, None, 2 * LAMPORTS_PER_SOL, unlock_timestamp + 60);
+        let (registry_address, _) = client::find_registry_address(&maker);
+
+        let result = process_merge_sol_vaults(
+            60,
+            (0, 0),
+            &get_config_data(&Pubkey::new_unique()),
+            &[
+                Check::success(),
+                Check::account(&maker)
+                    .lamports(LAMPORTS_PER_SOL + rent)
+                    .build(),
+                Check::account(&source).closed().build(),
+                Check::account(&destination)
+                    .lamports(rent + 3 * LAMPORTS_PER_SOL)
+                    .build(),
+            ],
+        );
+
+        let data = result.get_account(&destination).unwrap().data();
+        let vault = client::VaultAccount::decode_at(&destination, data).unwrap();
+        assert_eq!(vault.amount, 3 * LAMPORTS_PER_SOL);
+        assert_eq!(vault.seed_amount, 2 * LAMPORTS_PER_SOL);
+        assert_eq!(vault.unlock_timestamp, unlock_timestamp + 60);
+
+        let data = result.get_account(&registry_address).unwrap().data();
+        let registry = unsafe { &*(data.as_ptr() as *const UserRegistry) };
+        assert_eq!(u64::from_le_bytes(registry.live_vaults), 0);
+        assert_eq!(
+            u64::from_le_bytes(registry.mint_totals[0].amount),
+            3 * LAMPORTS_PER_SOL
+        );
+    }
+
+    #[test]
+    fn merge_vaults_fail_with_earlier_destination() {
+        process_merge_sol_vaults(
+            -1,
+            (0, 0),
+            &get_config_data(&Pubkey::new_unique()),
+            &[Check::err(ProgramError::Custom(18))], // UnlockTimestampTooEarly
+        );
+    }
+
+    #[test]
+    fn merge_vaults_fail_with_looser_penalty() {
+        process_merge_sol_vaults(
+            0,
+            (0, 500),
+            &get_config_data(&Pubkey::new_unique()),
+            &[Check::err(ProgramError::Custom(19))], // EarlyExitPenaltyMismatch
+        );
+        process_merge_sol_vaults(
+            0,
+            (1_000, 500),
+            &get_config_data(&Pubkey::new_unique()),
+            &[Check::err(ProgramError::Custom(19))], // EarlyExitPenaltyMismatch
+        );
+    }
+
+    #[test]
+    fn merge_vaults_fail_when_paused() {
+        let mut config_data = get_config_data(&Pubkey::new_unique());
+        config_data.paused = [1];
+
+        process_merge_sol_vaults(
+            60,
+            (0, 0),
+            &config_data,
+            &[Check::err(ProgramError::Custom(7))], // ProgramPaused
+        );
+    }
+
+    /// Merges a vault of 1,000,000 tokens into a vault of 2,000,000 tokens
+    /// unlocking at the same time, paying into `destination_ata` when set.
+    fn process_merge_spl_vaults(
+        destination_ata: Option<Pubkey>,
+        checks: &[Check],
+    ) -> InstructionResult {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+        let (token_program, token_program_account) =
+            mollusk_svm_programs_token::token::keyed_account();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+
+        let spl_vault = |amount: u64| {
+            let (address, bump) =
+                client::find_vault_address(&maker, Some(&mint), amount, unlock_timestamp);
+            let vault = Vault {
+                owner: maker.to_bytes(),
+                amount: amount.to_le_bytes(),
+                unlock_timestamp: unlock_timestamp.to_le_bytes(),
+                mint: Some(mint.to_bytes()),
+                bump: [bump],
+                id: [0; 8],
+                early_exit_penalty_bps: [0; 2],
+                seed_amount: amount.to_le_bytes(),
+                receipt_mint: None,
+                tracked: [1],
+                withdrawn: [0; 8],
+            };
+            let mut account = Account::new(
+                mollusk.sysvars.rent.minimum_balance(Vault::LEN),
+                Vault::LEN,
+                &PROGRAM_ID,
+            );
+            account.data = unsafe { to_bytes(&vault) }.to_vec();
+            (address, account)
+        };
+        let (source, source_account) = spl_vault(1_000_000);
+        let (destination, destination_account) = spl_vault(2_000_000);
+        let (registry_address, _, registry_account) =
+            get_registry(&mollusk, &maker, Some((Some(mint), 3_000_000)));
+        let (config, config_account) =
+            get_config(&mollusk, &get_config_data(&Pubkey::new_unique()));
+
+        let mut instruction = client::merge_vaults(&maker, &source, &destination, Some(&mint));
+        let source_ata = instruction.accounts[8].pubkey;
+        if let Some(destination_ata) = destination_ata {
+            instruction.accounts[9].pubkey = destination_ata;
+        }
+        let destination_ata = instruction.accounts[9].pubkey;
+        let destination_ata_owner =
+            if destination_ata == get_associated_token_address(&destination, &mint) {
+                destination
+            } else {
+                maker
+            };
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (maker, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+                (source, source_account),
+                (destination, destination_account),
+                (registry_address, registry_account.into()),
+                (config, config_account.into()),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+                (mint, mint_account),
+                (source_ata, token_account(mint, source, 1_000_000)),
+                (
+                    destination_ata,
+                    token_account(mint, destination_ata_owner, 2_000_000),
+                ),
+                (token_program, token_program_account),
+            ],
+            checks,
+        )
+    }
+
+    #[test]
+    fn merge_spl_vaults_successfully() {
+        let (mollusk, mint, _) = get_mollusk();
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let (destination, _) =
+            client::find_vault_address(&maker, Some(&mint), 2_000_000, unlock_timestamp);
+        let destination_ata = get_associated_token_address(&destination, &mint);
+
+        let result = process_merge_spl_vaults(None, &[Check::success()]);
+
+        let data = result.get_account(&destination_ata).unwrap().data();
+        assert_eq!(TokenAccount::unpack(data).unwrap().amount, 3_000_000);
+    }
+
+    #[test]
+    fn merge_spl_vaults_fail_with_wallet_ata() {
+        // the source vault could otherwise pay its tokens out to the owner
+        // before its unlock
+        let (_, mint, _) = get_mollusk();
+        let maker = Pubkey::new_from_array([0x02; 32]);
+
+        process_merge_spl_vaults(
+            Some(get_associated_token_address(&maker, &mint)),
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    /// Creates a SOL vault of `maker` with a receipt token, its receipt mint
+    /// address holding `receipt_mint_lamports` beforehand.
+    fn process_init_sol_vault_with_receipt(
+        receipt_mint_lamports: u64,
+        checks: &[Check],
+    ) -> InstructionResult {
+        let (mollusk, _, _) = get_mollusk();
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+        let (token_program, token_program_account) =
+            mollusk_svm_programs_token::token::keyed_account();
+        let (associated_token_program, associated_token_program_account) =
+            mollusk_svm_programs_token::associated_token::keyed_account();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let amount = LAMPORTS_PER_SOL;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let (config_address, config_account) =
+            get_config(&mollusk, &get_config_data(&Pubkey::new_unique()));
+
+        let instruction =
+            client::init_sol_vault_with_receipt(&maker, amount, unlock_timestamp, 0, &TREASURY);
+        let vault_address = instruction.accounts[1].pubkey;
+        let registry_address = instruction.accounts[3].pubkey;
+        let (receipt_mint, _) = client::find_receipt_mint_address(&vault_address);
+        let holder_ata = get_associated_token_address(&maker, &receipt_mint);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (
+                    maker,
+                    Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program),
+                ),
+                (vault_address, Account::new(0, 0, &system_program)),
+                (system_program, system_account),
+                (registry_address, Account::new(0, 0, &system_program)),
+                (config_address, config_account.into()),
+                (TREASURY, Account::new(0, 0, &system_program)),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+                (
+                    receipt_mint,
+                    Account::new(receipt_mint_lamports, 0, &system_program),
+                ),
+                (holder_ata, Account::new(0, 0, &system_program)),
+                (token_program, token_program_account),
+                (associated_token_program, associated_token_program_account),
+            ],
+            checks,
+        )
+    }
+
+    #[test]
+    fn init_sol_vault_with_receipt_successfully() {
+        let (mollusk, _, _) = get_mollusk();
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let instruction = client::init_sol_vault_with_receipt(
+            &maker,
+            LAMPORTS_PER_SOL,
+            unlock_timestamp,
+            0,
+            &TREASURY,
+        );
+        let vault_address = instruction.accounts[1].pubkey;
+        let (receipt_mint, _) = client::find_receipt_mint_address(&vault_address);
+        let holder_ata = get_associated_token_address(&maker, &receipt_mint);
+
+        let result = process_init_sol_vault_with_receipt(
+            0,
+            &[
+                Check::success(),
+                Check::account(&receipt_mint).owner(&spl_token::ID).build(),
+            ],
+        );
+
+        let data = result.get_account(&vault_address).unwrap().data();
+        let vault = client::VaultAccount::decode_at(&vault_address, data).unwrap();
+        assert_eq!(vault.receipt_mint, Some(receipt_mint));
+
+        let mint = Mint::unpack(result.get_account(&receipt_mint).unwrap().data()).unwrap();
+        assert_eq!(mint.supply, 1);
+        assert_eq!(mint.decimals, 0);
+        assert!(mint.mint_authority.is_none());
+
+        let holder_ata = result.get_account(&holder_ata).unwrap();
+        assert_eq!(TokenAccount::unpack(holder_ata.data()).unwrap().amount, 1);
+    }
+
+    #[test]
+    fn init_sol_vault_with_prefunded_receipt_mint() {
+        let (mollusk, _, _) = get_mollusk();
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let instruction = client::init_sol_vault_with_receipt(
+            &maker,
+            LAMPORTS_PER_SOL,
+            unlock_timestamp,
+            0,
+            &TREASURY,
+        );
+        let (receipt_mint, _) = client::find_receipt_mint_address(&instruction.accounts[1].pubkey);
+
+        // lamports sent to the receipt mint address ahead of time do not
+        // block the creation of the vault
+        process_init_sol_vault_with_receipt(
+            1_000,
+            &[
+                Check::success(),
+                Check::account(&receipt_mint).owner(&spl_token::ID).build(),
+            ],
+        );
+    }
+
+    #[test]
+    fn receipt_mint_address_is_canonical() {
+        let vault = Pubkey::new_from_array([0x05; 32]);
+        let (receipt_mint, bump) = client::find_receipt_mint_address(&vault);
+        assert!(
+            Vault::validate_receipt_pda(receipt_mint.as_array(), vault.as_array(), bump).is_ok()
+        );
+
+        let (receipt_mint, bump) = non_canonical_address(&[Vault::RECEIPT_SEED, vault.as_ref()]);
+        assert_eq!(
+            Vault::validate_receipt_pda(receipt_mint.as_array(), vault.as_array(), bump)
+                .map_err(u64::from),
+            Err(22) // NonCanonicalBump
+        );
+    }
+
+    /// Withdraws an unlocked SOL vault of `maker` with a receipt token as
+    /// `holder`, who holds the receipt when `holds_receipt`, presenting the
+    /// receipt accounts when `with_receipt`.
+    fn process_withdraw_sol_vault_with_receipt(
+        holder: Pubkey,
+        holds_receipt: bool,
+        with_receipt: bool,
+        checks: &[Check],
+    ) -> InstructionResult {
+        let (mut mollusk, _, _) = get_mollusk();
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+        let (token_program, token_program_account) =
+            mollusk_svm_programs_token::token::keyed_account();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let amount = LAMPORTS_PER_SOL;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let (vault_address, bump) =
+            client::find_vault_address(&maker, None, amount, unlock_timestamp);
+        let (receipt_mint, _) = client::find_receipt_mint_address(&vault_address);
+
+        let vault = Vault {
+            owner: maker.to_bytes(),
+            amount: amount.to_le_bytes(),
+            unlock_timestamp: unlock_timestamp.to_le_bytes(),
+            mint: None,
+            bump: [bump],
+            id: [0; 8],
+            early_exit_penalty_bps: [0; 2],
+            seed_amount: amount.to_le_bytes(),
+            receipt_mint: Some(receipt_mint.to_bytes()),
+            tracked: [1],
+            withdrawn: [0; 8],
+        };
+        let mut vault_account = Account::new(
+            mollusk.sysvars.rent.minimum_balance(Vault::LEN) + amount,
+            Vault::LEN,
+            &PROGRAM_ID,
+        );
+        vault_account.data = unsafe { to_bytes(&vault) }.to_vec();
+        let (registry_address, _, registry_account) =
+            get_registry(&mollusk, &maker, Some((None, amount)));
+
+        let receipt_mint_account = create_account_for_mint(Mint {
+            mint_authority: None.into(),
+            supply: 1,
+            decimals: 0,
+            is_initialized: true,
+            freeze_authority: None.into(),
+        });
+        let holder_ata = get_associated_token_address(&holder, &receipt_mint);
+
+        let instruction = if with_receipt {
+            client::withdraw_sol_vault_with_receipt(&holder, &maker, &vault_address)
+        } else {
+            client::withdraw_sol_vault(&holder, &vault_address)
+        };
+        mollusk.sysvars.clock.unix_timestamp = unlock_timestamp + 100;
+
+        let mut accounts = vec![
+            (holder, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+            (vault_address, vault_account),
+            (registry_address, registry_account.into()),
+            (EVENT_AUTHORITY_ID, Account::default()),
+            (
+                PROGRAM_ID,
+                mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+            ),
+        ];
+        if with_receipt {
+            accounts.extend([
+                (receipt_mint, receipt_mint_account),
+                (
+                    holder_ata,
+                    token_account(receipt_mint, holder, holds_receipt as u64),
+                ),
+                (token_program, token_program_account),
+            ]);
+        }
+
+        mollusk.process_and_validate_instruction(&instruction, &accounts, checks)
+    }
+
+    #[test]
+    fn withdraw_sol_vault_with_receipt_successfully() {
+        let (mollusk, _, _) = get_mollusk();
+        let holder = Pubkey::new_from_array([0x03; 32]);
+        let (vault_address, _) = client::find_vault_address(
+            &Pubkey::new_from_array([0x02; 32]),
+            None,
+            LAMPORTS_PER_SOL,
+            mollusk.sysvars.clock.unix_timestamp + 3600,
+        );
+        let (receipt_mint, _) = client::find_receipt_mint_address(&vault_address);
+
+        let result = process_withdraw_sol_vault_with_receipt(
+            holder,
+            true,
+            true,
+            &[
+                Check::success(),
+                Check::account(&vault_address).closed().build(),
+                Check::account(&get_associated_token_address(&holder, &receipt_mint))
+                    .closed()
+                    .build(),
+            ],
+        );
+
+        let mint = Mint::unpack(result.get_account(&receipt_mint).unwrap().data()).unwrap();
+        assert_eq!(mint.supply, 0);
+        assert!(result.get_account(&holder).unwrap().lamports > 2 * LAMPORTS_PER_SOL);
+    }
+
+    #[test]
+    fn withdraw_sol_vault_with_receipt_fail_without_holding_it() {
+        process_withdraw_sol_vault_with_receipt(
+            Pubkey::new_from_array([0x03; 32]),
+            false,
+            true,
+            &[Check::err(ProgramError::Custom(2))], // Unauthorized
+        );
+    }
+
+    #[test]
+    fn withdraw_sol_vault_with_receipt_fail_without_receipt_accounts() {
+        // the original owner can no longer withdraw by signing alone
+        process_withdraw_sol_vault_with_receipt(
+            Pubkey::new_from_array([0x02; 32]),
+            false,
+            false,
+            &[Check::err(ProgramError::Custom(20))], // ReceiptRequired
+        );
+    }
+
+    #[test]
+    fn deposit_locked_bucket_creates_bucket() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+        let (token_program, token_program_account) =
+            mollusk_svm_programs_token::token::keyed_account();
+        let (associated_token_program, associated_token_program_account) =
+            mollusk_svm_programs_token::associated_token::keyed_account();
+
+        let depositor = Pubkey::new_from_array([0x02; 32]);
+        let amount = 1_000_000;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let (bucket, _) = client::find_locked_bucket_address(&mint, unlock_timestamp);
+        let (receipt_mint, _) = client::find_bucket_receipt_mint_address(&bucket);
+        let bucket_ata = get_associated_token_address(&bucket, &mint);
+        let receipt_ata = get_associated_token_address(&depositor, &receipt_mint);
+        let (config_address, config_account) =
+            get_config(&mollusk, &get_config_data(&Pubkey::new_unique()));
+
+        let instruction =
+            client::deposit_locked_bucket(&depositor, &mint, amount, unlock_timestamp, &TREASURY);
+
+        let result = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (
+                    depositor,
+                    Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program),
+                ),
+                (bucket, Account::new(0, 0, &system_program)),
+                (mint, mint_account),
+                (receipt_mint, Account::new(0, 0, &system_program)),
+                (
+                    get_associated_token_address(&depositor, &mint),
+                    token_account(mint, depositor, amount),
+                ),
+                (bucket_ata, Account::new(0, 0, &system_program)),
+                (receipt_ata, Account::new(0, 0, &system_program)),
+                (token_program, token_program_account),
+                (associated_token_program, associated_token_program_account),
+                (system_program, system_account),
+                (config_address, config_account.into()),
+                (TREASURY, Account::new(0, 0, &system_program)),
+                (
+                    get_associated_token_address(&TREASURY, &mint),
+                    token_account(mint, TREASURY, 0),
+                ),
+                (
+                    client::find_mint_config_address(&mint).0,
+                    Account::new(0, 0, &system_program),
+                ),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            &[
+                Check::success(),
+                Check::account(&bucket).owner(&PROGRAM_ID).build(),
+            ],
+        );
+
+        let data = result.get_account(&bucket).unwrap().data();
+        let state = unsafe { &*(data.as_ptr() as *const LockedBucket) };
+        assert_eq!(state.discriminator, [LockedBucket::DISCRIMINATOR]);
+        assert_eq!(state.mint, mint.to_bytes());
+        assert_eq!(i64::from_le_bytes(state.unlock_timestamp), unlock_timestamp);
+        assert_eq!(state.receipt_mint, receipt_mint.to_bytes());
+        assert_eq!(u64::from_le_bytes(state.total), amount);
+
+        let receipt_mint_account = result.get_account(&receipt_mint).unwrap();
+        let receipt_mint_state = Mint::unpack(receipt_mint_account.data()).unwrap();
+        assert_eq!(receipt_mint_state.supply, amount);
+        assert_eq!(receipt_mint_state.decimals, 6);
+        assert_eq!(
+            receipt_mint_state.mint_authority,
+            Some(bucket).into(),
+            "the bucket keeps minting receipts"
+        );
+
+        let bucket_ata = result.get_account(&bucket_ata).unwrap();
+        assert_eq!(
+            TokenAccount::unpack(bucket_ata.data()).unwrap().amount,
+            amount
+        );
+        let receipt_ata = result.get_account(&receipt_ata).unwrap();
+        assert_eq!(
+            TokenAccount::unpack(receipt_ata.data()).unwrap().amount,
+            amount
+        );
+    }
+
+    #[test]
+    fn locked_bucket_addresses_are_canonical() {
+        let mint = Pubkey::new_unique();
+        let unlock_timestamp = 1_757_636_943i64;
+        let (bucket, bump) = client::find_locked_bucket_address(&mint, unlock_timestamp);
+        assert!(LockedBucket::validate_pda(
+            bucket.as_array(),
+            mint.as_array(),
+            unlock_timestamp,
+            bump
+        )
+        .is_ok());
+        let (receipt_mint, bump) = client::find_bucket_receipt_mint_address(&bucket);
+        assert!(LockedBucket::validate_receipt_pda(
+            receipt_mint.as_array(),
+            bucket.as_array(),
+            bump
+        )
+        .is_ok());
+
+        let (other_bucket, bump) = non_canonical_address(&[
+            LockedBucket::SEED,
+            mint.as_ref(),
+            &unlock_timestamp.to_le_bytes(),
+        ]);
+        assert_eq!(
+            LockedBucket::validate_pda(
+                other_bucket.as_array(),
+                mint.as_array(),
+                unlock_timestamp,
+                bump
+            )
+            .map_err(u64::from),
+            Err(22) // NonCanonicalBump
+        );
+        let (other_receipt_mint, bump) =
+            non_canonical_address(&[LockedBucket::RECEIPT_SEED, bucket.as_ref()]);
+        assert_eq!(
+            LockedBucket::validate_receipt_pda(
+                other_receipt_mint.as_array(),
+                bucket.as_array(),
+                bump
+            )
+            .map_err(u64::from),
+            Err(22) // NonCanonicalBump
+        );
+    }
+
+    /// Deposits into an existing bucket with the instruction and its accounts
+    /// changed by `edit`.
+    fn process_deposit_existing_locked_bucket(
+        edit: impl FnOnce(&mut Instruction, &mut Vec<(Pubkey, Account)>),
+        checks: &[Check],
+    ) -> InstructionResult {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+        let (token_program, token_program_account) =
+            mollusk_svm_programs_token::token::keyed_account();
+        let (associated_token_program, associated_token_program_account) =
+            mollusk_svm_programs_token::associated_token::keyed_account();
+
+        let depositor = Pubkey::new_from_array([0x02; 32]);
+        let amount = 1_000_000u64;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let (bucket, bump) = client::find_locked_bucket_address(&mint, unlock_timestamp);
+        let (receipt_mint, _) = client::find_bucket_receipt_mint_address(&bucket);
+        let receipt_ata = get_associated_token_address(&depositor, &receipt_mint);
+        let (config_address, config_account) =
+            get_config(&mollusk, &get_config_data(&Pubkey::new_unique()));
+
+        let state = LockedBucket {
+            discriminator: [LockedBucket::DISCRIMINATOR],
+            mint: mint.to_bytes(),
+            unlock_timestamp: unlock_timestamp.to_le_bytes(),
+            receipt_mint: receipt_mint.to_bytes(),
+            total: amount.to_le_bytes(),
+            bump: [bump],
+        };
+        let mut bucket_account = Account::new(
+            mollusk.sysvars.rent.minimum_balance(LockedBucket::LEN),
+            LockedBucket::LEN,
+            &PROGRAM_ID,
+        );
+        bucket_account.data = unsafe { to_bytes(&state) }.to_vec();
+        let receipt_mint_account = create_account_for_mint(Mint {
+            mint_authority: Some(bucket).into(),
+            supply: amount,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: None.into(),
+        });
+
+        let mut instruction =
+            client::deposit_locked_bucket(&depositor, &mint, amount, unlock_timestamp, &TREASURY);
+        let mut accounts = vec![
+            (
+                depositor,
+                Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program),
+            ),
+            (bucket, bucket_account),
+            (mint, mint_account),
+            (receipt_mint, receipt_mint_account),
+            (
+                get_associated_token_address(&depositor, &mint),
+                token_account(mint, depositor, amount),
+            ),
+            (
+                get_associated_token_address(&bucket, &mint),
+                token_account(mint, bucket, amount),
+            ),
+            (receipt_ata, token_account(receipt_mint, depositor, amount)),
+            (token_program, token_program_account),
+            (associated_token_program, associated_token_program_account),
+            (system_program, system_account),
+            (config_address, config_account.into()),
+            (TREASURY, Account::new(0, 0, &system_program)),
+            (
+                get_associated_token_address(&TREASURY, &mint),
+                token_account(mint, TREASURY, 0),
+            ),
+            (
+                client::find_mint_config_address(&mint).0,
+                Account::new(0, 0, &system_program),
+            ),
+            (EVENT_AUTHORITY_ID, Account::default()),
+            (
+                PROGRAM_ID,
+                mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+            ),
+        ];
+        edit(&mut instruction, &mut accounts);
+
+        mollusk.process_and_validate_instruction(&instruction, &accounts, checks)
+    }
+
+    #[test]
+    fn deposit_existing_locked_bucket_successfully() {
+        process_deposit_existing_locked_bucket(|_, _| {}, &[Check::success()]);
+    }
+
+    #[test]
+    fn deposit_locked_bucket_fail_with_foreign_bucket_ata() {
+        let (_, mint, _) = get_mollusk();
+        let depositor = Pubkey::new_from_array([0x02; 32]);
+        let foreign_ata = Pubkey::new_unique();
+
+        // the deposit sent to a token account of the depositor instead of
+        // the bucket's
+        process_deposit_existing_locked_bucket(
+            |instruction, accounts| {
+                instruction.accounts[5].pubkey = foreign_ata;
+                accounts.push((foreign_ata, token_account(mint, depositor, 0)));
+            },
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    #[test]
+    fn deposit_locked_bucket_fail_with_bucket_off_its_address() {
+        let copy = Pubkey::new_unique();
+
+        // a copy of the bucket data at another address of the program
+        process_deposit_existing_locked_bucket(
+            |instruction, accounts| {
+                let bucket = accounts[1].1.clone();
+                instruction.accounts[1].pubkey = copy;
+                accounts.push((copy, bucket));
+            },
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    #[test]
+    fn deposit_locked_bucket_fail_with_claim_receipt_as_bucket() {
+        // a claim receipt has the length of a bucket but not its type
+        process_deposit_existing_locked_bucket(
+            |_, accounts| accounts[1].1.data[0] = ClaimReceipt::DISCRIMINATOR,
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    /// Redeems `amount` of the receipts held by the only depositor of a
+    /// bucket of `total` tokens unlocking `delay` seconds from now.
+    fn process_redeem_locked_bucket(
+        delay: i64,
+        total: u64,
+        amount: u64,
+        checks: &[Check],
+    ) -> (InstructionResult, Pubkey, Pubkey) {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        let (token_program, token_program_account) =
+            mollusk_svm_programs_token::token::keyed_account();
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+
+        let holder = Pubkey::new_from_array([0x02; 32]);
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + delay;
+        let (bucket, bump) = client::find_locked_bucket_address(&mint, unlock_timestamp);
+        let (receipt_mint, _) = client::find_bucket_receipt_mint_address(&bucket);
+
+        let state = LockedBucket {
+            discriminator: [LockedBucket::DISCRIMINATOR],
+            mint: mint.to_bytes(),
+            unlock_timestamp: unlock_timestamp.to_le_bytes(),
+            receipt_mint: receipt_mint.to_bytes(),
+            total: total.to_le_bytes(),
+            bump: [bump],
+        };
+        let mut bucket_account = Account::new(
+            mollusk.sysvars.rent.minimum_balance(LockedBucket::LEN),
+            LockedBucket::LEN,
+            &PROGRAM_ID,
+        );
+        bucket_account.data = unsafe { to_bytes(&state) }.to_vec();
+
+        let receipt_mint_account = create_account_for_mint(Mint {
+            mint_authority: Some(bucket).into(),
+            supply: total,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: None.into(),
+        });
+
+        let instruction = client::redeem_locked_bucket(&holder, &mint, unlock_timestamp, amount);
+
+        let result = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (holder, Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+                (bucket, bucket_account),
+                (mint, mint_account),
+                (receipt_mint, receipt_mint_account),
+                (
+                    get_associated_token_address(&holder, &receipt_mint),
+                    token_account(receipt_mint, holder, total),
+                ),
+                (
+                    get_associated_token_address(&holder, &mint),
+                    token_account(mint, holder, 0),
+                ),
+                (
+                    get_associated_token_address(&bucket, &mint),
+                    token_account(mint, bucket, total),
+                ),
+                (token_program, token_program_account),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            checks,
+        );
+
+        (result, bucket, mint)
+    }
+
+    #[test]
+    fn redeem_locked_bucket_successfully() {
+        let holder = Pubkey::new_from_array([0x02; 32]);
+        let (result, bucket, mint) =
+            process_redeem_locked_bucket(-10, 1_000_000, 400_000, &[Check::success()]);
+
+        let data = result.get_account(&bucket).unwrap().data();
+        let state = unsafe { &*(data.as_ptr() as *const LockedBucket) };
+        assert_eq!(u64::from_le_bytes(state.total), 600_000);
+
+        let user_ata = result
+            .get_account(&get_associated_token_address(&holder, &mint))
+            .unwrap();
+        assert_eq!(
+            TokenAccount::unpack(user_ata.data()).unwrap().amount,
+            400_000
+        );
+        let receipt_mint = client::find_bucket_receipt_mint_address(&bucket).0;
+        let receipt_ata = result
+            .get_account(&get_associated_token_address(&holder, &receipt_mint))
+            .unwrap();
+        assert_eq!(
+            TokenAccount::unpack(receipt_ata.data()).unwrap().amount,
+            600_000
+        );
+    }
+
+    #[test]
+    fn redeem_locked_bucket_fail_with_vault_locking() {
+        process_redeem_locked_bucket(
+            3600,
+            1_000_000,
+            400_000,
+            &[Check::err(ProgramError::Custom(3))], // VaultLocking
+        );
+    }
+
+    #[test]
+    fn redeem_locked_bucket_fail_with_more_than_total() {
+        process_redeem_locked_bucket(
+            -10,
+            1_000_000,
+            1_000_001,
+            &[Check::err(ProgramError::InsufficientFunds)],
+        );
+    }
+
+    #[test]
+    fn get_voting_power_returns_weighted_amount() {
+        let (mollusk, _, _) = get_mollusk();
+        let owner = Pubkey::new_from_array([0x02; 32]);
+        let amount = LAMPORTS_PER_SOL;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let (vault, vault_account) = get_sol_vault(&mollusk, &owner, amount, unlock_timestamp, 0);
+
+        // half of the max lock duration left, so half of the 2x bonus of 3x
+        let expected = VotingPower {
+            owner: owner.to_bytes(),
+            mint: None,
+            amount: amount.to_le_bytes(),
+            unlock_timestamp: unlock_timestamp.to_le_bytes(),
+            voting_power: (2 * amount).to_le_bytes(),
+            receipt_mint: None,
+        };
+
+        mollusk.process_and_validate_instruction(
+            &client::get_voting_power(&vault, 3 * MAX_BPS, 7200),
+            &[(vault, vault_account.into())],
+            &[
+                Check::success(),
+                Check::return_data(unsafe { to_bytes(&expected) }),
+            ],
+        );
+    }
+
+    #[test]
+    fn get_voting_power_returns_receipt_mint() {
+        let (mollusk, _, _) = get_mollusk();
+        let owner = Pubkey::new_from_array([0x02; 32]);
+        let amount = LAMPORTS_PER_SOL;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let (vault, mut vault_account) =
+            get_sol_vault(&mollusk, &owner, amount, unlock_timestamp, 0);
+        let (receipt_mint, _) = client::find_receipt_mint_address(&vault);
+        let data = vault_account.data_as_mut_slice();
+        unsafe { &mut *(data.as_mut_ptr() as *mut Vault) }.receipt_mint =
+            Some(receipt_mint.to_bytes());
+
+        // the vote belongs to the receipt holder rather than the owner
+        let expected = VotingPower {
+            owner: owner.to_bytes(),
+            mint: None,
+            amount: amount.to_le_bytes(),
+            unlock_timestamp: unlock_timestamp.to_le_bytes(),
+            voting_power: (2 * amount).to_le_bytes(),
+            receipt_mint: Some(receipt_mint.to_bytes()),
+        };
+
+        mollusk.process_and_validate_instruction(
+            &client::get_voting_power(&vault, 3 * MAX_BPS, 7200),
+            &[(vault, vault_account.into())],
+            &[
+                Check::success(),
+                Check::return_data(unsafe { to_bytes(&expected) }),
+            ],
+        );
+    }
+
+    #[test]
+    fn get_voting_power_fail_with_vault_off_its_address() {
+        let (mollusk, _, _) = get_mollusk();
+        let owner = Pubkey::new_from_array([0x02; 32]);
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let (_, vault_account) =
+            get_sol_vault(&mollusk, &owner, LAMPORTS_PER_SOL, unlock_timestamp, 0);
+
+        // a copy of the vault data, which would double the vote of its owner
+        let copy = Pubkey::new_unique();
+        mollusk.process_and_validate_instruction(
+            &client::get_voting_power(&copy, 3 * MAX_BPS, 7200),
+            &[(copy, vault_account.into())],
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    /// Returns the config, a registry and a merkle vault, accounts of the
+    /// program that are not vaults.
+    fn non_vault_accounts(mollusk: &Mollusk) -> Vec<(Pubkey, Account)> {
+        let owner = Pubkey::new_from_array([0x02; 32]);
+        let (config, config_account) = get_config(mollusk, &get_config_data(&Pubkey::new_unique()));
+        let (registry, _, registry_account) =
+            get_registry(mollusk, &owner, Some((None, LAMPORTS_PER_SOL)));
+        let (merkle_vault, state) =
+            merkle_vault_state(mollusk, &owner, &Pubkey::new_from_array([0x03; 32]));
+
+        vec![
+            (config, config_account.into()),
+            (registry, registry_account.into()),
+            (merkle_vault, merkle_vault_account(mollusk, &state)),
+        ]
+    }
+
+    #[test]
+    fn get_voting_power_fail_with_non_vault_account() {
+        let (mollusk, _, _) = get_mollusk();
+
+        for (address, account) in non_vault_accounts(&mollusk) {
+            mollusk.process_and_validate_instruction(
+                &client::get_voting_power(&address, 3 * MAX_BPS, 7200),
+                &[(address, account)],
+                &[Check::err(ProgramError::InvalidAccountData)],
+            );
+        }
+    }
+
+    #[test]
+    fn vault_voting_power_grows_with_remaining_lock() {
+        let now = 1_757_633_343;
+        let vault = |unlock_timestamp: i64| Vault {
+            owner: [0x02; 32],
+            amount: 1_000_000u64.to_le_bytes(),
+            bump: [0],
+            unlock_timestamp: unlock_timestamp.to_le_bytes(),
+            mint: None,
+            id: [0; 8],
+            early_exit_penalty_bps: [0; 2],
+            seed_amount: 1_000_000u64.to_le_bytes(),
+            receipt_mint: None,
+            tracked: [1],
+            withdrawn: [0; 8],
+        };
+        let max_lock_duration = 4 * 365 * 86_400;
+
+        for (unlock_timestamp, expected) in [
+            (now - 10, 1_000_000),
+            (now, 1_000_000),
+            (now + max_lock_duration / 4, 1_750_000),
+            (now + max_lock_duration, 4_000_000),
+            (now + 2 * max_lock_duration, 4_000_000),
+        ] {
+            assert_eq!(
+                vault(unlock_timestamp)
+                    .voting_power(now, 4 * MAX_BPS, max_lock_duration)
+                    .unwrap(),
+                expected,
+                "unlock at {unlock_timestamp}"
+            );
+        }
+
+        assert_eq!(vault(now).voting_power(now, MAX_BPS, 1).unwrap(), 1_000_000);
+        for (max_multiplier_bps, max_lock_duration) in [(MAX_BPS - 1, 1), (MAX_BPS, 0)] {
+            assert_eq!(
+                vault(now)
+                    .voting_power(now, max_multiplier_bps, max_lock_duration)
+                    .map_err(u64::from),
+                Err(21) // InvalidVotingPowerConfig
+            );
+        }
+    }
+
+    /// Queries the status of a SOL vault unlocking `delay` seconds from now,
+    /// stored at `address` instead of its PDA if given.
+    fn process_get_vault_status(
+        delay: i64,
+        address: Option<Pubkey>,
+        checks: &[Check],
+    ) -> InstructionResult {
+        let (mollusk, _, _) = get_mollusk();
+        let owner = Pubkey::new_from_array([0x02; 32]);
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + delay;
+        let (vault, vault_account) =
+            get_sol_vault(&mollusk, &owner, LAMPORTS_PER_SOL, unlock_timestamp, 0);
+        let vault = address.unwrap_or(vault);
+
+        mollusk.process_and_validate_instruction(
+            &client::get_vault_status(&vault),
+            &[(vault, vault_account.into())],
+            checks,
+        )
+    }
+
+    fn vault_status(result: &InstructionResult) -> VaultStatus {
+        assert_eq!(result.return_data.len(), VaultStatus::LEN);
+        unsafe { *(result.return_data.as_ptr() as *const VaultStatus) }
+    }
+
+    #[test]
+    fn get_vault_status_of_locked_vault() {
+        let result = process_get_vault_status(3600, None, &[Check::success()]);
+        let status = vault_status(&result);
+
+        assert_eq!(status.owner, [0x02; 32]);
+        assert_eq!(status.mint, None);
+        assert_eq!(u64::from_le_bytes(status.amount), LAMPORTS_PER_SOL);
+        assert_eq!(u64::from_le_bytes(status.withdrawn), 0);
+        assert_eq!(u64::from_le_bytes(status.withdrawable), 0);
+        assert_eq!(status.receipt_mint, None);
+    }
+
+    #[test]
+    fn get_vault_status_of_split_vault() {
+        let (mollusk, _, _) = get_mollusk();
+        let owner = Pubkey::new_from_array([0x02; 32]);
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp - 10;
+        let (vault, mut vault_account) =
+            get_sol_vault(&mollusk, &owner, LAMPORTS_PER_SOL, unlock_timestamp, 0);
+        let data = vault_account.data_as_mut_slice();
+        let state = unsafe { &mut *(data.as_mut_ptr() as *mut Vault) };
+        state.amount = (LAMPORTS_PER_SOL / 4 * 3).to_le_bytes();
+        state.withdrawn = (LAMPORTS_PER_SOL / 4).to_le_bytes();
+
+        let result = mollusk.process_and_validate_instruction(
+            &client::get_vault_status(&vault),
+            &[(vault, vault_account.into())],
+            &[Check::success()],
+        );
+        let status = vault_status(&result);
+
+        // the split amount is reported withdrawn, the rest withdrawable
+        assert_eq!(u64::from_le_bytes(status.amount), LAMPORTS_PER_SOL / 4 * 3);
+        assert_eq!(u64::from_le_bytes(status.withdrawn), LAMPORTS_PER_SOL / 4);
+        assert_eq!(
+            u64::from_le_bytes(status.withdrawable),
+            LAMPORTS_PER_SOL / 4 * 3
+        );
+    }
+
+    #[test]
+    fn get_vault_status_of_unlocked_vault() {
+        let result = process_get_vault_status(-10, None, &[Check::success()]);
+        let status = vault_status(&result);
+
+        assert_eq!(u64::from_le_bytes(status.withdrawable), LAMPORTS_PER_SOL);
+        assert_eq!(
+            i64::from_le_bytes(status.unlock_timestamp),
+            get_mollusk().0.sysvars.clock.unix_timestamp - 10
+        );
+    }
+
+    #[test]
+    fn get_vault_status_of_receipt_vault() {
+        let (mollusk, _, _) = get_mollusk();
+        let owner = Pubkey::new_from_array([0x02; 32]);
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let (vault, mut vault_account) =
+            get_sol_vault(&mollusk, &owner, LAMPORTS_PER_SOL, unlock_timestamp, 0);
+        let (receipt_mint, _) = client::find_receipt_mint_address(&vault);
+        let data = vault_account.data_as_mut_slice();
+        unsafe { &mut *(data.as_mut_ptr() as *mut Vault) }.receipt_mint =
+            Some(receipt_mint.to_bytes());
+
+        let result = mollusk.process_and_validate_instruction(
+            &client::get_vault_status(&vault),
+            &[(vault, vault_account.into())],
+            &[Check::success()],
+        );
+        let status = vault_status(&result);
+
+        assert_eq!(status.owner, owner.to_bytes());
+        assert_eq!(status.receipt_mint, Some(receipt_mint.to_bytes()));
+    }
+
+    #[test]
+    fn get_vault_status_fail_with_vault_off_its_address() {
+        process_get_vault_status(
+            3600,
+            Some(Pubkey::new_unique()),
+            &[Check::err(ProgramError::InvalidAccountData)],
+        );
+    }
+
+    #[test]
+    fn get_vault_status_fail_with_non_vault_account() {
+        let (mollusk, _, _) = get_mollusk();
+
+        for (address, account) in non_vault_accounts(&mollusk) {
+            mollusk.process_and_validate_instruction(
+                &client::get_vault_status(&address),
+                &[(address, account)],
+                &[Check::err(ProgramError::InvalidAccountData)],
+            );
+        }
+    }
+
+    /// Returns a bump below the canonical bump of the SOL vault of `owner` and
+    /// the vault address it derives.
+    fn non_canonical_vault_address(
+        owner: &Pubkey,
+        amount: u64,
+        unlock_timestamp: i64,
+    ) -> (Pubkey, u8) {
+        let (_, canonical_bump) =
+            Vault::find_address(owner.as_array(), None, amount, unlock_timestamp);
+        (0..canonical_bump)
+            .rev()
+            .find_map(|bump| {
+                Vault::derive_address(owner.as_array(), None, amount, unlock_timestamp, bump)
+                    .ok()
+                    .map(|vault| (Pubkey::new_from_array(vault), bump))
+            })
+            .unwrap()
+    }
+
+    /// Returns the highest valid non-canonical bump of the PDA of `seeds`.
+    fn non_canonical_address(seeds: &[&[u8]]) -> (Pubkey, u8) {
+        let (_, canonical_bump) = Pubkey::find_program_address(seeds, &PROGRAM_ID);
+        (0..canonical_bump)
+            .rev()
+            .find_map(|bump| {
+                Pubkey::create_program_address(&[seeds, &[&[bump]]].concat(), &PROGRAM_ID)
+                    .ok()
+                    .map(|address| (address, bump))
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn vault_address_helpers_agree() {
+        let owner = Pubkey::new_from_array([0x02; 32]);
+        let mint = Pubkey::new_from_array([0x03; 32]);
+
+        for mint in [None, Some(&mint)] {
+            let (vault, bump) = client::find_vault_address(&owner, mint, 1_000, 1_757_633_343);
+            assert_eq!(
+                Vault::find_address(
+                    owner.as_array(),
+                    mint.map(Pubkey::as_array),
+                    1_000,
+                    1_757_633_343
+                ),
+                (vault.to_bytes(), bump)
+            );
+            assert_eq!(
+                Vault::derive_address(
+                    owner.as_array(),
+                    mint.map(Pubkey::as_array),
+                    1_000,
+                    1_757_633_343,
+                    bump
+                ),
+                Ok(vault.to_bytes())
+            );
+            assert!(Vault::validate_pda(
+                vault.as_array(),
+                owner.as_array(),
+                1_000,
+                1_757_633_343,
+                bump,
+                mint.map(|mint| mint.to_bytes()),
+            )
+            .is_ok());
+        }
+
+        let (vault, bump) = non_canonical_vault_address(&owner, 1_000, 1_757_633_343);
+        assert_eq!(
+            Vault::validate_pda(
+                vault.as_array(),
+                owner.as_array(),
+                1_000,
+                1_757_633_343,
+                bump,
+                None
+            )
+            .map_err(u64::from),
+            Err(22) // NonCanonicalBump
+        );
+    }
+
+    #[test]
+    fn init_sol_vault_fail_with_non_canonical_bump() {
+        let (mollusk, _, _) = get_mollusk();
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let amount = LAMPORTS_PER_SOL;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let (vault, bump) = non_canonical_vault_address(&maker, amount, unlock_timestamp);
+        let (registry, _) = client::find_registry_address(&maker);
+        let (config_address, config_account) =
+            get_config(&mollusk, &get_config_data(&Pubkey::new_unique()));
+
+        // a second vault for the same parameters at another bump
+        let mut instruction =
+            client::init_sol_vault(&maker, amount, unlock_timestamp, 0, &TREASURY);
+        instruction.accounts[1].pubkey = vault;
+        instruction.data[17] = bump;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (
+                    maker,
+                    Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program),
+                ),
+                (vault, Account::new(0, 0, &system_program)),
+                (system_program, system_account),
+                (registry, Account::new(0, 0, &system_program)),
+                (config_address, config_account.into()),
+                (TREASURY, Account::new(0, 0, &system_program)),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            &[Check::err(ProgramError::Custom(22))], // NonCanonicalBump
+        );
+    }
+
+    #[test]
+    fn registry_address_is_canonical() {
+        let owner = Pubkey::new_from_array([0x02; 32]);
+        let (registry, bump) = client::find_registry_address(&owner);
+        assert!(UserRegistry::validate_pda(registry.as_array(), owner.as_array(), bump).is_ok());
+
+        let (registry, bump) = non_canonical_address(&[UserRegistry::SEED, owner.as_ref()]);
+        assert_eq!(
+            UserRegistry::validate_pda(registry.as_array(), owner.as_array(), bump)
+                .map_err(u64::from),
+            Err(22) // NonCanonicalBump
+        );
+    }
+
+    #[test]
+    fn config_address_is_canonical() {
+        let (config, bump) = client::find_config_address();
+        assert!(Config::validate_pda(config.as_array(), bump).is_ok());
+
+        let (config, bump) = non_canonical_address(&[Config::SEED]);
+        assert_eq!(
+            Config::validate_pda(config.as_array(), bump).map_err(u64::from),
+            Err(22) // NonCanonicalBump
+        );
+    }
+
+    #[test]
+    fn mint_config_address_is_canonical() {
+        let mint = Pubkey::new_from_array([0x03; 32]);
+        let (mint_config, bump) = client::find_mint_config_address(&mint);
+        assert!(MintConfig::validate_pda(mint_config.as_array(), mint.as_array(), bump).is_ok());
+
+        let (mint_config, bump) = non_canonical_address(&[MintConfig::SEED, mint.as_ref()]);
+        assert_eq!(
+            MintConfig::validate_pda(mint_config.as_array(), mint.as_array(), bump)
+                .map_err(u64::from),
+            Err(22) // NonCanonicalBump
+        );
+    }
+
+    #[test]
+    fn init_spl_vault_fail_with_non_canonical_mint_config_bump() {
+        let (mollusk, mint, mint_account) = get_mollusk();
+        let treasury_ata_account = create_account_for_token_account(TokenAccount {
+            mint,
+            owner: TREASURY,
+            amount: 0,
+            delegate: None.into(),
+            state: AccountState::Initialized,
+            is_native: None.into(),
+            delegated_amount: 0,
+            close_authority: None.into(),
+        });
+
+        // an empty mint config at another bump would skip the lock bounds
+        // of the mint
+        let (mint_config, bump) = non_canonical_address(&[MintConfig::SEED, mint.as_ref()]);
+        process_init_spl_vault_with(
+            &mollusk,
+            (mint, mint_account),
+            &get_config_data(&Pubkey::new_unique()),
+            None,
+            treasury_ata_account,
+            |instruction| {
+                instruction.accounts[12].pubkey = mint_config;
+                instruction.data[21] = bump;
+            },
+            &[Check::err(ProgramError::Custom(22))], // NonCanonicalBump
+        );
+    }
+
+    #[test]
+    fn init_sol_vault_fail_with_non_canonical_registry_bump() {
+        let (mollusk, _, _) = get_mollusk();
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let amount = LAMPORTS_PER_SOL;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let (vault, _) = client::find_vault_address(&maker, None, amount, unlock_timestamp);
+        let (registry, bump) = non_canonical_address(&[UserRegistry::SEED, maker.as_ref()]);
+        let (config_address, config_account) =
+            get_config(&mollusk, &get_config_data(&Pubkey::new_unique()));
+
+        // a second registry of the maker at another bump
+        let mut instruction =
+            client::init_sol_vault(&maker, amount, unlock_timestamp, 0, &TREASURY);
+        instruction.accounts[3].pubkey = registry;
+        instruction.data[18] = bump;
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (
+                    maker,
+                    Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program),
+                ),
+                (vault, Account::new(0, 0, &system_program)),
+                (system_program, system_account),
+                (registry, Account::new(0, 0, &system_program)),
+                (config_address, config_account.into()),
+                (TREASURY, Account::new(0, 0, &system_program)),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            &[Check::err(ProgramError::Custom(22))], // NonCanonicalBump
+        );
+    }
+
+    #[test]
+    fn init_sol_vault_with_prefunded_registry() {
+        let (mollusk, _, _) = get_mollusk();
+        let (system_program, system_account) =
+            mollusk_svm::program::keyed_account_for_system_program();
+
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let amount = LAMPORTS_PER_SOL;
+        let unlock_timestamp = mollusk.sysvars.clock.unix_timestamp + 3600;
+        let (vault, _) = client::find_vault_address(&maker, None, amount, unlock_timestamp);
+        let (registry, _) = client::find_registry_address(&maker);
+        let (config_address, config_account) =
+            get_config(&mollusk, &get_config_data(&Pubkey::new_unique()));
+        let (_, _, expected_registry) = get_registry(&mollusk, &maker, Some((None, amount)));
+
+        // anyone can send lamports to the registry address before it exists
+        let instruction = client::init_sol_vault(&maker, amount, unlock_timestamp, 0, &TREASURY);
+
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (
+                    maker,
+                    Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program),
+                ),
+                (vault, Account::new(0, 0, &system_program)),
+                (system_program, system_account),
+                (registry, Account::new(1_000, 0, &system_program)),
+                (config_address, config_account.into()),
+                (TREASURY, Account::new(0, 0, &system_program)),
+                (EVENT_AUTHORITY_ID, Account::default()),
+                (
+                    PROGRAM_ID,
+                    mollusk_svm::program::create_program_account_loader_v3(&PROGRAM_ID),
+                ),
+            ],
+            &[
+                Check::success(),
+                Check::account(&registry)
+                    .owner(&PROGRAM_ID)
+                    .lamports(mollusk.sysvars.rent.minimum_balance(UserRegistry::LEN))
+                    .data(expected_registry.data())
+                    .build(),
             ],
         );
     }
+
+    #[test]
+    fn event_layouts_are_stable() {
+        assert_eq!(EVENT_VERSION, 1);
+
+        assert_eq!(VaultCreated::DISCRIMINATOR, 0);
+        assert_eq!(VaultCreated::LEN, 32 + 32 + 33 + 8 + 8 + 8 + 8);
+
+        assert_eq!(VaultWithdrawn::DISCRIMINATOR, 1);
+        assert_eq!(VaultWithdrawn::LEN, 32 + 32 + 33 + 8 + 8 + 8 + 8 + 8);
+
+        assert_eq!(MerkleClaimed::DISCRIMINATOR, 2);
+        assert_eq!(MerkleClaimed::LEN, 32 + 32 + 32 + 8 + 8 + 8);
+
+        assert_eq!(VaultSplit::DISCRIMINATOR, 3);
+        assert_eq!(VaultSplit::LEN, 32 + 32 + 33 + 8 + 8 + 8);
+
+        assert_eq!(VaultsMerged::DISCRIMINATOR, 4);
+        assert_eq!(VaultsMerged::LEN, 32 + 32 + 33 + 8 + 8 + 8);
+
+        assert_eq!(BucketDeposited::DISCRIMINATOR, 5);
+        assert_eq!(BucketDeposited::LEN, 32 + 32 + 32 + 8 + 8 + 8);
+
+        assert_eq!(BucketRedeemed::DISCRIMINATOR, 6);
+        assert_eq!(BucketRedeemed::LEN, 32 + 32 + 32 + 8 + 8);
+
+        assert_eq!(MerkleReclaimed::DISCRIMINATOR, 7);
+        assert_eq!(MerkleReclaimed::LEN, 32 + 32 + 32 + 8 + 8);
+
+        assert_eq!(MerkleVaultCreated::DISCRIMINATOR, 8);
+        assert_eq!(MerkleVaultCreated::LEN, 32 + 32 + 32 + 32 + 8 + 8 + 8);
+    }
+
+    #[test]
+    fn event_authority_matches_seeds() {
+        let (event_authority, bump) =
+            Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &PROGRAM_ID);
+
+        assert_eq!(event_authority, EVENT_AUTHORITY_ID);
+        assert_eq!(bump, EVENT_AUTHORITY_BUMP);
+    }
+
+    #[test]
+    fn emit_event_fail_without_event_authority_signature() {
+        let (mollusk, _, _) = get_mollusk();
+
+        let instruction = Instruction::new_with_bytes(
+            PROGRAM_ID,
+            &[*EmitEvent::DISCRIMINATOR, EVENT_VERSION, 0],
+            vec![AccountMeta::new_readonly(EVENT_AUTHORITY_ID, false)],
+        );
+
+        let _: InstructionResult = mollusk.process_and_validate_instruction(
+            &instruction,
+            &[(EVENT_AUTHORITY_ID, Account::default())],
+            &[Check::err(ProgramError::MissingRequiredSignature)],
+        );
+    }
+
+    #[test]
+    fn client_decodes_vault_account() {
+        let maker = Pubkey::new_from_array([0x02; 32]);
+        let mint = Pubkey::new_unique();
+        let vault_account_data = Vault {
+            owner: maker.to_bytes(),
+            amount: LAMPORTS_PER_SOL.to_le_bytes(),
+            unlock_timestamp: 1_000i64.to_le_bytes(),
+            mint: None,
+            bump: [254],
+            id: [0; 8],
+            early_exit_penalty_bps: 250u16.to_le_bytes(),
+            seed_amount: LAMPORTS_PER_SOL.to_le_bytes(),
+            receipt_mint: None,
+            tracked: [1],
+            withdrawn: 400u64.to_le_bytes(),
+        };
+        let vault_data = unsafe { to_bytes::<Vault>(&vault_account_data) };
+
+        let vault = client::VaultAccount::decode(vault_data).unwrap();
+        assert_eq!(
+            vault,
+            client::VaultAccount {
+                owner: maker,
+                amount: LAMPORTS_PER_SOL,
+                bump: 254,
+                unlock_timestamp: 1_000,
+                mint: None,
+                id: 0,
+                early_exit_penalty_bps: 250,
+                seed_amount: LAMPORTS_PER_SOL,
+                receipt_mint: None,
+                tracked: true,
+                withdrawn: 400,
+            }
+        );
+
+        let mut data = vault_data.to_vec();
+        data[core::mem::offset_of!(Vault, mint)] = 1;
+        data[core::mem::offset_of!(Vault, mint) + 1..][..32].copy_from_slice(mint.as_ref());
+        assert_eq!(
+            client::VaultAccount::decode(&data).unwrap().mint,
+            Some(mint)
+        );
+
+        data[core::mem::offset_of!(Vault, mint)] = 2;
+        assert!(client::VaultAccount::decode(&data).is_err());
+        assert!(client::VaultAccount::decode(&data[1..]).is_err());
+
+        let mut data = vault_data.to_vec();
+        data[core::mem::offset_of!(Vault, receipt_mint)] = 2;
+        assert!(client::VaultAccount::decode(&data).is_err());
+    }
+
+    #[test]
+    fn client_appends_receipt_accounts() {
+        let owner = Pubkey::new_unique();
+        let holder = Pubkey::new_unique();
+
+        let plain = client::init_sol_vault(&owner, LAMPORTS_PER_SOL, 1_000, 0, &TREASURY);
+        let instruction =
+            client::init_sol_vault_with_receipt(&owner, LAMPORTS_PER_SOL, 1_000, 0, &TREASURY);
+        let vault = plain.accounts[1].pubkey;
+        let (receipt_mint, bump) = client::find_receipt_mint_address(&vault);
+
+        assert_eq!(instruction.accounts[..plain.accounts.len()], plain.accounts);
+        assert_eq!(
+            instruction.accounts[plain.accounts.len()].pubkey,
+            receipt_mint
+        );
+        assert_eq!(
+            instruction.accounts[plain.accounts.len() + 1].pubkey,
+            get_associated_token_address(&owner, &receipt_mint)
+        );
+        assert_eq!(instruction.data, [plain.data, vec![bump]].concat());
+
+        let instruction = client::withdraw_sol_vault_with_receipt(&holder, &owner, &vault);
+        assert_eq!(instruction.accounts[0], AccountMeta::new(holder, true));
+        assert_eq!(
+            instruction.accounts[2].pubkey,
+            client::find_registry_address(&owner).0
+        );
+        assert_eq!(
+            instruction.accounts[instruction.accounts.len() - 2].pubkey,
+            get_associated_token_address(&holder, &receipt_mint)
+        );
+    }
+
+    /// Deserializes empty accounts at `keys` from an input buffer laid out
+    /// the way the runtime passes accounts to a program, as the CPI builders
+    /// take account infos.
+    fn account_infos<const N: usize>(
+        keys: [Pubkey; N],
+    ) -> [pinocchio::account_info::AccountInfo; N] {
+        // the account header, the realloc padding and the rent epoch
+        const ACCOUNT_LEN: usize = 88 + 10 * 1024 + 8;
+
+        // u64 words keep the accounts aligned, the instruction data length
+        // and the program id after the accounts are left zeroed
+        let input = vec![0u64; (8 + N * ACCOUNT_LEN + 8 + 32) / 8].leak();
+        let bytes = unsafe {
+            std::slice::from_raw_parts_mut(input.as_mut_ptr() as *mut u8, input.len() * 8)
+        };
+        bytes[..8].copy_from_slice(&(N as u64).to_le_bytes());
+        for (index, key) in keys.iter().enumerate() {
+            let account = &mut bytes[8 + index * ACCOUNT_LEN..];
+            account[0] = u8::MAX; // not a duplicate of an earlier account
+            account[8..40].copy_from_slice(key.as_ref());
+        }
+
+        let mut accounts = [const { std::mem::MaybeUninit::uninit() }; N];
+        let (_, count, _) = unsafe {
+            pinocchio::entrypoint::deserialize::<N>(input.as_mut_ptr() as *mut u8, &mut accounts)
+        };
+        assert_eq!(count, N);
+        accounts.map(|account| unsafe { account.assume_init() })
+    }
+
+    /// Converts the account metas of a CPI builder to the client ones.
+    fn client_metas(metas: &[pinocchio::instruction::AccountMeta]) -> Vec<AccountMeta> {
+        metas
+            .iter()
+            .map(|meta| AccountMeta {
+                pubkey: Pubkey::new_from_array(*meta.pubkey),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cpi_sol_builders_match_client_instructions() {
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+        let owner = Pubkey::new_unique();
+        let amount = LAMPORTS_PER_SOL;
+        let unlock_timestamp = 1_000;
+        let (vault, bump) = client::find_vault_address(&owner, None, amount, unlock_timestamp);
+        let (registry, registry_bump) = client::find_registry_address(&owner);
+
+        let [signer, vault_info, system_program, registry_info, config, treasury, event_authority, program] =
+            account_infos([
+                owner,
+                vault,
+                system_program,
+                registry,
+                client::find_config_address().0,
+                TREASURY,
+                EVENT_AUTHORITY_ID,
+                PROGRAM_ID,
+            ]);
+
+        let instruction = client::init_sol_vault(&owner, amount, unlock_timestamp, 500, &TREASURY);
+        let init = cpi::InitializeSolVault {
+            signer: &signer,
+            vault: &vault_info,
+            system_program: &system_program,
+            registry: &registry_info,
+            config: &config,
+            treasury: &treasury,
+            event_authority: &event_authority,
+            program: &program,
+            amount,
+            unlock_timestamp,
+            bump,
+            registry_bump,
+            early_exit_penalty_bps: 500,
+        };
+        assert_eq!(client_metas(&init.account_metas()), instruction.accounts);
+        assert_eq!(init.instruction_data().to_vec(), instruction.data);
+
+        let instruction = client::withdraw_sol_vault(&owner, &vault);
+        let withdraw = cpi::WithdrawSolVault {
+            signer: &signer,
+            vault: &vault_info,
+            registry: &registry_info,
+            event_authority: &event_authority,
+            program: &program,
+        };
+        assert_eq!(
+            client_metas(&withdraw.account_metas()),
+            instruction.accounts
+        );
+        assert_eq!(withdraw.instruction_data().to_vec(), instruction.data);
+    }
+
+    #[test]
+    fn cpi_spl_builders_match_client_instructions() {
+        let (system_program, _) = mollusk_svm::program::keyed_account_for_system_program();
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let amount = 1_000_000;
+        let unlock_timestamp = 1_000;
+        let (vault, bump) =
+            client::find_vault_address(&owner, Some(&mint), amount, unlock_timestamp);
+        let (registry, registry_bump) = client::find_registry_address(&owner);
+        let (mint_config, mint_config_bump) = client::find_mint_config_address(&mint);
+
+        let [signer, vault_info, mint_info, user_ata, vault_ata, token_program, associated_token_program, system_program, registry_info, config, treasury, treasury_ata, mint_config_info, event_authority, program] =
+            account_infos([
+                owner,
+                vault,
+                mint,
+                get_associated_token_address(&owner, &mint),
+                get_associated_token_address(&vault, &mint),
+                spl_token::ID,
+                spl_associated_token_account::ID,
+                system_program,
+                registry,
+                client::find_config_address().0,
+                TREASURY,
+                get_associated_token_address(&TREASURY, &mint),
+                mint_config,
+                EVENT_AUTHORITY_ID,
+                PROGRAM_ID,
+            ]);
+
+        let instruction =
+            client::init_spl_vault(&owner, &mint, amount, unlock_timestamp, 500, &TREASURY);
+        let init = cpi::InitializeSplVault {
+            signer: &signer,
+            vault: &vault_info,
+            mint: &mint_info,
+            user_ata: &user_ata,
+            vault_ata: &vault_ata,
+            token_program: &token_program,
+            associated_token_program: &associated_token_program,
+            system_program: &system_program,
+            registry: &registry_info,
+            config: &config,
+            treasury: &treasury,
+            treasury_ata: &treasury_ata,
+            mint_config: &mint_config_info,
+            event_authority: &event_authority,
+            program: &program,
+            amount,
+            unlock_timestamp,
+            bump,
+            registry_bump,
+            early_exit_penalty_bps: 500,
+            mint_config_bump,
+        };
+        assert_eq!(client_metas(&init.account_metas()), instruction.accounts);
+        assert_eq!(init.instruction_data().to_vec(), instruction.data);
+
+        let instruction = client::withdraw_spl_vault(&owner, &vault, &mint);
+        let withdraw = cpi::WithdrawSplVault {
+            signer: &signer,
+            vault: &vault_info,
+            mint: &mint_info,
+            user_ata: &user_ata,
+            vault_ata: &vault_ata,
+            token_program: &token_program,
+            system_program: &system_program,
+            registry: &registry_info,
+            event_authority: &event_authority,
+            program: &program,
+        };
+        assert_eq!(
+            client_metas(&withdraw.account_metas()),
+            instruction.accounts
+        );
+        assert_eq!(withdraw.instruction_data().to_vec(), instruction.data);
+    }
+
+    #[test]
+    fn cpi_query_builders_match_client_instructions() {
+        let vault = Pubkey::new_unique();
+        let [vault_info] = account_infos([vault]);
+
+        let instruction = client::get_voting_power(&vault, 20_000, 86_400);
+        let voting_power = cpi::GetVotingPower {
+            vault: &vault_info,
+            max_multiplier_bps: 20_000,
+            max_lock_duration: 86_400,
+        };
+        assert_eq!(
+            client_metas(&voting_power.account_metas()),
+            instruction.accounts
+        );
+        assert_eq!(voting_power.instruction_data().to_vec(), instruction.data);
+
+        let instruction = client::get_vault_status(&vault);
+        let status = cpi::GetVaultStatus { vault: &vault_info };
+        assert_eq!(client_metas(&status.account_metas()), instruction.accounts);
+        assert_eq!(status.instruction_data().to_vec(), instruction.data);
+    }
+
+    /// Checks that `fields` are laid out back to back and fill `len` bytes.
+    fn assert_contiguous(name: &str, fields: &[IdlField], len: usize) {
+        let mut offset = 0;
+        for field in fields {
+            assert_eq!(field.offset, offset, "{name}.{} offset", field.name);
+            offset += field.ty.size();
+        }
+        assert_eq!(offset, len, "{name} length");
+    }
+
+    #[test]
+    fn idl_is_up_to_date() {
+        let generated = idl::generate();
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/idl/pinocchio_timebase_vault.json"
+        );
+        if std::env::var_os("UPDATE_IDL").is_some() {
+            std::fs::write(path, &generated).unwrap();
+        }
+
+        let checked_in = std::fs::read_to_string(path).unwrap();
+        assert!(
+            checked_in == generated,
+            "{path} is out of date, regenerate it with `UPDATE_IDL=1 cargo test idl_is_up_to_date`"
+        );
+    }
+
+    #[test]
+    fn idl_layouts_match_rust_definitions() {
+        let structs: Vec<&IdlStruct> = idl::ACCOUNTS
+            .iter()
+            .chain(idl::TYPES)
+            .copied()
+            .chain(idl::EVENTS.iter().map(|(_, def)| *def))
+            .collect();
+        for def in structs {
+            assert_contiguous(def.name, def.fields, def.len);
+        }
+
+        assert_eq!(Vault::IDL.len, Vault::LEN);
+        assert_eq!(UserRegistry::IDL.len, UserRegistry::LEN);
+        assert_eq!(Config::IDL.len, Config::LEN);
+        assert_eq!(MintConfig::IDL.len, MintConfig::LEN);
+        assert_eq!(VaultCreated::IDL.len, VaultCreated::LEN);
+        assert_eq!(VaultWithdrawn::IDL.len, VaultWithdrawn::LEN);
+        assert_eq!(MerkleVault::IDL.len, MerkleVault::LEN);
+        assert_eq!(ClaimReceipt::IDL.len, ClaimReceipt::LEN);
+        assert_eq!(MerkleClaimed::IDL.len, MerkleClaimed::LEN);
+        assert_eq!(VaultSplit::IDL.len, VaultSplit::LEN);
+        assert_eq!(VaultsMerged::IDL.len, VaultsMerged::LEN);
+        assert_eq!(LockedBucket::IDL.len, LockedBucket::LEN);
+        assert_eq!(BucketDeposited::IDL.len, BucketDeposited::LEN);
+        assert_eq!(BucketRedeemed::IDL.len, BucketRedeemed::LEN);
+        assert_eq!(VotingPower::IDL.len, VotingPower::LEN);
+        assert_eq!(VaultStatus::IDL.len, VaultStatus::LEN);
+
+        for (i, ix) in idl::INSTRUCTIONS.iter().enumerate() {
+            assert_eq!(ix.discriminator as usize, i, "{} discriminator", ix.name);
+        }
+
+        let codes: Vec<u32> = idl::ERRORS.iter().map(|error| error.code).collect();
+        assert_eq!(codes, (0..idl::ERRORS.len() as u32).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn idl_matches_client_instructions() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let vault = Pubkey::new_unique();
+        let settings = ConfigSettings {
+            min_lock_duration: 0,
+            max_lock_duration: 0,
+            fee_bps: 0,
+            flat_fee: 0,
+            treasury: TREASURY,
+            penalty_recipient: PENALTY_RECIPIENT,
+        };
+
+        let cases: Vec<(Instruction, &IdlInstruction)> = vec![
+            (
+                client::init_sol_vault(&owner, 1, 1, 0, &TREASURY),
+                idl::INSTRUCTIONS[0],
+            ),
+            (
+                client::withdraw_sol_vault(&owner, &vault),
+                idl::INSTRUCTIONS[1],
+            ),
+            (
+                client::init_spl_vault(&owner, &mint, 1, 1, 0, &TREASURY),
+                idl::INSTRUCTIONS[2],
+            ),
+            (
+                client::withdraw_spl_vault(&owner, &vault, &mint),
+                idl::INSTRUCTIONS[3],
+            ),
+            (
+                client::initialize_config(&owner, &settings),
+                idl::INSTRUCTIONS[4],
+            ),
+            (
+                client::update_config(&owner, &settings),
+                idl::INSTRUCTIONS[5],
+            ),
+            (client::transfer_admin(&owner, &vault), idl::INSTRUCTIONS[6]),
+            (client::set_paused(&owner, true), idl::INSTRUCTIONS[7]),
+            (
+                client::early_withdraw_sol_vault(&owner, &vault, &PENALTY_RECIPIENT),
+                idl::INSTRUCTIONS[8],
+            ),
+            (
+                client::early_withdraw_spl_vault(&owner, &vault, &mint),
+                idl::INSTRUCTIONS[9],
+            ),
+            (
+                client::set_mint_config(&owner, &mint, 0, 0),
+                idl::INSTRUCTIONS[10],
+            ),
+            (
+                client::set_mint_list_mode(&owner, MintListMode::AllowList),
+                idl::INSTRUCTIONS[11],
+            ),
+            (
+                client::add_mint_to_list(&owner, &mint),
+                idl::INSTRUCTIONS[12],
+            ),
+            (
+                client::remove_mint_from_list(&owner, &mint),
+                idl::INSTRUCTIONS[13],
+            ),
+            (
+                client::init_merkle_vault(&owner, &mint, &[1; 32], 1, 1, &TREASURY),
+                idl::INSTRUCTIONS[17],
+            ),
+            (
+                client::claim_merkle_vault(&owner, &vault, &mint, 1, 1, &[]),
+                idl::INSTRUCTIONS[18],
+            ),
+            (
+                client::split_vault(&owner, &vault, None, &owner, 1, 1),
+                idl::INSTRUCTIONS[19],
+            ),
+            (
+                client::merge_vaults(&owner, &vault, &mint, None),
+                idl::INSTRUCTIONS[20],
+            ),
+            (
+                client::deposit_locked_bucket(&owner, &mint, 1, 1, &TREASURY),
+                idl::INSTRUCTIONS[21],
+            ),
+            (
+                client::redeem_locked_bucket(&owner, &mint, 1, 1),
+                idl::INSTRUCTIONS[22],
+            ),
+            (
+                client::get_voting_power(&vault, MAX_BPS, 1),
+                idl::INSTRUCTIONS[23],
+            ),
+            (client::get_vault_status(&vault), idl::INSTRUCTIONS[24]),
+            (
+                client::reclaim_merkle_vault(&owner, &vault, &mint),
+                idl::INSTRUCTIONS[25],
+            ),
+        ];
+
+        let batch = client::batch_init_spl_vault(
+            &owner,
+            &mint,
+            &[client::BatchVault {
+                beneficiary: vault,
+                amount: 1,
+                unlock_timestamp: 1,
+            }],
+            0,
+            &TREASURY,
+        );
+        let entry = &BatchVaultEntry::IDL;
+        assert_eq!(batch.data[0], BatchInitializeSplVault::IDL.discriminator);
+        assert_contiguous(entry.name, entry.fields, entry.len);
+        assert_eq!(
+            batch.data.len() - 1,
+            BatchInitializeSplVault::IDL
+                .args
+                .iter()
+                .map(|arg| arg.ty.size())
+                .sum::<usize>()
+                + entry.len
+        );
+        assert_eq!(
+            batch.accounts.len(),
+            BatchInitializeSplVault::IDL.accounts.len() + 3
+        );
+
+        let batch = client::batch_withdraw(&owner, &[(vault, None), (vault, Some(mint))], true);
+        let ix = idl::INSTRUCTIONS[16];
+        assert_eq!(batch.data[0], ix.discriminator);
+        assert_contiguous(ix.name, ix.args, batch.data.len() - 1);
+        assert_eq!(batch.accounts.len(), ix.accounts.len() + 1 + 4);
+        for (meta, account) in batch.accounts.iter().zip(ix.accounts) {
+            assert_eq!(meta.is_writable, account.writable, "{}", account.name);
+            assert_eq!(meta.is_signer, account.signer, "{}", account.name);
+        }
+
+        for (instruction, ix) in cases {
+            assert_eq!(instruction.data[0], ix.discriminator, "{}", ix.name);
+            assert_contiguous(ix.name, ix.args, instruction.data.len() - 1);
+
+            assert_eq!(instruction.accounts.len(), ix.accounts.len(), "{}", ix.name);
+            for (meta, account) in instruction.accounts.iter().zip(ix.accounts) {
+                assert_eq!(
+                    meta.is_writable, account.writable,
+                    "{}.{}",
+                    ix.name, account.name
+                );
+                assert_eq!(
+                    meta.is_signer, account.signer,
+                    "{}.{}",
+                    ix.name, account.name
+                );
+            }
+        }
+    }
+
+    fn vault_bytes(owner: &Pubkey, mint: Option<&Pubkey>, unlock_timestamp: i64) -> Vec<u8> {
+        let (_, bump) = client::find_vault_address(owner, mint, LAMPORTS_PER_SOL, unlock_timestamp);
+        let vault = Vault {
+            owner: owner.to_bytes(),
+            amount: LAMPORTS_PER_SOL.to_le_bytes(),
+            bump: [bump],
+            unlock_timestamp: unlock_timestamp.to_le_bytes(),
+            mint: mint.map(|mint| mint.to_bytes()),
+            id: [0; 8],
+            early_exit_penalty_bps: [0; 2],
+            seed_amount: LAMPORTS_PER_SOL.to_le_bytes(),
+            receipt_mint: None,
+            tracked: [1],
+            withdrawn: [0; 8],
+        };
+        unsafe { to_bytes(&vault) }.to_vec()
+    }
+
+    fn matches(filter: &client::filters::Memcmp, data: &[u8]) -> bool {
+        data[filter.offset..].starts_with(&filter.bytes)
+    }
+
+    #[test]
+    fn client_decodes_vault_at_its_address() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let data = vault_bytes(&owner, Some(&mint), 1_000);
+        let (address, _) = client::find_vault_address(&owner, Some(&mint), LAMPORTS_PER_SOL, 1_000);
+
+        assert_eq!(data.len(), client::VAULT_ACCOUNT_LEN);
+        let vault = client::VaultAccount::decode_at(&address, &data).unwrap();
+        assert_eq!(vault.mint, Some(mint));
+        assert_eq!(vault.address().unwrap(), address);
+
+        assert!(client::VaultAccount::decode_at(&Pubkey::new_unique(), &data).is_err());
+    }
+
+    #[test]
+    fn client_filters_select_vaults() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let sol_vault = vault_bytes(&owner, None, 1_000);
+        let spl_vault = vault_bytes(&owner, Some(&mint), 1_000);
+        let other_vault = vault_bytes(&Pubkey::new_unique(), Some(&Pubkey::new_unique()), 1_000);
+
+        let owner_filter = client::filters::owner_filter(&owner);
+        assert!(matches(&owner_filter, &sol_vault));
+        assert!(matches(&owner_filter, &spl_vault));
+        assert!(!matches(&owner_filter, &other_vault));
+
+        let mint_filter = client::filters::mint_filter(Some(&mint));
+        assert!(!matches(&mint_filter, &sol_vault));
+        assert!(matches(&mint_filter, &spl_vault));
+        assert!(!matches(&mint_filter, &other_vault));
+
+        let sol_filter = client::filters::mint_filter(None);
+        assert!(matches(&sol_filter, &sol_vault));
+        assert!(!matches(&sol_filter, &spl_vault));
+    }
+
+    #[test]
+    fn client_unlock_timestamp_filters_cover_range() {
+        let owner = Pubkey::new_unique();
+        let ranges = [
+            (1_757_633_343, 1_757_633_343),
+            (1_757_633_343, 1_757_633_343 + 3600),
+            (1_757_633_343, 1_757_633_343 + 365 * 24 * 3600),
+            (-5, 5),
+            (0, 1 << 55),
+        ];
+
+        for (start, end) in ranges {
+            let filters = client::filters::unlock_timestamp_filters(start, end);
+            assert!(!filters.is_empty());
+            assert!(filters.len() <= client::filters::MAX_UNLOCK_TIMESTAMP_QUERIES);
+
+            for timestamp in [start, start + (end - start) / 2, end] {
+                let data = vault_bytes(&owner, None, timestamp);
+                assert_eq!(
+                    filters
+                        .iter()
+                        .filter(|filter| matches(filter, &data))
+                        .count(),
+                    1,
+                    "{timestamp} in {start}..={end}"
+                );
+            }
+        }
+
+        let filters = client::filters::unlock_timestamp_filters(1_000, 1_000);
+        assert!(!matches(&filters[0], &vault_bytes(&owner, None, 1_001)));
+        assert!(client::filters::unlock_timestamp_filters(1, 0).is_empty());
+    }
 }